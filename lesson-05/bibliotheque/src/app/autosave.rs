@@ -0,0 +1,132 @@
+use super::event::{AppEvent, DomainEvent};
+use super::state::SharedBibliotheque;
+use crate::services::ErreurSauvegarde;
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::Duration;
+
+// Thread de sauvegarde automatique: toutes les `intervalle`, sauvegarde la
+// bibliothèque si elle a des modifications non enregistrées et publie le
+// résultat sur le canal d'événements
+// Rien n'est écrit tant que la bibliothèque n'a été ni chargée ni sauvegardée à la main:
+// le fichier existant (catalogue d'exemple, autre poste) n'est jamais remplacé d'office
+pub fn spawn(
+    biblio: SharedBibliotheque,
+    fichier: String,
+    intervalle: Duration,
+    sender: Sender<AppEvent>,
+) {
+    thread::spawn(move || {
+        loop {
+            thread::sleep(intervalle);
+
+            let event = match sauvegarder_si_modifiee(&biblio, &fichier) {
+                None => continue,
+                Some(event) => event,
+            };
+
+            // Le Receiver a disparu: l'application se termine
+            if sender.send(AppEvent::Domain(event)).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+// Retourne None si rien n'était à sauvegarder
+fn sauvegarder_si_modifiee(biblio: &SharedBibliotheque, fichier: &str) -> Option<DomainEvent> {
+    let mut b = biblio.lock().unwrap();
+    if !b.est_modifiee() || !b.est_synchronisee() {
+        return None;
+    }
+
+    Some(match b.sauvegarder(fichier) {
+        Ok(()) => DomainEvent::Autosaved {
+            fichier: fichier.to_string(),
+        },
+        // Pas d'écrasement automatique: l'utilisateur choisit dans le dialog de conflit
+        Err(ErreurSauvegarde::Conflit { revision }) => DomainEvent::ModifieAilleurs {
+            fichier: fichier.to_string(),
+            revision,
+        },
+        Err(e) => DomainEvent::AutosaveFailed {
+            erreur: e.to_string(),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Auteur;
+    use crate::services::Bibliotheque;
+    use std::sync::{Arc, Mutex};
+
+    fn verne() -> Auteur {
+        Auteur::new(1, "Jules".to_string(), "Verne".to_string())
+    }
+
+    #[test]
+    fn only_saves_when_modified() {
+        let fichier = std::env::temp_dir().join(format!("autosave-{}.json", std::process::id()));
+        let fichier = fichier.to_str().unwrap();
+        let biblio = Arc::new(Mutex::new(Bibliotheque::new()));
+        biblio.lock().unwrap().sauvegarder(fichier).unwrap();
+
+        assert!(sauvegarder_si_modifiee(&biblio, fichier).is_none());
+
+        biblio.lock().unwrap().ajouter_auteur(verne()).unwrap();
+        assert!(matches!(
+            sauvegarder_si_modifiee(&biblio, fichier),
+            Some(DomainEvent::Autosaved { .. })
+        ));
+        assert!(!biblio.lock().unwrap().est_modifiee());
+        assert!(sauvegarder_si_modifiee(&biblio, fichier).is_none());
+
+        let _ = std::fs::remove_file(fichier);
+        let _ = std::fs::remove_file(format!("{}.lock", fichier));
+    }
+
+    #[test]
+    fn never_writes_a_library_that_was_neither_loaded_nor_saved() {
+        let fichier = std::env::temp_dir().join(format!("autosave-jamais-{}.json", std::process::id()));
+        let fichier = fichier.to_str().unwrap();
+        std::fs::write(fichier, "{\"livres\": [], \"auteurs\": []}").unwrap();
+        let biblio = Arc::new(Mutex::new(Bibliotheque::new()));
+
+        biblio.lock().unwrap().ajouter_auteur(verne()).unwrap();
+        assert!(sauvegarder_si_modifiee(&biblio, fichier).is_none());
+        // Le fichier est intact et les modifications restent à sauvegarder
+        assert!(Bibliotheque::charger(fichier).unwrap().get_auteurs().is_empty());
+        assert!(biblio.lock().unwrap().est_modifiee());
+
+        let _ = std::fs::remove_file(fichier);
+    }
+
+    #[test]
+    fn reports_a_save_from_another_process() {
+        let fichier = std::env::temp_dir().join(format!("autosave-conflit-{}.json", std::process::id()));
+        let fichier = fichier.to_str().unwrap();
+        let ailleurs = Arc::new(Mutex::new(Bibliotheque::new()));
+        ailleurs.lock().unwrap().sauvegarder(fichier).unwrap();
+        let ici = Arc::new(Mutex::new(Bibliotheque::charger(fichier).unwrap()));
+
+        ailleurs.lock().unwrap().ajouter_auteur(verne()).unwrap();
+        assert!(matches!(sauvegarder_si_modifiee(&ailleurs, fichier), Some(DomainEvent::Autosaved { .. })));
+
+        ici.lock()
+            .unwrap()
+            .ajouter_auteur(Auteur::new(1, "Victor".to_string(), "Hugo".to_string()))
+            .unwrap();
+        assert!(matches!(
+            sauvegarder_si_modifiee(&ici, fichier),
+            Some(DomainEvent::ModifieAilleurs { revision: 2, .. })
+        ));
+        // Rien n'a été écrasé et les modifications restent à sauvegarder
+        assert!(ici.lock().unwrap().est_modifiee());
+        assert_eq!(Bibliotheque::charger(fichier).unwrap().get_auteurs()[0].nom, "Verne");
+
+        let _ = std::fs::remove_file(fichier);
+        let _ = std::fs::remove_file(format!("{}.lock", fichier));
+    }
+}
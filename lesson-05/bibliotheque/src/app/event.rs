@@ -0,0 +1,119 @@
+use crate::services::metadonnees::Metadonnees;
+use crossterm::event::{self, Event, KeyEvent, KeyEventKind, MouseEvent, MouseEventKind};
+use std::sync::mpsc::{self, Receiver, RecvError, Sender};
+use std::thread;
+use std::time::Duration;
+
+// Tout ce qui peut faire réagir l'application passe par ce type:
+// entrées du terminal, ticks réguliers et événements métier des threads de fond
+#[derive(Debug)]
+pub enum AppEvent {
+    Key(KeyEvent),
+    Mouse(MouseEvent),
+    // Texte collé depuis le terminal (bracketed paste), reçu d'un seul bloc
+    Paste(String),
+    Tick,
+    // La nouvelle taille est lue par ratatui au prochain draw
+    Resize,
+    Domain(DomainEvent),
+}
+
+// Événements métier émis par les threads de travail
+#[derive(Debug, Clone)]
+pub enum DomainEvent {
+    Autosaved { fichier: String },
+    AutosaveFailed { erreur: String },
+    // Fichier enregistré par un autre processus (voir surveillance)
+    ModifieAilleurs { fichier: String, revision: u64 },
+    // Réponse à une recherche de notice lancée par un formulaire
+    Metadonnees {
+        isbn: String,
+        resultat: Result<Option<Metadonnees>, String>,
+    },
+}
+
+// Catégories d'événements auxquelles un écran peut s'abonner
+// (clavier, souris et collage sont toujours envoyés à l'écran au sommet de la pile)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    Tick,
+    Resize,
+    Domain,
+}
+
+impl AppEvent {
+    pub fn kind(&self) -> Option<EventKind> {
+        match self {
+            AppEvent::Key(_) | AppEvent::Mouse(_) | AppEvent::Paste(_) => None,
+            AppEvent::Tick => Some(EventKind::Tick),
+            AppEvent::Resize => Some(EventKind::Resize),
+            AppEvent::Domain(_) => Some(EventKind::Domain),
+        }
+    }
+}
+
+// Canal d'événements de l'application
+// CONCURRENCE: chaque source tourne dans son propre thread et possède un clone
+// du Sender; la boucle principale est le seul Receiver
+pub struct EventHandler {
+    sender: Sender<AppEvent>,
+    receiver: Receiver<AppEvent>,
+}
+
+impl EventHandler {
+    // Démarre les threads d'entrée terminal et de tick
+    pub fn new(tick_rate: Duration) -> Self {
+        let (sender, receiver) = mpsc::channel();
+
+        let input_sender = sender.clone();
+        thread::spawn(move || {
+            loop {
+                // poll avec timeout pour remarquer la fermeture du canal
+                match event::poll(tick_rate) {
+                    Ok(true) => {}
+                    Ok(false) => continue,
+                    Err(_) => break,
+                }
+
+                let app_event = match event::read() {
+                    // Windows envoie aussi les relâchements de touche
+                    Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => AppEvent::Key(key),
+                    // Les simples déplacements du pointeur ne nous intéressent pas
+                    Ok(Event::Mouse(mouse)) if mouse.kind != MouseEventKind::Moved => {
+                        AppEvent::Mouse(mouse)
+                    }
+                    Ok(Event::Paste(texte)) => AppEvent::Paste(texte),
+                    Ok(Event::Resize(_, _)) => AppEvent::Resize,
+                    Ok(_) => continue,
+                    Err(_) => break,
+                };
+
+                if input_sender.send(app_event).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let tick_sender = sender.clone();
+        thread::spawn(move || {
+            loop {
+                thread::sleep(tick_rate);
+                if tick_sender.send(AppEvent::Tick).is_err() {
+                    break;
+                }
+            }
+        });
+
+        EventHandler { sender, receiver }
+    }
+
+    // Sender à donner aux threads de travail pour publier leurs événements
+    pub fn sender(&self) -> Sender<AppEvent> {
+        self.sender.clone()
+    }
+
+    // Bloque jusqu'au prochain événement
+    pub fn next(&self) -> Result<AppEvent, RecvError> {
+        self.receiver.recv()
+    }
+}
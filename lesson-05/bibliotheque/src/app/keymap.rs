@@ -0,0 +1,472 @@
+use crate::i18n::{t, tf};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::fmt;
+
+// Actions nommées déclenchées par les touches
+// Les écrans réagissent aux actions, pas aux touches: les raccourcis se configurent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    // Globales, traitées par App
+    Quit,
+    NextTheme,
+    Help,
+    Palette,
+    // Listes et menus
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Select,
+    Back,
+    // Opérations sur le livre affiché (ou les livres marqués)
+    Mark,
+    Borrow,
+    Return,
+    Edit,
+    Delete,
+    // Formulaires
+    NextField,
+    PrevField,
+    Submit,
+    // Préremplit le formulaire depuis une source externe (ex: notice d'un ISBN)
+    Lookup,
+    // Rapport de l'opération en cours (ex: inventaire)
+    Report,
+    // Restreint la liste affichée (ex: livres d'un rayon)
+    Filter,
+}
+
+// (action, nom dans la configuration, clé de traduction de la description)
+const ACTIONS: &[(Action, &str, &str)] = &[
+    (Action::Quit, "quit", "action.quit"),
+    (Action::NextTheme, "next_theme", "action.next_theme"),
+    (Action::Help, "help", "action.help"),
+    (Action::Palette, "palette", "action.palette"),
+    (Action::Up, "up", "action.up"),
+    (Action::Down, "down", "action.down"),
+    (Action::PageUp, "page_up", "action.page_up"),
+    (Action::PageDown, "page_down", "action.page_down"),
+    (Action::Select, "select", "action.select"),
+    (Action::Back, "back", "action.back"),
+    (Action::Mark, "mark", "action.mark"),
+    (Action::Borrow, "borrow", "action.borrow"),
+    (Action::Return, "return", "action.return"),
+    (Action::Edit, "edit", "action.edit"),
+    (Action::Delete, "delete", "action.delete"),
+    (Action::NextField, "next_field", "action.next_field"),
+    (Action::PrevField, "prev_field", "action.prev_field"),
+    (Action::Submit, "submit", "action.submit"),
+    (Action::Lookup, "lookup", "action.lookup"),
+    (Action::Report, "report", "action.report"),
+    (Action::Filter, "filter", "action.filter"),
+];
+
+impl Action {
+    pub fn from_name(name: &str) -> Option<Action> {
+        ACTIONS.iter().find(|(_, n, _)| *n == name).map(|(a, _, _)| *a)
+    }
+
+    pub fn description(self) -> &'static str {
+        let (_, _, cle) = ACTIONS.iter().find(|(a, _, _)| *a == self).unwrap();
+        t(cle)
+    }
+}
+
+// Où une touche est interprétée: un même caractère navigue dans une liste
+// mais doit être saisi tel quel dans un formulaire
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyContext {
+    // Actif sur tous les écrans
+    Global,
+    List,
+    Form,
+    Dialog,
+}
+
+const CONTEXTS: &[(KeyContext, &str)] = &[
+    (KeyContext::Global, "global"),
+    (KeyContext::List, "list"),
+    (KeyContext::Form, "form"),
+    (KeyContext::Dialog, "dialog"),
+];
+
+impl KeyContext {
+    pub fn from_name(name: &str) -> Option<KeyContext> {
+        CONTEXTS.iter().find(|(_, n)| *n == name).map(|(c, _)| *c)
+    }
+}
+
+// Une touche et ses modificateurs, ex: "ctrl+t", "pagedown", "G"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        KeyBinding { code, modifiers }
+    }
+
+    pub fn parse(texte: &str) -> Result<KeyBinding, String> {
+        let inconnue = || tf("keymap.unknown_key", &[("touche", &texte)]);
+
+        // Le dernier "+" de "ctrl++" est la touche, pas un séparateur
+        let (prefixe, touche) = match texte.strip_suffix("++") {
+            Some(prefixe) => (prefixe, "+"),
+            None if texte == "+" => ("", "+"),
+            None => texte.rsplit_once('+').unwrap_or(("", texte)),
+        };
+        if touche.is_empty() {
+            return Err(inconnue());
+        }
+
+        let mut modifiers = KeyModifiers::NONE;
+        for modificateur in prefixe.split('+').filter(|m| !m.is_empty()) {
+            modifiers |= match modificateur.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(inconnue()),
+            };
+        }
+
+        let code = match touche.to_lowercase().as_str() {
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "pageup" | "pgup" => KeyCode::PageUp,
+            "pagedown" | "pgdn" => KeyCode::PageDown,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "space" => KeyCode::Char(' '),
+            nom if nom.len() > 1 && nom.starts_with('f') => {
+                KeyCode::F(nom[1..].parse().map_err(|_| inconnue())?)
+            }
+            _ => {
+                let mut chars = touche.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return Err(inconnue()),
+                }
+            }
+        };
+
+        Ok(KeyBinding::new(code, normalize(code, modifiers)))
+    }
+
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        self.code == key.code && self.modifiers == normalize(key.code, key.modifiers)
+    }
+}
+
+// Shift est déjà porté par le caractère ('G', '?') ou par BackTab
+fn normalize(code: KeyCode, modifiers: KeyModifiers) -> KeyModifiers {
+    match code {
+        KeyCode::Char(_) | KeyCode::BackTab => modifiers - KeyModifiers::SHIFT,
+        _ => modifiers,
+    }
+}
+
+// Affichage compact pour les barres d'aide: "^T", "PgDn", "↑"
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            if let KeyCode::Char(c) = self.code {
+                return write!(f, "^{}", c.to_ascii_uppercase());
+            }
+            write!(f, "Ctrl+")?;
+        }
+        match self.code {
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Binding {
+    pub context: KeyContext,
+    pub action: Action,
+    pub key: KeyBinding,
+}
+
+// Table touche -> action, par contexte
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+    pub name: String,
+    bindings: Vec<Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap::preset("default").unwrap()
+    }
+}
+
+impl Keymap {
+    pub fn preset(name: &str) -> Option<Keymap> {
+        use Action::*;
+        use KeyContext::*;
+
+        // Communs à tous les préréglages
+        let mut table: Vec<(KeyContext, Action, &[&str])> = vec![
+            (Global, Quit, &["ctrl+c"]),
+            (Global, NextTheme, &["ctrl+t"]),
+            (Global, Help, &["f1"]),
+            (List, Help, &["?"]),
+            (List, Mark, &["space"]),
+            (List, Borrow, &["b"]),
+            (List, Return, &["r"]),
+            (List, Edit, &["e"]),
+            (List, Delete, &["d", "delete"]),
+            (List, Filter, &["f"]),
+            (Dialog, Select, &["enter"]),
+            (Dialog, Back, &["esc"]),
+            (Dialog, Help, &["?"]),
+            (Form, Lookup, &["ctrl+l"]),
+            (Form, Report, &["ctrl+r"]),
+        ];
+
+        let specifiques: Vec<(KeyContext, Action, &[&str])> = match name {
+            "default" => vec![
+                (Global, Palette, &["ctrl+p"]),
+                (List, Up, &["up", "k"]),
+                (List, Down, &["down", "j"]),
+                (List, PageUp, &["pageup"]),
+                (List, PageDown, &["pagedown"]),
+                (List, Select, &["enter"]),
+                (List, Back, &["esc"]),
+                (Form, NextField, &["tab", "down"]),
+                (Form, PrevField, &["backtab", "up"]),
+                (Form, Submit, &["enter"]),
+                (Form, Back, &["esc"]),
+            ],
+            "vim" => vec![
+                (Global, Palette, &["ctrl+p"]),
+                (List, Up, &["k", "up"]),
+                (List, Down, &["j", "down"]),
+                (List, PageUp, &["ctrl+u", "ctrl+b", "pageup"]),
+                (List, PageDown, &["ctrl+d", "ctrl+f", "pagedown"]),
+                (List, Select, &["l", "enter"]),
+                (List, Back, &["h", "q", "esc"]),
+                (Form, NextField, &["tab", "ctrl+j"]),
+                (Form, PrevField, &["backtab", "ctrl+k"]),
+                (Form, Submit, &["enter"]),
+                (Form, Back, &["esc"]),
+                (Dialog, Back, &["q"]),
+            ],
+            // Ctrl+P monte dans les listes: la palette prend M-x
+            "emacs" => vec![
+                (Global, Palette, &["alt+x"]),
+                (List, Up, &["ctrl+p", "up"]),
+                (List, Down, &["ctrl+n", "down"]),
+                (List, PageUp, &["alt+v", "pageup"]),
+                (List, PageDown, &["ctrl+v", "pagedown"]),
+                (List, Select, &["enter"]),
+                (List, Back, &["ctrl+g", "esc"]),
+                (Form, NextField, &["tab", "ctrl+n"]),
+                (Form, PrevField, &["backtab", "ctrl+p"]),
+                (Form, Submit, &["enter"]),
+                (Form, Back, &["ctrl+g", "esc"]),
+                (Dialog, Back, &["ctrl+g"]),
+            ],
+            _ => return None,
+        };
+        table.extend(specifiques);
+
+        let mut keymap = Keymap {
+            name: name.to_string(),
+            bindings: Vec::new(),
+        };
+        for (context, action, keys) in table {
+            for key in keys {
+                let key = KeyBinding::parse(key).expect("touche invalide dans un préréglage");
+                keymap.bindings.push(Binding { context, action, key });
+            }
+        }
+        Some(keymap)
+    }
+
+    // Remplace toutes les touches d'une action dans un contexte
+    pub fn bind(&mut self, context: KeyContext, action: Action, keys: Vec<KeyBinding>) {
+        self.bindings.retain(|b| !(b.context == context && b.action == action));
+        self.bindings
+            .extend(keys.into_iter().map(|key| Binding { context, action, key }));
+    }
+
+    // Action d'une touche: d'abord le contexte de l'écran, puis les raccourcis globaux
+    pub fn action(&self, context: KeyContext, key: &KeyEvent) -> Option<Action> {
+        let trouver = |context: KeyContext| {
+            self.bindings
+                .iter()
+                .find(|b| b.context == context && b.key.matches(key))
+                .map(|b| b.action)
+        };
+        trouver(context).or_else(|| trouver(KeyContext::Global))
+    }
+
+    pub fn keys(&self, context: KeyContext, action: Action) -> Vec<KeyBinding> {
+        let dans = |context: KeyContext| -> Vec<KeyBinding> {
+            self.bindings
+                .iter()
+                .filter(|b| b.context == context && b.action == action)
+                .map(|b| b.key)
+                .collect()
+        };
+        let keys = dans(context);
+        if keys.is_empty() { dans(KeyContext::Global) } else { keys }
+    }
+
+    // Barre d'aide: la première touche de chaque action, ex: "↑/↓:Naviguer"
+    // Les actions sans touche n'apparaissent pas
+    pub fn hints<'a>(
+        &self,
+        context: KeyContext,
+        help: &[(&[Action], &'a str)],
+    ) -> Vec<(String, &'a str)> {
+        help.iter()
+            .filter_map(|(actions, description)| {
+                let keys: Vec<String> = actions
+                    .iter()
+                    .filter_map(|action| self.keys(context, *action).first().map(ToString::to_string))
+                    .collect();
+                (!keys.is_empty()).then(|| (keys.join("/"), *description))
+            })
+            .collect()
+    }
+
+    // Tous les raccourcis actifs dans un contexte (globaux compris), une ligne par action
+    pub fn overview(&self, context: KeyContext) -> Vec<(String, &'static str)> {
+        let mut lignes = Vec::new();
+        for ctx in [context, KeyContext::Global] {
+            for (action, _, _) in ACTIONS {
+                let keys: Vec<String> = self
+                    .bindings
+                    .iter()
+                    .filter(|b| b.context == ctx && b.action == *action)
+                    .map(|b| b.key.to_string())
+                    .collect();
+                let deja = lignes.iter().any(|(_, a)| a == action);
+                if !keys.is_empty() && !deja {
+                    lignes.push((keys.join(", "), *action));
+                }
+            }
+        }
+        lignes
+            .into_iter()
+            .map(|(keys, action)| (keys, action.description()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let cas = [
+            ("ctrl+t", "^T"),
+            ("pagedown", "PgDn"),
+            ("shift+tab", "Shift+Tab"),
+            ("G", "G"),
+            ("alt+v", "Alt+v"),
+            ("f1", "F1"),
+            ("space", "Space"),
+            ("+", "+"),
+            ("ctrl++", "^+"),
+        ];
+        for (texte, affichage) in cas {
+            assert_eq!(KeyBinding::parse(texte).unwrap().to_string(), affichage, "{}", texte);
+        }
+        assert!(KeyBinding::parse("hyper+x").is_err());
+        assert!(KeyBinding::parse("nope").is_err());
+        assert!(KeyBinding::parse("").is_err());
+    }
+
+    #[test]
+    fn shift_is_ignored_for_characters() {
+        let keymap = Keymap::default();
+        let question = KeyEvent::new(KeyCode::Char('?'), KeyModifiers::SHIFT);
+        assert_eq!(keymap.action(KeyContext::List, &question), Some(Action::Help));
+        let backtab = KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT);
+        assert_eq!(keymap.action(KeyContext::Form, &backtab), Some(Action::PrevField));
+    }
+
+    #[test]
+    fn context_bindings_take_precedence_over_global() {
+        let keymap = Keymap::default();
+        assert_eq!(keymap.action(KeyContext::List, &key(KeyCode::Char('j'))), Some(Action::Down));
+        // Dans un formulaire, 'j' est du texte et '?' aussi
+        assert_eq!(keymap.action(KeyContext::Form, &key(KeyCode::Char('j'))), None);
+        assert_eq!(keymap.action(KeyContext::Form, &key(KeyCode::Char('?'))), None);
+        // Les raccourcis globaux restent actifs partout
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(keymap.action(KeyContext::Form, &ctrl_c), Some(Action::Quit));
+    }
+
+    #[test]
+    fn presets_differ() {
+        let vim = Keymap::preset("vim").unwrap();
+        let emacs = Keymap::preset("emacs").unwrap();
+        let ctrl_n = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        assert_eq!(emacs.action(KeyContext::List, &ctrl_n), Some(Action::Down));
+        assert_eq!(vim.action(KeyContext::List, &ctrl_n), None);
+        assert_eq!(vim.action(KeyContext::List, &key(KeyCode::Char('q'))), Some(Action::Back));
+        assert!(Keymap::preset("nano").is_none());
+    }
+
+    #[test]
+    fn bind_replaces_action_keys() {
+        let mut keymap = Keymap::default();
+        keymap.bind(KeyContext::List, Action::Down, vec![KeyBinding::parse("n").unwrap()]);
+        assert_eq!(keymap.action(KeyContext::List, &key(KeyCode::Char('n'))), Some(Action::Down));
+        assert_eq!(keymap.action(KeyContext::List, &key(KeyCode::Char('j'))), None);
+        assert_eq!(keymap.action(KeyContext::List, &key(KeyCode::Down)), None);
+    }
+
+    #[test]
+    fn hints_use_first_key_of_each_action() {
+        let keymap = Keymap::default();
+        let hints = keymap.hints(
+            KeyContext::List,
+            &[(&[Action::Up, Action::Down], "Naviguer"), (&[Action::NextTheme], "Thème")],
+        );
+        assert_eq!(hints, vec![("↑/↓".to_string(), "Naviguer"), ("^T".to_string(), "Thème")]);
+    }
+}
@@ -1,39 +1,78 @@
+pub mod autosave;
+pub mod event;
+pub mod keymap;
+pub mod recherche_isbn;
+pub mod screen;
 pub mod state;
+pub mod surveillance;
 
-use crate::models::{Auteur, Livre};
-use crate::ui::screens::*;
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crate::ui::screens::{CommandPalette, ConflitScreen, KeyHelpScreen, MainMenu};
+use crate::ui::theme::Theme;
+use crossterm::event::{KeyEvent, MouseEvent};
+use event::{AppEvent, DomainEvent};
+use keymap::{Action, KeyContext, Keymap};
 use ratatui::Frame;
-use state::{Screen, SharedBibliotheque};
+use recherche_isbn::RechercheIsbn;
+use screen::{Context, Navigation, Screen};
+use state::SharedBibliotheque;
+
+pub const FICHIER_BIBLIOTHEQUE: &str = "bibliotheque.json";
+pub const FICHIER_CACHE_ISBN: &str = "notices.json";
+// Comptes des opérateurs, à côté du fichier de la bibliothèque
+pub const FICHIER_OPERATEURS: &str = "operateurs.json";
 
 pub struct App {
     pub biblio: SharedBibliotheque,
-    pub screen_stack: Vec<Screen>,
+    pub screen_stack: Vec<Box<dyn Screen>>,
     pub should_quit: bool,
+    // Thèmes disponibles, Ctrl+T passe au suivant
+    pub themes: Vec<Theme>,
+    pub theme_index: usize,
+    pub keymap: Keymap,
+    // Bip demandé par un écran, émis par la boucle principale au prochain tour
+    pub bell: bool,
+    // Recherche de notices par ISBN; None si aucun fournisseur n'est branché
+    pub recherche_isbn: Option<RechercheIsbn>,
+    // Révision pour laquelle le dialog de conflit a déjà été proposé
+    conflit_signale: Option<u64>,
 }
 
 impl App {
+    // Thèmes prédéfinis; main passe par with_themes avec ceux de la configuration
+    #[cfg(test)]
     pub fn new(biblio: SharedBibliotheque) -> Self {
+        Self::with_themes(biblio, Theme::presets(), 0)
+    }
+
+    pub fn with_themes(biblio: SharedBibliotheque, themes: Vec<Theme>, theme_index: usize) -> Self {
         App {
             biblio,
-            screen_stack: vec![Screen::main_menu()],
+            screen_stack: vec![Box::new(MainMenu::new())],
             should_quit: false,
+            themes,
+            theme_index,
+            keymap: Keymap::default(),
+            bell: false,
+            recherche_isbn: None,
+            conflit_signale: None,
         }
     }
 
-    pub fn current_screen(&self) -> &Screen {
-        self.screen_stack
-            .last()
-            .expect("Screen stack should never be empty")
+    pub fn with_keymap(mut self, keymap: Keymap) -> Self {
+        self.keymap = keymap;
+        self
     }
 
-    pub fn current_screen_mut(&mut self) -> &mut Screen {
-        self.screen_stack
-            .last_mut()
-            .expect("Screen stack should never be empty")
+    pub fn with_recherche_isbn(mut self, recherche: RechercheIsbn) -> Self {
+        self.recherche_isbn = Some(recherche);
+        self
+    }
+
+    pub fn next_theme(&mut self) {
+        self.theme_index = (self.theme_index + 1) % self.themes.len();
     }
 
-    pub fn push_screen(&mut self, screen: Screen) {
+    pub fn push_screen(&mut self, screen: Box<dyn Screen>) {
         self.screen_stack.push(screen);
     }
 
@@ -43,333 +82,168 @@ impl App {
         }
     }
 
-    pub fn replace_screen(&mut self, screen: Screen) {
+    pub fn replace_screen(&mut self, screen: Box<dyn Screen>) {
         if !self.screen_stack.is_empty() {
             self.screen_stack.pop();
         }
         self.screen_stack.push(screen);
     }
 
-    pub fn render(&mut self, frame: &mut Frame) {
-        let area = frame.area();
-
-        // Cloner la référence Arc avant le match pour éviter les conflits d'emprunt
-        let biblio = self.biblio.clone();
-
-        let current = self.screen_stack.last_mut().expect("Screen stack should never be empty");
-
-        match current {
-            Screen::MainMenu(state) => {
-                let menu = MainMenu::new(state);
-                frame.render_widget(menu, area);
-            }
-            Screen::AddBook(state) => {
-                let mut screen = AddBookScreen::new(state);
-                screen.render(area, frame.buffer_mut());
-            }
-            Screen::ListBooks(state) => {
-                let screen = ListBooksScreen::new(&biblio, state);
-                frame.render_widget(screen, area);
-            }
-            Screen::BorrowBook(state) => {
-                let mut screen = BorrowBookScreen::new(state);
-                screen.render(area, frame.buffer_mut());
-            }
-            Screen::ReturnBook(state) => {
-                let mut screen = ReturnBookScreen::new(state);
-                screen.render(area, frame.buffer_mut());
-            }
-            Screen::AddAuthor(state) => {
-                let mut screen = AddAuthorScreen::new(state);
-                screen.render(area, frame.buffer_mut());
-            }
-            Screen::ListAuthors(state) => {
-                let screen = ListAuthorsScreen::new(&biblio, state);
-                frame.render_widget(screen, area);
-            }
-            Screen::Message(state) => {
-                let screen = MessageScreen::new(state);
-                frame.render_widget(screen, area);
-            }
-        }
-    }
-
-    pub fn handle_input(&mut self, key: KeyEvent) -> std::io::Result<()> {
-        // Quitter avec Ctrl+C
-        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
-            self.should_quit = true;
-            return Ok(());
-        }
-
-        // Gestion Esc pour retour arrière (sauf pour les messages)
-        if key.code == KeyCode::Esc {
-            match self.current_screen() {
-                Screen::Message(_) => {
-                    self.pop_screen();
-                }
-                _ => {
-                    if self.screen_stack.len() > 1 {
-                        self.pop_screen();
-                    } else {
-                        self.should_quit = true;
-                    }
+    // Applique la commande de navigation renvoyée par un écran
+    fn navigate(&mut self, navigation: Navigation) {
+        match navigation {
+            Navigation::None => {}
+            Navigation::Push(screen) => self.push_screen(screen),
+            Navigation::Pop => self.pop_screen(),
+            Navigation::Replace(screen) => self.replace_screen(screen),
+            Navigation::Global(action) => {
+                self.run_global(action);
+            }
+            Navigation::Sequence(navigations) => {
+                for navigation in navigations {
+                    self.navigate(navigation);
                 }
             }
-            return Ok(());
-        }
-
-        // Enter pour fermer les messages
-        if key.code == KeyCode::Enter {
-            if let Screen::Message(_) = self.current_screen() {
-                self.pop_screen();
-                return Ok(());
-            }
+            Navigation::Bell => self.bell = true,
+            Navigation::Quit => self.should_quit = true,
         }
+    }
 
-        // Cloner la référence Arc avant le match pour éviter les conflits d'emprunt
-        let biblio = self.biblio.clone();
+    // Pile d'écrans et contexte empruntés séparément: un écran de la pile reçoit le
+    // contexte sans que App soit emprunté en entier
+    fn screens_and_context(&mut self) -> (&mut Vec<Box<dyn Screen>>, Context<'_>) {
+        let ctx = Context {
+            biblio: &self.biblio,
+            theme: &self.themes[self.theme_index],
+            keymap: &self.keymap,
+            recherche_isbn: self.recherche_isbn.as_ref(),
+        };
+        (&mut self.screen_stack, ctx)
+    }
 
-        // Dispatcher selon l'écran actuel
-        let current = self.screen_stack.last_mut().expect("Screen stack should never be empty");
+    pub fn render(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let (screens, ctx) = self.screens_and_context();
+        let current = screens.last_mut().expect("Screen stack should never be empty");
+        current.render(area, frame.buffer_mut(), &ctx);
+    }
 
-        match current {
-            Screen::MainMenu(state) => {
-                let mut menu = MainMenu::new(state);
-                match menu.handle_key_event(key) {
-                    MenuAction::AddAuthor => self.push_screen(Screen::add_author()),
-                    MenuAction::AddBook => self.push_screen(Screen::add_book()),
-                    MenuAction::ListBooks => self.push_screen(Screen::list_books()),
-                    MenuAction::BorrowBook => self.push_screen(Screen::borrow_book()),
-                    MenuAction::ReturnBook => self.push_screen(Screen::return_book()),
-                    MenuAction::ListAuthors => self.push_screen(Screen::list_authors()),
-                    MenuAction::Save => self.handle_save(),
-                    MenuAction::Load => self.handle_load(),
-                    MenuAction::Quit => self.should_quit = true,
-                    MenuAction::None => {}
-                }
-            }
-            Screen::AddBook(state) => {
-                let mut screen = AddBookScreen::new(state);
-                if let Some(BookAction::SubmitAdd(values)) = screen.handle_key_event(key) {
-                    self.handle_add_book(values);
-                }
-            }
-            Screen::ListBooks(state) => {
-                let mut screen = ListBooksScreen::new(&biblio, state);
-                screen.handle_key_event(key);
-            }
-            Screen::BorrowBook(state) => {
-                let mut screen = BorrowBookScreen::new(state);
-                if let Some(BookAction::SubmitBorrow(id_str)) = screen.handle_key_event(key) {
-                    self.handle_borrow_book(id_str);
-                }
-            }
-            Screen::ReturnBook(state) => {
-                let mut screen = ReturnBookScreen::new(state);
-                if let Some(BookAction::SubmitReturn(id_str)) = screen.handle_key_event(key) {
-                    self.handle_return_book(id_str);
+    // Point d'entrée de la boucle d'événements
+    pub fn handle_event(&mut self, event: AppEvent) -> std::io::Result<()> {
+        match event.kind() {
+            None => match event {
+                AppEvent::Key(key) => self.handle_input(key)?,
+                AppEvent::Mouse(mouse) => self.handle_mouse(mouse),
+                AppEvent::Paste(texte) => self.handle_paste(&texte),
+                _ => {}
+            },
+            // Les événements de fond sont diffusés à tous les écrans abonnés de la pile
+            Some(kind) => {
+                if let AppEvent::Domain(DomainEvent::ModifieAilleurs { fichier, revision }) = &event {
+                    // Rien de chargé encore (avant la connexion ou le déverrouillage): la
+                    // version en mémoire n'a rien à voir avec le fichier, on l'ignore
+                    if !self.biblio.lock().unwrap().est_synchronisee() {
+                        return Ok(());
+                    }
+                    self.modifie_ailleurs(fichier, *revision);
                 }
-            }
-            Screen::AddAuthor(state) => {
-                let mut screen = AddAuthorScreen::new(state);
-                if let Some(AuthorAction::SubmitAdd(values)) = screen.handle_key_event(key) {
-                    self.handle_add_author(values);
+                let (screens, mut ctx) = self.screens_and_context();
+                for screen in screens {
+                    if screen.subscriptions().contains(&kind) {
+                        screen.handle_event(&event, &mut ctx);
+                    }
                 }
             }
-            Screen::ListAuthors(state) => {
-                let mut screen = ListAuthorsScreen::new(&biblio, state);
-                screen.handle_key_event(key);
-            }
-            Screen::Message(_) => {
-                // Géré plus haut (Esc ou Enter pour fermer)
-            }
         }
-
         Ok(())
     }
 
-    fn handle_add_book(&mut self, values: Vec<String>) {
-        let id = values[0].parse::<u32>().unwrap_or(0);
-        let titre = values[1].clone();
-        let auteur_id = values[2].parse::<u32>().unwrap_or(0);
-        let annee = values[3].parse::<u32>().unwrap_or(0);
-
-        if titre.is_empty() {
-            self.push_screen(Screen::message(
-                "Erreur".to_string(),
-                "Le titre ne peut pas être vide!".to_string(),
-                true,
-            ));
-            return;
-        }
-
-        let livre = Livre::new(id, titre, auteur_id, annee);
+    // Fichier enregistré par un autre processus: rechargé d'office si on n'a rien modifié,
+    // sinon l'utilisateur choisit dans le dialog de conflit (une fois par révision)
+    // Un rechargement qui échoue (fichier chiffré par l'autre poste, illisible) laisse
+    // une révision périmée en mémoire: le dialog s'ouvre aussi, avec l'erreur
+    fn modifie_ailleurs(&mut self, fichier: &str, revision: u64) {
         let mut b = self.biblio.lock().unwrap();
-        b.ajouter_livre(livre);
-
-        match b.associer_livre_auteur(id, auteur_id) {
-            Ok(()) => {
-                drop(b);
-                self.pop_screen();
-                self.push_screen(Screen::message(
-                    "Succès".to_string(),
-                    "Livre ajouté et associé avec succès!".to_string(),
-                    false,
-                ));
-            }
-            Err(e) => {
-                drop(b);
-                self.pop_screen();
-                self.push_screen(Screen::message(
-                    "Attention".to_string(),
-                    format!("Livre ajouté mais: {}", e),
-                    false,
-                ));
-            }
-        }
-    }
-
-    fn handle_borrow_book(&mut self, id_str: String) {
-        let id = match id_str.parse::<u32>() {
-            Ok(id) => id,
-            Err(_) => {
-                self.push_screen(Screen::message(
-                    "Erreur".to_string(),
-                    "ID invalide".to_string(),
-                    true,
-                ));
-                return;
-            }
-        };
-
-        let mut b = self.biblio.lock().unwrap();
-        match b.emprunter_livre(id) {
-            Ok(_) => {
-                drop(b);
-                self.pop_screen();
-                self.push_screen(Screen::message(
-                    "Succès".to_string(),
-                    "Livre emprunté avec succès!".to_string(),
-                    false,
-                ));
-            }
-            Err(e) => {
-                drop(b);
-                self.push_screen(Screen::message(
-                    "Erreur".to_string(),
-                    format!("Erreur: {}", e),
-                    true,
-                ));
-            }
-        }
-    }
-
-    fn handle_return_book(&mut self, id_str: String) {
-        let id = match id_str.parse::<u32>() {
-            Ok(id) => id,
-            Err(_) => {
-                self.push_screen(Screen::message(
-                    "Erreur".to_string(),
-                    "ID invalide".to_string(),
-                    true,
-                ));
-                return;
+        let erreur = if b.est_modifiee() {
+            None
+        } else {
+            match b.recharger(fichier) {
+                Ok(()) => return,
+                Err(e) => Some(e.to_string()),
             }
         };
+        drop(b);
 
-        let mut b = self.biblio.lock().unwrap();
-        match b.retourner_livre(id) {
-            Ok(_) => {
-                drop(b);
-                self.pop_screen();
-                self.push_screen(Screen::message(
-                    "Succès".to_string(),
-                    "Livre retourné avec succès!".to_string(),
-                    false,
-                ));
-            }
-            Err(e) => {
-                drop(b);
-                self.push_screen(Screen::message(
-                    "Erreur".to_string(),
-                    format!("Erreur: {}", e),
-                    true,
-                ));
+        if self.conflit_signale != Some(revision) {
+            self.conflit_signale = Some(revision);
+            let mut conflit = ConflitScreen::new(fichier, revision);
+            if let Some(erreur) = erreur {
+                conflit = conflit.with_erreur_rechargement(erreur);
             }
+            self.push_screen(Box::new(conflit));
         }
     }
 
-    fn handle_add_author(&mut self, values: Vec<String>) {
-        let id = values[0].parse::<u32>().unwrap_or(0);
-        let prenom = values[1].clone();
-        let nom = values[2].clone();
+    pub fn handle_input(&mut self, key: KeyEvent) -> std::io::Result<()> {
+        let current = self.current();
+        let action = self.keymap.action(current.key_context(), &key);
 
-        if prenom.is_empty() || nom.is_empty() {
-            self.push_screen(Screen::message(
-                "Erreur".to_string(),
-                "Le prénom et le nom ne peuvent pas être vides!".to_string(),
-                true,
-            ));
-            return;
+        if let Some(global) = action.filter(|action| !current.captures(*action))
+            && self.run_global(global)
+        {
+            return Ok(());
         }
 
-        let auteur = Auteur::new(id, prenom, nom);
-        let mut b = self.biblio.lock().unwrap();
-        b.ajouter_auteur(auteur);
-        drop(b);
+        let (screens, mut ctx) = self.screens_and_context();
+        let current = screens.last_mut().expect("Screen stack should never be empty");
+        let navigation = current.handle_key(key, action, &mut ctx);
+        self.navigate(navigation);
 
-        self.pop_screen();
-        self.push_screen(Screen::message(
-            "Succès".to_string(),
-            "Auteur ajouté avec succès!".to_string(),
-            false,
-        ));
+        Ok(())
     }
 
-    fn handle_save(&mut self) {
-        let b = self.biblio.lock().unwrap();
-        match b.sauvegarder("bibliotheque.json") {
-            Ok(_) => {
-                drop(b);
-                self.push_screen(Screen::message(
-                    "Succès".to_string(),
-                    "Bibliothèque sauvegardée dans bibliotheque.json".to_string(),
-                    false,
-                ));
-            }
-            Err(e) => {
-                drop(b);
-                self.push_screen(Screen::message(
-                    "Erreur".to_string(),
-                    format!("Erreur lors de la sauvegarde: {}", e),
-                    true,
-                ));
-            }
+    // Actions globales, valables depuis n'importe quel écran
+    // Renvoie false si l'action est laissée à l'écran courant
+    fn run_global(&mut self, action: Action) -> bool {
+        let context = self.current().key_context();
+        match action {
+            Action::Quit => self.should_quit = true,
+            Action::NextTheme => self.next_theme(),
+            // Dans un dialogue (dont l'aide elle-même), l'écran décide
+            Action::Help if context != KeyContext::Dialog => {
+                let aide = KeyHelpScreen::new(self.keymap.overview(context));
+                self.push_screen(Box::new(aide));
+            }
+            Action::Palette if context != KeyContext::Dialog => {
+                let palette = CommandPalette::new(&self.biblio.lock().unwrap());
+                self.push_screen(Box::new(palette));
+            }
+            _ => return false,
         }
+        true
     }
 
-    fn handle_load(&mut self) {
-        use crate::services::Bibliotheque;
+    fn current(&self) -> &dyn Screen {
+        self.screen_stack
+            .last()
+            .expect("Screen stack should never be empty")
+            .as_ref()
+    }
 
-        match Bibliotheque::charger("bibliotheque.json") {
-            Ok(nouvelle_biblio) => {
-                let mut b = self.biblio.lock().unwrap();
-                *b = nouvelle_biblio;
-                drop(b);
-                self.push_screen(Screen::message(
-                    "Succès".to_string(),
-                    "Bibliothèque chargée depuis bibliotheque.json".to_string(),
-                    false,
-                ));
-            }
-            Err(e) => {
-                self.push_screen(Screen::message(
-                    "Erreur".to_string(),
-                    format!("Erreur lors du chargement: {}", e),
-                    true,
-                ));
-            }
-        }
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let (screens, mut ctx) = self.screens_and_context();
+        let current = screens.last_mut().expect("Screen stack should never be empty");
+        let navigation = current.handle_mouse(mouse, &mut ctx);
+        self.navigate(navigation);
+    }
+
+    pub fn handle_paste(&mut self, text: &str) {
+        let (screens, mut ctx) = self.screens_and_context();
+        let current = screens.last_mut().expect("Screen stack should never be empty");
+        let navigation = current.handle_paste(text, &mut ctx);
+        self.navigate(navigation);
     }
 }
 
+#[cfg(test)]
+mod tests;
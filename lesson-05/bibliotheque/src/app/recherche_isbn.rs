@@ -0,0 +1,30 @@
+use super::event::{AppEvent, DomainEvent};
+use crate::services::metadonnees::FournisseurMetadonnees;
+use std::sync::Arc;
+use std::sync::mpsc::Sender;
+use std::thread;
+
+// Recherche de notices en arrière-plan: l'appel réseau ne bloque pas l'interface,
+// la réponse revient sur le canal d'événements (DomainEvent::Metadonnees)
+pub struct RechercheIsbn {
+    fournisseur: Arc<dyn FournisseurMetadonnees>,
+    sender: Sender<AppEvent>,
+}
+
+impl RechercheIsbn {
+    pub fn new(fournisseur: Arc<dyn FournisseurMetadonnees>, sender: Sender<AppEvent>) -> Self {
+        RechercheIsbn { fournisseur, sender }
+    }
+
+    // CONCURRENCE: un thread par recherche; le thread garde un clone de l'Arc
+    // et du Sender, il se termine après avoir publié la réponse
+    pub fn lancer(&self, isbn: String) {
+        let fournisseur = Arc::clone(&self.fournisseur);
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let resultat = fournisseur.chercher(&isbn).map_err(|e| e.to_string());
+            // Le Receiver a disparu: l'application se termine, la réponse est perdue
+            let _ = sender.send(AppEvent::Domain(DomainEvent::Metadonnees { isbn, resultat }));
+        });
+    }
+}
@@ -0,0 +1,97 @@
+use super::event::{AppEvent, EventKind};
+use super::keymap::{Action, KeyContext, Keymap};
+use super::recherche_isbn::RechercheIsbn;
+use super::state::SharedBibliotheque;
+use crate::i18n::t;
+use crate::ui::screens::MessageScreen;
+use crate::ui::theme::Theme;
+use crossterm::event::{KeyEvent, MouseEvent};
+use ratatui::{buffer::Buffer, layout::Rect};
+
+// Ce dont un écran a besoin pour s'afficher et réagir
+pub struct Context<'a> {
+    pub biblio: &'a SharedBibliotheque,
+    pub theme: &'a Theme,
+    pub keymap: &'a Keymap,
+    pub recherche_isbn: Option<&'a RechercheIsbn>,
+}
+
+// Commande de navigation renvoyée par un écran après un événement
+// App l'applique sur la pile d'écrans
+pub enum Navigation {
+    None,
+    Push(Box<dyn Screen>),
+    Pop,
+    // Remplace l'écran courant (ex: fermer un formulaire et afficher le résultat)
+    Replace(Box<dyn Screen>),
+    // Action globale traitée par App (ex: changer de thème depuis la palette)
+    Global(Action),
+    // Plusieurs commandes appliquées dans l'ordre (ex: fermer la palette puis ouvrir un écran)
+    Sequence(Vec<Navigation>),
+    // Bip du terminal (ex: confirmation sonore d'un scan)
+    Bell,
+    Quit,
+}
+
+impl Navigation {
+    pub fn push(screen: impl Screen + 'static) -> Self {
+        Navigation::Push(Box::new(screen))
+    }
+
+    pub fn replace(screen: impl Screen + 'static) -> Self {
+        Navigation::Replace(Box::new(screen))
+    }
+
+    // Dialogue affiché par-dessus l'écran courant
+    pub fn message(title: &str, message: impl Into<String>, is_error: bool) -> Self {
+        Self::push(MessageScreen::new(title, message, is_error))
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::message(t("common.error"), message, true)
+    }
+}
+
+// Un écran de l'application
+// Ajouter un écran = une struct qui implémente ce trait (+ une entrée de menu)
+pub trait Screen {
+    fn title(&self) -> String;
+
+    // Raccourcis affichés dans la barre d'aide: (actions, description)
+    // Les touches affichées viennent du keymap
+    fn help(&self) -> Vec<(&'static [Action], &'static str)>;
+
+    // Contexte dans lequel le keymap interprète les touches de cet écran
+    fn key_context(&self) -> KeyContext {
+        KeyContext::List
+    }
+
+    // Actions globales que l'écran traite lui-même au lieu de laisser faire App
+    // (ex: la touche de la palette la referme au lieu d'en ouvrir une autre)
+    fn captures(&self, _action: Action) -> bool {
+        false
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context);
+
+    // `action` est l'action associée à la touche par le keymap, s'il y en a une
+    // (la touche brute reste utile pour la saisie de texte)
+    fn handle_key(&mut self, key: KeyEvent, action: Option<Action>, ctx: &mut Context) -> Navigation;
+
+    // Les zones cliquables sont celles mémorisées lors du dernier render
+    fn handle_mouse(&mut self, _mouse: MouseEvent, _ctx: &mut Context) -> Navigation {
+        Navigation::None
+    }
+
+    // Texte collé: les écrans de saisie l'insèrent dans le champ actif
+    fn handle_paste(&mut self, _text: &str, _ctx: &mut Context) -> Navigation {
+        Navigation::None
+    }
+
+    // Événements de fond reçus même quand l'écran n'est pas au sommet de la pile
+    fn subscriptions(&self) -> &'static [EventKind] {
+        &[]
+    }
+
+    fn handle_event(&mut self, _event: &AppEvent, _ctx: &mut Context) {}
+}
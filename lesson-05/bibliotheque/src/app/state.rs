@@ -1,15 +1,9 @@
 use crate::services::Bibliotheque;
+use ratatui::layout::{Position, Rect};
 use std::sync::{Arc, Mutex};
 
 pub type SharedBibliotheque = Arc<Mutex<Bibliotheque>>;
 
-// État pour les formulaires
-#[derive(Default)]
-pub struct FormState {
-    pub fields: Vec<String>,
-    pub focused_field: usize,
-}
-
 // État pour les listes
 #[derive(Default)]
 pub struct ListState {
@@ -17,71 +11,52 @@ pub struct ListState {
     pub scroll_offset: usize,
 }
 
-// État pour les messages/dialogs
-pub struct MessageState {
-    pub title: String,
-    pub message: String,
-    pub is_error: bool,
-}
-
-// Différents types d'écrans de l'application
-pub enum Screen {
-    MainMenu(ListState),
-    AddBook(FormState),
-    ListBooks(ListState),
-    BorrowBook(FormState),
-    ReturnBook(FormState),
-    AddAuthor(FormState),
-    ListAuthors(ListState),
-    Message(MessageState),
-}
-
-impl Screen {
-    pub fn main_menu() -> Self {
-        Screen::MainMenu(ListState::default())
-    }
-
-    pub fn add_book() -> Self {
-        Screen::AddBook(FormState {
-            fields: vec![String::new(); 4], // ID, Titre, Auteur ID, Année
-            focused_field: 0,
-        })
-    }
-
-    pub fn list_books() -> Self {
-        Screen::ListBooks(ListState::default())
-    }
-
-    pub fn borrow_book() -> Self {
-        Screen::BorrowBook(FormState {
-            fields: vec![String::new(); 1], // ID du livre
-            focused_field: 0,
-        })
-    }
-
-    pub fn return_book() -> Self {
-        Screen::ReturnBook(FormState {
-            fields: vec![String::new(); 1], // ID du livre
-            focused_field: 0,
-        })
-    }
-
-    pub fn add_author() -> Self {
-        Screen::AddAuthor(FormState {
-            fields: vec![String::new(); 3], // ID, Prénom, Nom
-            focused_field: 0,
-        })
-    }
-
-    pub fn list_authors() -> Self {
-        Screen::ListAuthors(ListState::default())
+impl ListState {
+    // Déplace la sélection sans boucler (PgUp/PgDn, molette)
+    pub fn move_by(&mut self, delta: isize, count: usize) {
+        if count == 0 {
+            return;
+        }
+        self.selected = self.selected.saturating_add_signed(delta).min(count - 1);
+    }
+
+    // Ajuste scroll_offset pour garder l'élément sélectionné visible
+    pub fn scroll_into_view(&mut self, visible_lines: usize) {
+        if visible_lines == 0 {
+            return;
+        }
+
+        if self.selected >= self.scroll_offset + visible_lines {
+            // Scroll down
+            self.scroll_offset = self.selected.saturating_sub(visible_lines - 1);
+        } else if self.selected < self.scroll_offset {
+            // Scroll up
+            self.scroll_offset = self.selected;
+        }
+    }
+
+    // Recale l'état sur la taille réelle de la liste et de la zone d'affichage
+    // (appelé au rendu: suit les redimensionnements et les suppressions)
+    pub fn fit(&mut self, count: usize, visible_lines: usize) {
+        self.selected = self.selected.min(count.saturating_sub(1));
+        self.scroll_into_view(visible_lines);
+        // Pas de lignes vides en bas si la fenêtre s'est agrandie
+        self.scroll_offset = self.scroll_offset.min(count.saturating_sub(visible_lines));
+    }
+
+    // Index de l'élément affiché sous le pointeur, dans la zone où la liste a été rendue
+    pub fn item_at(&self, area: Rect, column: u16, row: u16) -> Option<usize> {
+        if !area.contains(Position::new(column, row)) {
+            return None;
+        }
+        Some(self.scroll_offset + (row - area.y) as usize)
     }
+}
 
-    pub fn message(title: String, message: String, is_error: bool) -> Self {
-        Screen::Message(MessageState {
-            title,
-            message,
-            is_error,
-        })
-    }
+// Ligne d'information temporaire (ex: sauvegarde automatique)
+pub struct StatusLine {
+    pub text: String,
+    pub is_error: bool,
+    // Nombre de ticks restants avant effacement
+    pub ticks_left: u16,
 }
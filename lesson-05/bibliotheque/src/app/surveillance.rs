@@ -0,0 +1,98 @@
+use super::event::{AppEvent, DomainEvent};
+use super::state::SharedBibliotheque;
+use crate::services::stockage::{self, Empreinte};
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::Duration;
+
+// Surveillance du fichier de la bibliothèque: un autre processus qui l'enregistre
+// change sa date de modification et sa révision
+pub struct Surveillance {
+    fichier: PathBuf,
+    // Empreinte lors de la dernière vérification
+    empreinte: Option<Empreinte>,
+}
+
+impl Surveillance {
+    pub fn new(fichier: &str) -> Self {
+        let fichier = PathBuf::from(fichier);
+        Surveillance {
+            empreinte: stockage::empreinte(&fichier),
+            fichier,
+        }
+    }
+
+    // Some si le fichier a changé depuis la dernière vérification et porte une autre
+    // révision que la bibliothèque en mémoire (nos propres sauvegardes n'en portent pas d'autre)
+    pub fn verifier(&mut self, biblio: &SharedBibliotheque) -> Option<DomainEvent> {
+        let empreinte = stockage::empreinte(&self.fichier);
+        if empreinte == self.empreinte {
+            return None;
+        }
+        self.empreinte = empreinte;
+
+        let contenu = stockage::lire_si_existe(&self.fichier).ok()??;
+        let revision = stockage::revision(&contenu).ok()?;
+        if revision == biblio.lock().unwrap().revision() {
+            return None;
+        }
+        Some(DomainEvent::ModifieAilleurs {
+            fichier: self.fichier.display().to_string(),
+            revision,
+        })
+    }
+}
+
+// Thread qui vérifie le fichier toutes les `intervalle`
+pub fn spawn(biblio: SharedBibliotheque, fichier: String, intervalle: Duration, sender: Sender<AppEvent>) {
+    thread::spawn(move || {
+        let mut surveillance = Surveillance::new(&fichier);
+        loop {
+            thread::sleep(intervalle);
+
+            let Some(event) = surveillance.verifier(&biblio) else {
+                continue;
+            };
+            // Le Receiver a disparu: l'application se termine
+            if sender.send(AppEvent::Domain(event)).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Auteur;
+    use crate::services::Bibliotheque;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn signals_saves_from_other_processes_only() {
+        let fichier = std::env::temp_dir().join(format!("surveillance-{}.json", std::process::id()));
+        let fichier = fichier.to_str().unwrap();
+        let biblio = Arc::new(Mutex::new(Bibliotheque::new()));
+        let mut surveillance = Surveillance::new(fichier);
+        assert!(surveillance.verifier(&biblio).is_none());
+
+        // Notre propre sauvegarde
+        biblio.lock().unwrap().sauvegarder(fichier).unwrap();
+        assert!(surveillance.verifier(&biblio).is_none());
+
+        // Un autre processus repart du fichier et l'enregistre
+        let mut ailleurs = Bibliotheque::charger(fichier).unwrap();
+        ailleurs.ajouter_auteur(Auteur::new(1, "Jules".to_string(), "Verne".to_string())).unwrap();
+        ailleurs.sauvegarder(fichier).unwrap();
+        assert!(matches!(
+            surveillance.verifier(&biblio),
+            Some(DomainEvent::ModifieAilleurs { revision: 2, .. })
+        ));
+        // Signalé une seule fois
+        assert!(surveillance.verifier(&biblio).is_none());
+
+        let _ = std::fs::remove_file(fichier);
+        let _ = std::fs::remove_file(format!("{}.lock", fichier));
+    }
+}
@@ -0,0 +1,1853 @@
+// Harnais de test de l'interface: on pilote `App::handle_input` avec des
+// séquences de touches scriptées, puis on compare le `Buffer` rendu par un
+// `TestBackend` à des snapshots (dossier `snapshots/`, géré par insta).
+//
+// Pour mettre à jour les snapshots après un changement volontaire de l'UI:
+//   cargo insta review   (ou INSTA_UPDATE=always cargo test)
+
+use super::App;
+use super::event::{AppEvent, DomainEvent};
+use super::keymap::{Action, KeyBinding, KeyContext, Keymap};
+use super::recherche_isbn::RechercheIsbn;
+use crate::models::{Auteur, Emplacement, Emprunt, Livre, Rayon, Role, Serie, Session, Tome};
+use crate::i18n::{LangueDeTest, Locale};
+use crate::services::Bibliotheque;
+use crate::services::metadonnees::FichierNotices;
+use crate::services::operateurs::Operateurs;
+use crate::ui::screens::{AddOperatorScreen, LoginScreen, PassphraseScreen};
+use crate::ui::theme::Theme;
+use chrono::NaiveDate;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use insta::assert_snapshot;
+use ratatui::{Terminal, backend::TestBackend};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const WIDTH: u16 = 80;
+const HEIGHT: u16 = 24;
+
+// Index des entrées du menu principal (voir MENU_ITEMS)
+const MENU_ADD_AUTHOR: usize = 0;
+const MENU_ADD_BOOK: usize = 1;
+const MENU_LIST_BOOKS: usize = 2;
+const MENU_BORROW_BOOK: usize = 3;
+const MENU_RETURN_BOOK: usize = 4;
+const MENU_SCAN: usize = 5;
+const MENU_INVENTORY: usize = 6;
+const MENU_SHELF_READ: usize = 7;
+const MENU_LIST_AUTHORS: usize = 8;
+const MENU_REPORTS: usize = 9;
+const MENU_LABELS: usize = 10;
+const MENU_OPERATORS: usize = 11;
+const MENU_ENCRYPTION: usize = 12;
+const MENU_LOAD: usize = 14;
+
+struct Harness {
+    app: App,
+    terminal: Terminal<TestBackend>,
+    // Événements publiés par les threads de fond (recherche de notices)
+    evenements: Option<Receiver<AppEvent>>,
+    // Langue des textes attendus, tenue pendant tout le test
+    _langue: LangueDeTest,
+}
+
+impl Harness {
+    fn new() -> Self {
+        Self::with_biblio(fixture())
+    }
+
+    fn with_biblio(biblio: Bibliotheque) -> Self {
+        Self::with_size(biblio, WIDTH, HEIGHT)
+    }
+
+    fn with_size(biblio: Bibliotheque, width: u16, height: u16) -> Self {
+        Self::with_langue(biblio, width, height, LangueDeTest::francais())
+    }
+
+    // Écrans dans une autre langue: les autres tests attendent la fin de celui-ci
+    fn with_locale(locale: Locale) -> Self {
+        Self::with_langue(fixture(), WIDTH, HEIGHT, LangueDeTest::fixer(locale))
+    }
+
+    fn with_langue(biblio: Bibliotheque, width: u16, height: u16, langue: LangueDeTest) -> Self {
+        let terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        Harness {
+            app: App::new(Arc::new(Mutex::new(biblio))),
+            terminal,
+            evenements: None,
+            _langue: langue,
+        }
+    }
+
+    // Notices lues dans le fichier de réponses enregistrées d'Open Library
+    fn with_notices() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let fournisseur = FichierNotices::new(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/openlibrary.json"));
+        let mut h = Self::new();
+        h.app = h.app.with_recherche_isbn(RechercheIsbn::new(Arc::new(fournisseur), sender));
+        h.evenements = Some(receiver);
+        h
+    }
+
+    // Attend le prochain événement d'un thread de fond et le transmet à l'application
+    fn attendre_evenement(&mut self) -> &mut Self {
+        let event = self
+            .evenements
+            .as_ref()
+            .expect("harnais sans threads de fond")
+            .recv_timeout(Duration::from_secs(5))
+            .expect("aucun événement reçu");
+        self.send(event)
+    }
+
+    fn press(&mut self, code: KeyCode) -> &mut Self {
+        self.press_with(code, KeyModifiers::NONE)
+    }
+
+    fn press_with(&mut self, code: KeyCode, modifiers: KeyModifiers) -> &mut Self {
+        self.app
+            .handle_input(KeyEvent::new(code, modifiers))
+            .expect("handle_input ne devrait pas échouer");
+        self
+    }
+
+    fn send(&mut self, event: AppEvent) -> &mut Self {
+        self.app
+            .handle_event(event)
+            .expect("handle_event ne devrait pas échouer");
+        self
+    }
+
+    // Collage depuis le terminal (bracketed paste)
+    fn paste(&mut self, text: &str) -> &mut Self {
+        self.send(AppEvent::Paste(text.to_string()))
+    }
+
+    // Les zones cliquables viennent du rendu: on dessine avant chaque événement souris
+    fn mouse(&mut self, kind: MouseEventKind, column: u16, row: u16) -> &mut Self {
+        self.render();
+        self.send(AppEvent::Mouse(MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }))
+    }
+
+    fn click(&mut self, column: u16, row: u16) -> &mut Self {
+        self.mouse(MouseEventKind::Down(MouseButton::Left), column, row)
+    }
+
+    fn type_text(&mut self, text: &str) -> &mut Self {
+        for c in text.chars() {
+            self.press(KeyCode::Char(c));
+        }
+        self
+    }
+
+    // Remplit les champs d'un formulaire dans l'ordre, Tab entre chaque champ
+    fn fill(&mut self, values: &[&str]) -> &mut Self {
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 {
+                self.press(KeyCode::Tab);
+            }
+            self.type_text(value);
+        }
+        self
+    }
+
+    fn open_menu_item(&mut self, index: usize) -> &mut Self {
+        for _ in 0..index {
+            self.press(KeyCode::Down);
+        }
+        self.press(KeyCode::Enter)
+    }
+
+    fn render(&mut self) -> &TestBackend {
+        self.terminal.draw(|f| self.app.render(f)).unwrap();
+        self.terminal.backend()
+    }
+
+    // Titre de l'écran au sommet de la pile
+    fn title(&self) -> String {
+        self.app.screen_stack.last().unwrap().title()
+    }
+
+    // Contenu texte du dernier rendu, pour les assertions sans snapshot
+    fn rendered_text(&mut self) -> String {
+        let buffer = self.render().buffer().clone();
+        buffer.content().iter().map(|cell| cell.symbol()).collect()
+    }
+
+    fn livre(&self, id: u32) -> Livre {
+        let b = self.app.biblio.lock().unwrap();
+        b.get_livres().iter().find(|l| l.id == id).cloned().unwrap()
+    }
+}
+
+// Petite bibliothèque déterministe utilisée par tous les tests
+fn fixture() -> Bibliotheque {
+    let mut b = Bibliotheque::new();
+    b.ajouter_auteur(Auteur::new(1, "Antoine".to_string(), "de Saint-Exupery".to_string())).unwrap();
+    b.ajouter_auteur(Auteur::new(2, "Frank".to_string(), "Herbert".to_string())).unwrap();
+
+    b.ajouter_livre(Livre::new(1, "Le Petit Prince".to_string(), 1, 1943)).unwrap();
+    b.associer_livre_auteur(1, 1).unwrap();
+
+    // Un emprunt rendu, puis un emprunt en cours (en retard depuis longtemps)
+    let date = |mois, jour| NaiveDate::from_ymd_opt(2024, mois, jour).unwrap();
+    let mut vol_de_nuit = Livre::new(2, "Vol de nuit".to_string(), 1, 1931);
+    vol_de_nuit.emprunte = true;
+    vol_de_nuit.historique = vec![
+        Emprunt {
+            retour: Some(date(1, 20)),
+            ..Emprunt::new(date(1, 5))
+        },
+        Emprunt::new(date(3, 1)),
+    ];
+    b.ajouter_livre(vol_de_nuit).unwrap();
+    b.associer_livre_auteur(2, 1).unwrap();
+
+    b
+}
+
+const TITLE_MAIN_MENU: &str = "Menu Principal";
+const TITLE_ADD_AUTHOR: &str = "✍️  Ajouter un auteur";
+const TITLE_ADD_BOOK: &str = "📚 Ajouter un livre";
+const TITLE_LIST_BOOKS: &str = "📖 Liste des livres";
+const TITLE_BORROW_BOOK: &str = "✋ Emprunter un livre";
+const TITLE_BOOK_DETAIL: &str = "📋 Fiche du livre";
+const TITLE_KEY_HELP: &str = "⌨️  Raccourcis clavier";
+const TITLE_PALETTE: &str = "🔎 Palette de commandes";
+
+// --- Navigation dans le menu ---
+
+#[test]
+fn main_menu_initial_render() {
+    let mut h = Harness::new();
+    assert_snapshot!(h.render());
+}
+
+#[test]
+fn main_menu_moves_selection_down_and_up() {
+    let mut h = Harness::new();
+    h.press(KeyCode::Down).press(KeyCode::Char('j'));
+    assert_snapshot!(h.render());
+
+    h.press(KeyCode::Up).press(KeyCode::Char('k')).press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_ADD_AUTHOR);
+}
+
+#[test]
+fn main_menu_wraps_around() {
+    let mut h = Harness::new();
+    h.press(KeyCode::Up).press(KeyCode::Down).press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_ADD_AUTHOR);
+}
+
+#[test]
+fn main_menu_scrolls_to_keep_the_selection_visible() {
+    let mut h = Harness::with_size(fixture(), WIDTH, 16);
+    h.press(KeyCode::Up);
+    assert_snapshot!(h.render());
+
+    h.press(KeyCode::Down);
+    assert_snapshot!(h.render());
+}
+
+#[test]
+fn esc_goes_back_then_quits_from_main_menu() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_LIST_BOOKS);
+    assert_eq!(h.title(), TITLE_LIST_BOOKS);
+
+    h.press(KeyCode::Esc);
+    assert_eq!(h.title(), TITLE_MAIN_MENU);
+    assert!(!h.app.should_quit);
+
+    h.press(KeyCode::Esc);
+    assert!(h.app.should_quit);
+}
+
+#[test]
+fn ctrl_c_quits_from_any_screen() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_ADD_BOOK);
+    h.press_with(KeyCode::Char('c'), KeyModifiers::CONTROL);
+    assert!(h.app.should_quit);
+}
+
+#[test]
+fn quit_menu_entry_quits() {
+    let mut h = Harness::new();
+    h.press(KeyCode::Up).press(KeyCode::Enter);
+    assert!(h.app.should_quit);
+}
+
+// --- Listes ---
+
+#[test]
+fn list_books_render() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_LIST_BOOKS).press(KeyCode::Down);
+    assert_snapshot!(h.render());
+}
+
+#[test]
+fn list_books_empty_render() {
+    let mut h = Harness::with_biblio(Bibliotheque::new());
+    h.open_menu_item(MENU_LIST_BOOKS).press(KeyCode::Down);
+    assert_snapshot!(h.render());
+}
+
+#[test]
+fn list_authors_render() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_LIST_AUTHORS).press(KeyCode::Down);
+    assert_snapshot!(h.render());
+}
+
+// --- Fiche d'un livre ---
+
+#[test]
+fn enter_on_book_opens_detail_with_history() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_LIST_BOOKS)
+        .press(KeyCode::Down)
+        .press(KeyCode::Enter);
+
+    assert_eq!(h.title(), TITLE_BOOK_DETAIL);
+    assert_snapshot!(h.render());
+
+    h.press(KeyCode::Esc);
+    assert_eq!(h.title(), TITLE_LIST_BOOKS);
+}
+
+#[test]
+fn borrow_and_return_from_detail() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_LIST_BOOKS).press(KeyCode::Enter);
+    assert!(h.rendered_text().contains("Jamais emprunté"));
+
+    h.press(KeyCode::Char('b'));
+    assert_eq!(h.title(), "Succès");
+    // Le message fermé, la fiche est toujours là et montre l'emprunt
+    h.press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_BOOK_DETAIL);
+    assert!(h.rendered_text().contains("à rendre le"));
+    assert!(h.livre(1).emprunt_en_cours().is_some());
+
+    h.press(KeyCode::Char('b'));
+    assert_eq!(h.title(), "Erreur");
+    h.press(KeyCode::Enter).press(KeyCode::Char('r'));
+    assert_eq!(h.title(), "Succès");
+
+    let livre = h.livre(1);
+    assert!(!livre.emprunte);
+    assert_eq!(livre.historique.len(), 1);
+    assert!(livre.historique[0].retour.is_some());
+}
+
+#[test]
+fn edit_book_from_detail() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_LIST_BOOKS).press(KeyCode::Enter).press(KeyCode::Char('e'));
+    assert_eq!(h.title(), "📝 Modifier le livre");
+    assert!(h.rendered_text().contains("Antoine de Saint-Exupery"));
+
+    // Titre, auteur (inchangé), année
+    h.type_text(" (illustré)").press(KeyCode::Tab).press(KeyCode::Tab);
+    for _ in 0..4 {
+        h.press(KeyCode::Backspace);
+    }
+    h.type_text("1946").press(KeyCode::Enter);
+    assert_eq!(h.title(), "Succès");
+
+    let livre = h.livre(1);
+    assert_eq!(livre.titre, "Le Petit Prince (illustré)");
+    assert_eq!(livre.annee, 1946);
+    assert_eq!(livre.auteur_id, 1);
+
+    h.press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_BOOK_DETAIL);
+    assert!(h.rendered_text().contains("Le Petit Prince (illustré)"));
+}
+
+#[test]
+fn edit_book_can_change_author() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_LIST_BOOKS).press(KeyCode::Enter).press(KeyCode::Char('e'));
+    h.press(KeyCode::Tab);
+    for _ in 0.."Antoine de Saint-Exupery".len() {
+        h.press(KeyCode::Backspace);
+    }
+    h.type_text("herb").press(KeyCode::Tab).press(KeyCode::Enter);
+    assert_eq!(h.title(), "Succès");
+
+    assert_eq!(h.livre(1).auteur_id, 2);
+    let b = h.app.biblio.lock().unwrap();
+    assert_eq!(b.get_auteurs()[0].livres, vec![2]);
+    assert_eq!(b.get_auteurs()[1].livres, vec![1]);
+}
+
+#[test]
+fn refused_edit_leaves_no_new_author_or_series() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_LIST_BOOKS).press(KeyCode::Enter).press(KeyCode::Char('e'));
+    h.press(KeyCode::Tab);
+    for _ in 0.."Antoine de Saint-Exupery".len() {
+        h.press(KeyCode::Backspace);
+    }
+    h.type_text("Isaac Asimov").press(KeyCode::Enter);
+    h.fill(&["", "", "", "Fondation"]).press(KeyCode::Enter).press(KeyCode::Tab).type_text("1");
+    // Livre supprimé entre-temps (ex: par un autre poste): la modification échoue
+    h.app.biblio.lock().unwrap().supprimer_livre(1).unwrap();
+    h.press(KeyCode::Enter);
+
+    assert_eq!(h.title(), "Erreur");
+    let b = h.app.biblio.lock().unwrap();
+    assert_eq!(b.get_auteurs().len(), 2);
+    assert!(b.get_series().is_empty());
+}
+
+#[test]
+fn delete_book_asks_for_confirmation() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_LIST_BOOKS).press(KeyCode::Enter).press(KeyCode::Char('d'));
+    assert_eq!(h.title(), "Supprimer le livre");
+    assert_snapshot!(h.render());
+
+    // Esc annule
+    h.press(KeyCode::Esc);
+    assert_eq!(h.title(), TITLE_BOOK_DETAIL);
+    assert_eq!(h.app.biblio.lock().unwrap().get_livres().len(), 2);
+
+    h.press(KeyCode::Char('d')).press(KeyCode::Enter);
+    assert_eq!(h.title(), "Succès");
+    assert!(h.rendered_text().contains("Livre « Le Petit Prince » supprimé"));
+    assert_eq!(h.app.biblio.lock().unwrap().get_livres().len(), 1);
+
+    // La fiche du livre supprimé est fermée: retour à la liste
+    h.press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_LIST_BOOKS);
+}
+
+// --- Opérations depuis la liste ---
+
+#[test]
+fn mark_books_and_borrow_in_batch() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_LIST_BOOKS)
+        .press(KeyCode::Char(' '))
+        .press(KeyCode::Char(' '));
+    let texte = h.rendered_text();
+    assert!(texte.contains("(2 marqués)"));
+    assert!(texte.contains("[x] #1 - Le Petit Prince"));
+    assert!(texte.contains("[x] #2 - Vol de nuit"));
+
+    // Le livre 1 est emprunté, le livre 2 l'était déjà
+    h.press(KeyCode::Char('b'));
+    assert_eq!(h.title(), "Emprunts");
+    assert_snapshot!(h.render());
+    assert!(h.livre(1).emprunte);
+
+    // Le bilan fermé, les marques sont effacées
+    h.press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_LIST_BOOKS);
+    assert!(!h.rendered_text().contains("[x]"));
+}
+
+#[test]
+fn space_toggles_mark() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_LIST_BOOKS).press(KeyCode::Char(' '));
+    assert!(h.rendered_text().contains("(1 marqué)"));
+
+    h.press(KeyCode::Up).press(KeyCode::Char(' '));
+    assert!(!h.rendered_text().contains("marqué"));
+}
+
+#[test]
+fn borrow_and_return_selected_book_from_list() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_LIST_BOOKS).press(KeyCode::Char('b'));
+    assert_eq!(h.title(), "Succès");
+    assert!(h.livre(1).emprunte);
+
+    h.press(KeyCode::Enter).press(KeyCode::Down).press(KeyCode::Char('r'));
+    assert_eq!(h.title(), "Succès");
+    assert!(!h.livre(2).emprunte);
+
+    // Un retour groupé qui échoue pour un livre le signale dans le bilan
+    h.press(KeyCode::Enter)
+        .press(KeyCode::Up)
+        .press(KeyCode::Char(' '))
+        .press(KeyCode::Char(' '))
+        .press(KeyCode::Char('r'));
+    assert_eq!(h.title(), "Retours");
+    let texte = h.rendered_text();
+    assert!(texte.contains("1 réussi, 1 échec"));
+    assert!(texte.contains("#2 Vol de nuit: Ce livre n'est pas emprunté"));
+}
+
+// --- Mode scan ---
+
+// Fixture où le Petit Prince a un ISBN, en deux exemplaires pour l'un des tests
+fn fixture_isbn(exemplaires: u32) -> Bibliotheque {
+    let mut b = fixture();
+    for id in 0..exemplaires {
+        b.ajouter_livre(Livre {
+            isbn: Some("9782070612758".to_string()),
+            ..Livre::new(10 + id, "Le Petit Prince (poche)".to_string(), 1, 1999)
+        })
+        .unwrap();
+    }
+    b
+}
+
+#[test]
+fn scan_toggles_borrow_and_return() {
+    let mut h = Harness::with_biblio(fixture_isbn(1));
+    h.open_menu_item(MENU_SCAN);
+    assert_eq!(h.title(), "📷 Mode scan");
+
+    // Le scanner tape l'ISBN avec des tirets puis Entrée
+    h.type_text("978-2-07-061275-8").press(KeyCode::Enter);
+    assert!(h.livre(10).emprunte);
+    assert!(h.app.bell);
+
+    // ID d'exemplaire: Vol de nuit était emprunté, il est retourné
+    h.type_text("2").press(KeyCode::Enter);
+    assert!(!h.livre(2).emprunte);
+
+    h.type_text("42").press(KeyCode::Enter);
+    assert_snapshot!(h.render());
+
+    // Un second passage du même livre le retourne
+    h.type_text("9782070612758").press(KeyCode::Enter);
+    assert!(!h.livre(10).emprunte);
+    assert!(h.rendered_text().contains("(1 emprunt, 2 retours, 1 erreur)"));
+}
+
+// Session d'inventaire commencée à une date fixe, reprise à l'ouverture de l'écran
+fn fixture_inventaire() -> Bibliotheque {
+    let mut b = fixture();
+    b.commencer_inventaire_le(NaiveDate::from_ymd_opt(2024, 6, 3).unwrap()).unwrap();
+    b
+}
+
+#[test]
+fn inventory_marks_books_by_id_title_and_code() {
+    let mut h = Harness::with_biblio(fixture_inventaire());
+    h.open_menu_item(MENU_INVENTORY).type_text("1").press(KeyCode::Enter);
+
+    // Recherche par titre: le livre se choisit dans la liste
+    h.type_text("vol").press(KeyCode::Enter);
+    assert!(h.rendered_text().contains("Quel livre est en rayon?"));
+    h.press(KeyCode::Enter);
+
+    h.type_text("978-0-00-000000-2").press(KeyCode::Enter);
+    h.type_text("introuvable").press(KeyCode::Enter);
+    h.type_text("1").press(KeyCode::Enter);
+    assert_snapshot!(h.render());
+}
+
+#[test]
+fn inventory_report_then_close() {
+    let mut h = Harness::with_biblio(fixture_inventaire());
+    h.open_menu_item(MENU_INVENTORY)
+        .type_text("2")
+        .press(KeyCode::Enter)
+        .type_text("42424242")
+        .press(KeyCode::Enter)
+        .press_with(KeyCode::Char('r'), KeyModifiers::CONTROL);
+    assert_snapshot!(h.render());
+
+    h.press(KeyCode::Enter).press(KeyCode::Enter);
+    assert_eq!(h.title(), "Succès");
+    assert!(h.rendered_text().contains("Inventaire clôturé: 1 manquant(s)"));
+    assert!(h.app.biblio.lock().unwrap().inventaire().is_none());
+    h.press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_MAIN_MENU);
+}
+
+#[test]
+fn inventory_session_is_resumed() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_INVENTORY).type_text("1").press(KeyCode::Enter);
+    assert!(h.rendered_text().contains("1 / 2 livres vus"));
+
+    // Quitter l'écran ne ferme pas la session
+    h.press(KeyCode::Esc).press(KeyCode::Enter);
+    assert!(h.rendered_text().contains("1 / 2 livres vus"));
+    h.type_text("1").press(KeyCode::Enter);
+    assert!(h.rendered_text().contains("Déjà pointé: Le Petit Prince"));
+}
+
+#[test]
+fn scan_of_shared_isbn_asks_for_copy_barcode() {
+    let mut h = Harness::with_biblio(fixture_isbn(2));
+    h.open_menu_item(MENU_SCAN)
+        .type_text("9782070612758")
+        .press(KeyCode::Enter);
+
+    assert!(h.rendered_text().contains("2 exemplaires ont l'ISBN 9782070612758"));
+    assert!(!h.livre(10).emprunte && !h.livre(11).emprunte);
+
+    h.type_text("11").press(KeyCode::Enter);
+    assert!(h.livre(11).emprunte);
+}
+
+#[test]
+fn scan_ignores_empty_enter_and_closes_with_esc() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_SCAN).press(KeyCode::Enter);
+    assert!(!h.app.bell);
+    assert!(h.rendered_text().contains("Aucun scan pour l'instant"));
+
+    h.press(KeyCode::Esc);
+    assert_eq!(h.title(), TITLE_MAIN_MENU);
+}
+
+#[test]
+fn book_isbn_is_saved_and_shown_in_detail() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_ADD_BOOK)
+        .fill(&["3", "Terre des hommes", "1", "1939", "978-2-07-036024-6"])
+        .press(KeyCode::Enter);
+    assert_eq!(h.title(), "Succès");
+    assert_eq!(h.livre(3).isbn.as_deref(), Some("9782070360246"));
+
+    // Clé de contrôle fausse: refusée
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_ADD_BOOK)
+        .fill(&["3", "Terre des hommes", "1", "1939", "9782070360247"])
+        .press(KeyCode::Enter);
+    assert!(h.rendered_text().contains("Clé de contrôle ISBN incorrecte"));
+}
+
+// --- Séries ---
+
+// Fondation: le tome 2 est au catalogue avant le tome 1 (emprunté), Dune entre les deux
+fn fixture_serie() -> Bibliotheque {
+    let mut b = fixture();
+    b.ajouter_auteur(Auteur::new(3, "Isaac".to_string(), "Asimov".to_string())).unwrap();
+    b.ajouter_serie(Serie::new(1, "Fondation".to_string())).unwrap();
+    let tome = |numero| Some(Tome { serie_id: 1, numero });
+    b.ajouter_livre(Livre {
+        tome: tome(2),
+        ..Livre::new(3, "Fondation et Empire".to_string(), 3, 1952)
+    })
+    .unwrap();
+    b.ajouter_livre(Livre::new(4, "Dune".to_string(), 2, 1965)).unwrap();
+    b.ajouter_livre(Livre {
+        tome: tome(1),
+        emprunte: true,
+        ..Livre::new(5, "Fondation".to_string(), 3, 1951)
+    })
+    .unwrap();
+    for (livre, auteur) in [(3, 3), (4, 2), (5, 3)] {
+        b.associer_livre_auteur(livre, auteur).unwrap();
+    }
+    b
+}
+
+#[test]
+fn list_groups_series_volumes_in_order() {
+    let mut h = Harness::with_biblio(fixture_serie());
+    h.open_menu_item(MENU_LIST_BOOKS);
+    assert_snapshot!(h.render());
+
+    // La sélection suit l'ordre affiché: 3e ligne = tome 1
+    h.press(KeyCode::Down).press(KeyCode::Down).press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_BOOK_DETAIL);
+    let fiche = h.rendered_text();
+    assert!(fiche.contains("Série: Fondation, tome 1"));
+    assert!(fiche.contains("Tomes de la série"));
+    assert!(fiche.contains("> 1. #5 Fondation"));
+    assert!(fiche.contains("2. #3 Fondation et Empire"));
+}
+
+#[test]
+fn returning_a_volume_offers_the_next_one() {
+    let mut h = Harness::with_biblio(fixture_serie());
+    h.open_menu_item(MENU_RETURN_BOOK).type_text("5").press(KeyCode::Enter);
+    assert_eq!(h.title(), "Tome suivant");
+    assert_snapshot!(h.render());
+
+    h.press(KeyCode::Enter);
+    assert_eq!(h.title(), "Succès");
+    assert!(h.livre(3).emprunte);
+    assert!(!h.livre(5).emprunte);
+
+    // Refuser la proposition ramène au menu sans rien emprunter
+    let mut h = Harness::with_biblio(fixture_serie());
+    h.open_menu_item(MENU_RETURN_BOOK).type_text("5").press(KeyCode::Enter).press(KeyCode::Esc);
+    assert_eq!(h.title(), TITLE_MAIN_MENU);
+    assert!(!h.livre(3).emprunte);
+}
+
+#[test]
+fn scan_return_mentions_next_volume() {
+    let mut h = Harness::with_biblio(fixture_serie());
+    h.open_menu_item(MENU_SCAN).type_text("5").press(KeyCode::Enter);
+    assert!(h.rendered_text().contains("Retourné: Fondation · tome suivant disponible: #3"));
+}
+
+#[test]
+fn add_book_creates_series_inline() {
+    let mut h = Harness::with_biblio(fixture_serie());
+    h.open_menu_item(MENU_ADD_BOOK)
+        .fill(&["6", "Seconde Fondation", "asimov", "1953", "", "Fond"])
+        .press(KeyCode::Tab)
+        .type_text("3")
+        .press(KeyCode::Enter);
+    assert_eq!(h.title(), "Succès");
+    assert_eq!(h.livre(6).tome, Some(Tome { serie_id: 1, numero: 3 }));
+
+    // Série inconnue: créée à partir du nom saisi
+    let mut h = Harness::with_biblio(fixture_serie());
+    h.open_menu_item(MENU_ADD_BOOK)
+        .fill(&["7", "Le Messie de Dune", "herbert", "1969", "", "Dune"])
+        .press(KeyCode::Enter)
+        .press(KeyCode::Tab)
+        .type_text("2")
+        .press(KeyCode::Enter);
+    assert_eq!(h.title(), "Succès");
+    assert!(h.rendered_text().contains("Série « Dune » créée (#2)"));
+    assert_eq!(h.livre(7).tome, Some(Tome { serie_id: 2, numero: 2 }));
+}
+
+#[test]
+fn volume_number_needs_a_series_and_vice_versa() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_ADD_BOOK)
+        .fill(&["3", "Dune", "herbert", "1965", "", "", "1"])
+        .press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_ADD_BOOK);
+    assert!(h.rendered_text().contains("Un tome appartient à une série"));
+
+    let mut h = Harness::with_biblio(fixture_serie());
+    h.open_menu_item(MENU_ADD_BOOK)
+        .fill(&["6", "Seconde Fondation", "asimov", "1953", "", "Fond"])
+        .press(KeyCode::Tab)
+        .press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_ADD_BOOK);
+    assert!(h.rendered_text().contains("Champ obligatoire"));
+}
+
+// --- Emplacements ---
+
+// Salle "Adultes": Romans (Dune, Vol de nuit, Cosmos égaré, Germinal), Documentaires vide
+// Salle "Jeunesse": Albums (Le Petit Prince)
+fn fixture_rayons() -> Bibliotheque {
+    let mut b = fixture();
+    b.ajouter_auteur(Auteur::new(3, "Carl".to_string(), "Sagan".to_string())).unwrap();
+    b.ajouter_auteur(Auteur::new(4, "Émile".to_string(), "Zola".to_string())).unwrap();
+    b.ajouter_livre(Livre::new(3, "Dune".to_string(), 2, 1965)).unwrap();
+    b.ajouter_livre(Livre::new(4, "Cosmos".to_string(), 3, 1980)).unwrap();
+    b.ajouter_livre(Livre::new(5, "Germinal".to_string(), 4, 1885)).unwrap();
+    for (livre, auteur) in [(3, 2), (4, 3), (5, 4)] {
+        b.associer_livre_auteur(livre, auteur).unwrap();
+    }
+
+    let rayon = |id, salle: &str, nom: &str| Rayon::new(id, salle.to_string(), nom.to_string());
+    b.ajouter_rayon(rayon(1, "Adultes", "Romans")).unwrap();
+    b.ajouter_rayon(rayon(2, "Jeunesse", "Albums")).unwrap();
+    b.ajouter_rayon(rayon(3, "Adultes", "Documentaires")).unwrap();
+    let places = [(3, "813 HER", 1, 1), (2, "843 SAI", 1, 2), (4, "520 SAG", 1, 3), (5, "843 ZOL", 1, 4), (1, "843 SAI", 2, 1)];
+    for (livre, cote, rayon_id, position) in places {
+        b.placer_livre(livre, Some(cote.to_string()), Some(Emplacement { rayon_id, position })).unwrap();
+    }
+    b
+}
+
+#[test]
+fn add_book_places_it_on_a_new_shelf_then_at_the_end() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_ADD_BOOK)
+        .fill(&["3", "Dune", "herbert", "1965", "", "", "", "813 HER", "Adultes / Romans"])
+        .press(KeyCode::Enter)
+        .press(KeyCode::Enter);
+    assert_eq!(h.title(), "Succès");
+    assert!(h.rendered_text().contains("Rayon « Adultes / Romans » créé (#1)"));
+    assert_eq!(h.livre(3).cote.as_deref(), Some("813 HER"));
+    assert_eq!(h.livre(3).emplacement, Some(Emplacement { rayon_id: 1, position: 1 }));
+
+    // Rayon existant choisi dans la liste, sans position: au bout du rayon
+    let mut biblio = fixture();
+    biblio.ajouter_rayon(Rayon::new(1, "Adultes".to_string(), "Romans".to_string())).unwrap();
+    biblio.placer_livre(2, None, Some(Emplacement { rayon_id: 1, position: 1 })).unwrap();
+    let mut h = Harness::with_biblio(biblio);
+    h.open_menu_item(MENU_ADD_BOOK)
+        .fill(&["4", "Terre des hommes", "saint", "1939", "", "", "", "843 SAI", "roma"])
+        .press(KeyCode::Enter)
+        .press(KeyCode::Enter);
+    assert_eq!(h.title(), "Succès");
+    assert_eq!(h.livre(4).emplacement, Some(Emplacement { rayon_id: 1, position: 2 }));
+}
+
+#[test]
+fn position_needs_a_shelf_and_a_new_shelf_needs_its_room() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_ADD_BOOK)
+        .fill(&["3", "Dune", "herbert", "1965", "", "", "", "", "", "4"])
+        .press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_ADD_BOOK);
+    assert!(h.rendered_text().contains("Une position se prend dans un rayon"));
+
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_ADD_BOOK)
+        .fill(&["3", "Dune", "herbert", "1965", "", "", "", "", "Romans"])
+        .press(KeyCode::Enter)
+        .press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_ADD_BOOK);
+    assert!(h.rendered_text().contains("Nouveau rayon: « Salle / Rayon »"));
+}
+
+#[test]
+fn list_is_filtered_by_room_or_shelf() {
+    let mut h = Harness::with_biblio(fixture_rayons());
+    h.open_menu_item(MENU_LIST_BOOKS).press(KeyCode::Char('f'));
+    assert_snapshot!(h.render());
+
+    // Salle "Jeunesse": seul Le Petit Prince
+    h.press(KeyCode::Down).press(KeyCode::Down).press(KeyCode::Down).press(KeyCode::Down);
+    h.press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_LIST_BOOKS);
+    let liste = h.rendered_text();
+    assert!(liste.contains("Liste des livres · Jeunesse"));
+    assert!(liste.contains("Le Petit Prince"));
+    assert!(!liste.contains("Germinal"));
+
+    // Rayon "Romans": la sélection ouvre la fiche avec la cote et l'emplacement
+    h.press(KeyCode::Char('f')).press(KeyCode::Down).press(KeyCode::Down).press(KeyCode::Enter);
+    assert!(h.rendered_text().contains("Liste des livres · Adultes / Romans"));
+    h.press(KeyCode::Enter);
+    let fiche = h.rendered_text();
+    assert!(fiche.contains("Vol de nuit"));
+    assert!(fiche.contains("Cote: 843 SAI"));
+    assert!(fiche.contains("Emplacement: Adultes / Romans, position 2"));
+
+    // Retour à tous les livres
+    h.press(KeyCode::Esc).press(KeyCode::Char('f')).press(KeyCode::Enter);
+    assert!(h.rendered_text().contains("Germinal"));
+    assert!(h.rendered_text().contains("Le Petit Prince"));
+}
+
+#[test]
+fn shelf_read_flags_misfiled_books() {
+    let mut h = Harness::with_biblio(fixture_rayons());
+    h.open_menu_item(MENU_SHELF_READ);
+    assert_snapshot!(h.render());
+
+    h.press(KeyCode::Enter);
+    assert_snapshot!(h.render());
+
+    // Entrée sur le livre égaré ouvre sa fiche
+    h.press(KeyCode::Down).press(KeyCode::Down).press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_BOOK_DETAIL);
+    assert!(h.rendered_text().contains("Cosmos"));
+}
+
+// --- Rapports ---
+
+#[test]
+fn report_form_writes_the_loans_of_the_month() {
+    let dossier = std::env::temp_dir().join(format!("rapports-{}", std::process::id()));
+    let mut h = Harness::new();
+    // Emprunts du mois, en Markdown, pour mars 2024
+    h.open_menu_item(MENU_REPORTS)
+        .press(KeyCode::Right)
+        .press(KeyCode::Tab)
+        .press(KeyCode::Right)
+        .press(KeyCode::Tab)
+        .press_with(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .type_text("03/2024")
+        .press(KeyCode::Tab)
+        .press_with(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .type_text(dossier.to_str().unwrap())
+        .press(KeyCode::Enter);
+    assert_eq!(h.title(), "Succès");
+
+    let rapport = std::fs::read_to_string(dossier.join("emprunts-2024-03.md")).unwrap();
+    assert!(rapport.starts_with("# Emprunts du mois 03/2024"));
+    assert!(rapport.contains("| 01/03/2024 | 2 | Vol de nuit | 22/03/2024 | en cours |"));
+    let _ = std::fs::remove_dir_all(&dossier);
+}
+
+#[test]
+fn report_form_rejects_a_bad_month() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_REPORTS)
+        .press(KeyCode::Tab)
+        .press(KeyCode::Tab)
+        .press_with(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .type_text("mars")
+        .press(KeyCode::Enter);
+    assert_eq!(h.title(), "📄 Rapports");
+    assert!(h.rendered_text().contains("Mois attendu: AAAA-MM ou MM/AAAA"));
+}
+
+// --- Accès concurrent au fichier ---
+
+fn modifie_ailleurs(fichier: &str, revision: u64) -> AppEvent {
+    AppEvent::Domain(DomainEvent::ModifieAilleurs {
+        fichier: fichier.to_string(),
+        revision,
+    })
+}
+
+// Fichier enregistré par un autre poste, qui y a ajouté un livre; renvoie aussi notre
+// poste, qui l'a chargé juste avant (révision 1, le fichier est en révision 2)
+fn fichier_modifie_ailleurs(nom: &str) -> (String, Bibliotheque) {
+    let fichier = std::env::temp_dir().join(format!("{}-{}.json", nom, std::process::id()));
+    let fichier = fichier.to_str().unwrap().to_string();
+    let mut ailleurs = fixture();
+    ailleurs.sauvegarder(&fichier).unwrap();
+    let notre_poste = Bibliotheque::charger(&fichier).unwrap();
+    ailleurs.ajouter_livre(Livre::new(3, "Vol de nuit".to_string(), 1, 1931)).unwrap();
+    ailleurs.sauvegarder(&fichier).unwrap();
+    (fichier, notre_poste)
+}
+
+fn supprimer_fichier(fichier: &str) {
+    let _ = std::fs::remove_file(fichier);
+    let _ = std::fs::remove_file(format!("{}.lock", fichier));
+}
+
+#[test]
+fn external_change_with_local_edits_asks_once() {
+    let (fichier, notre_poste) = fichier_modifie_ailleurs("conflit-une-fois");
+    let mut h = Harness::with_biblio(notre_poste);
+    h.app.biblio.lock().unwrap().ajouter_serie(Serie::new(1, "Le Petit Prince".to_string())).unwrap();
+    // Le nom affiché est celui de l'événement; le dialog ne relit pas le fichier
+    h.send(modifie_ailleurs("bibliotheque.json", 4));
+    assert_snapshot!(h.render());
+
+    // Plus tard: le menu signale le changement, le dialog ne revient pas pour la même révision
+    h.press(KeyCode::Esc).send(modifie_ailleurs("bibliotheque.json", 4));
+    assert_eq!(h.title(), TITLE_MAIN_MENU);
+    assert!(h.rendered_text().contains("bibliotheque.json modifié par un autre poste"));
+    h.send(modifie_ailleurs("bibliotheque.json", 5));
+    assert_eq!(h.title(), "⚠️  Modifié par un autre poste");
+    supprimer_fichier(&fichier);
+}
+
+#[test]
+fn conflict_dialog_merges_both_versions() {
+    let (fichier, notre_poste) = fichier_modifie_ailleurs("conflit-ecran");
+    let mut h = Harness::with_biblio(notre_poste);
+    h.app.biblio.lock().unwrap().ajouter_serie(Serie::new(1, "Le Petit Prince".to_string())).unwrap();
+    h.send(modifie_ailleurs(&fichier, 2)).press(KeyCode::Down).press(KeyCode::Enter);
+
+    assert_eq!(h.title(), "Succès");
+    assert!(h.rendered_text().contains("Versions fusionnées"));
+    let b = h.app.biblio.lock().unwrap();
+    assert!(!b.est_modifiee());
+    assert_eq!(b.get_livres().len(), 3);
+    assert_eq!(b.get_series().len(), 1);
+    drop(b);
+    supprimer_fichier(&fichier);
+}
+
+#[test]
+fn external_change_without_local_edits_reloads_silently() {
+    let (fichier, notre_poste) = fichier_modifie_ailleurs("rechargement");
+    let mut h = Harness::with_biblio(notre_poste);
+
+    h.send(modifie_ailleurs(&fichier, 2));
+    assert_eq!(h.title(), TITLE_MAIN_MENU);
+    assert_eq!(h.app.biblio.lock().unwrap().get_livres().len(), 3);
+    supprimer_fichier(&fichier);
+}
+
+#[test]
+fn external_change_that_cannot_be_reloaded_opens_the_conflict_dialog() {
+    let (fichier, notre_poste) = fichier_modifie_ailleurs("rechargement-chiffre");
+    let mut h = Harness::with_biblio(notre_poste);
+    // L'autre poste chiffre le fichier: sans la phrase, impossible de le relire
+    let mut ailleurs = Bibliotheque::charger(&fichier).unwrap();
+    ailleurs.changer_phrase(&fichier, "phrase").unwrap();
+
+    h.send(modifie_ailleurs(&fichier, 3));
+    assert_eq!(h.title(), "⚠️  Modifié par un autre poste");
+    assert!(h.rendered_text().contains("n'a pas pu être rechargé"));
+    assert_eq!(h.app.biblio.lock().unwrap().revision(), 1);
+    supprimer_fichier(&fichier);
+}
+
+#[test]
+fn external_change_before_the_first_load_is_ignored() {
+    let (fichier, _) = fichier_modifie_ailleurs("avant-chargement");
+    let mut ailleurs = Bibliotheque::charger(&fichier).unwrap();
+    ailleurs.changer_phrase(&fichier, "phrase").unwrap();
+    let mut h = Harness::with_biblio(Bibliotheque::new());
+
+    // Ni rechargement, ni dialog qui proposerait d'écraser le fichier avec une bibliothèque vide
+    h.send(modifie_ailleurs(&fichier, 3));
+    assert_eq!(h.title(), TITLE_MAIN_MENU);
+    assert!(!h.rendered_text().contains("modifié par un autre poste"));
+    assert!(h.app.biblio.lock().unwrap().get_livres().is_empty());
+    assert_eq!(Bibliotheque::charger_avec_phrase(&fichier, "phrase").unwrap().get_livres().len(), 3);
+    supprimer_fichier(&fichier);
+}
+
+#[test]
+fn labels_form_prints_spine_labels_of_shelved_books() {
+    let dossier = std::env::temp_dir().join(format!("etiquettes-ecran-{}", std::process::id()));
+    let mut h = Harness::with_biblio(fixture_rayons());
+    h.open_menu_item(MENU_LABELS);
+    assert_snapshot!(h.render());
+
+    h.press(KeyCode::Tab)
+        .press(KeyCode::Tab)
+        .press(KeyCode::Tab)
+        .press(KeyCode::Tab)
+        .press_with(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .type_text(dossier.to_str().unwrap())
+        .press(KeyCode::Enter);
+    assert_eq!(h.title(), "Succès");
+    assert!(h.rendered_text().contains("5 étiquettes sur 1 planche"));
+
+    let fichier = format!("dos-{}.pdf", chrono::Local::now().date_naive().format("%Y-%m-%d"));
+    assert!(std::fs::read(dossier.join(fichier)).unwrap().starts_with(b"%PDF-"));
+    let _ = std::fs::remove_dir_all(&dossier);
+}
+
+#[test]
+fn labels_need_call_numbers_and_a_start_within_the_sheet() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_LABELS).press(KeyCode::Enter);
+    assert_eq!(h.title(), "Erreur");
+    assert!(h.rendered_text().contains("Aucun livre à étiqueter"));
+
+    // Avery L7160: 21 étiquettes par planche
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_LABELS)
+        .press(KeyCode::Tab)
+        .press(KeyCode::Tab)
+        .press(KeyCode::Right)
+        .press(KeyCode::Tab)
+        .press_with(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .type_text("30")
+        .press(KeyCode::Enter);
+    assert_eq!(h.title(), "Erreur");
+    assert!(h.rendered_text().contains("La planche Avery L7160 n'a que 21 étiquettes"));
+}
+
+// --- Ajout ---
+
+#[test]
+fn isbn_lookup_prefills_the_book_form() {
+    let mut h = Harness::with_notices();
+    h.open_menu_item(MENU_ADD_BOOK)
+        .fill(&["3", "", "", "", "978-2-07-061275-8"])
+        .press_with(KeyCode::Char('l'), KeyModifiers::CONTROL);
+    assert!(h.title().contains("recherche de la notice 9782070612758"));
+
+    h.attendre_evenement();
+    assert_eq!(h.title(), TITLE_ADD_BOOK);
+    assert_snapshot!(h.render());
+
+    // L'auteur de la notice est reconnu parmi les auteurs existants
+    h.press(KeyCode::Enter);
+    assert_eq!(h.title(), "Succès");
+    let livre = h.livre(3);
+    assert_eq!((livre.titre.as_str(), livre.auteur_id, livre.annee), ("Le Petit Prince", 1, 1943));
+}
+
+#[test]
+fn isbn_lookup_offers_to_create_an_unknown_author() {
+    let mut h = Harness::with_notices();
+    h.open_menu_item(MENU_ADD_BOOK)
+        .fill(&["3", "", "", "", "9782070360024"])
+        .press_with(KeyCode::Char('l'), KeyModifiers::CONTROL)
+        .attendre_evenement();
+
+    // Comme pour une saisie au clavier, la création se confirme par Entrée
+    h.press(KeyCode::Enter);
+    assert_snapshot!(h.render());
+    h.press(KeyCode::Enter).press(KeyCode::Enter);
+    assert_eq!(h.title(), "Succès");
+    assert!(h.rendered_text().contains("auteur « Albert Camus » créé (#3)"));
+    assert_eq!(h.livre(3).titre, "L'Étranger");
+}
+
+#[test]
+fn isbn_lookup_errors_show_under_the_isbn() {
+    let mut h = Harness::with_notices();
+    h.open_menu_item(MENU_ADD_BOOK)
+        .fill(&["3", "Titre saisi", "", "", "9780000000002"])
+        .press_with(KeyCode::Char('l'), KeyModifiers::CONTROL)
+        .attendre_evenement();
+    let texte = h.rendered_text();
+    assert!(texte.contains("Aucune notice trouvée pour 9780000000002"));
+    assert!(texte.contains("Titre saisi"));
+
+    // ISBN invalide: pas de recherche
+    h.press(KeyCode::Backspace).press_with(KeyCode::Char('l'), KeyModifiers::CONTROL);
+    assert_eq!(h.title(), TITLE_ADD_BOOK);
+    assert!(h.rendered_text().contains("ISBN invalide"));
+
+    // Sans fournisseur branché
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_ADD_BOOK)
+        .fill(&["3", "", "", "", "9782070612758"])
+        .press_with(KeyCode::Char('l'), KeyModifiers::CONTROL);
+    assert!(h.rendered_text().contains("Recherche par ISBN indisponible"));
+}
+
+#[test]
+fn add_book_form_keeps_typed_values() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_ADD_BOOK).fill(&["3", "Terre des hommes", "1"]);
+    assert_snapshot!(h.render());
+}
+
+#[test]
+fn add_book_flow() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_ADD_BOOK)
+        .fill(&["3", "Terre des hommes", "1", "1939"])
+        .press(KeyCode::Enter);
+
+    assert_eq!(h.title(), "Succès");
+    assert_snapshot!(h.render());
+
+    let livre = h.livre(3);
+    assert_eq!(livre.titre, "Terre des hommes");
+    assert_eq!(livre.annee, 1939);
+
+    // Enter ferme le message et ramène au menu (le formulaire a été dépilé)
+    h.press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_MAIN_MENU);
+}
+
+#[test]
+fn add_book_with_accented_title_edits_and_pastes() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_ADD_BOOK).fill(&["3", "Les Misrables"]);
+
+    // Le curseur se place entre les caractères, pas au milieu d'un octet
+    for _ in 0..6 {
+        h.press(KeyCode::Left);
+    }
+    h.type_text("é").press(KeyCode::End).paste(", tome Ⅰ\n");
+    assert!(h.rendered_text().contains("Les Misérables, tome Ⅰ█"));
+
+    h.press_with(KeyCode::Left, KeyModifiers::CONTROL)
+        .press_with(KeyCode::Backspace, KeyModifiers::CONTROL)
+        .press(KeyCode::Tab)
+        .paste("1")
+        .press(KeyCode::Tab)
+        .type_text("1862")
+        .press(KeyCode::Enter);
+
+    assert_eq!(h.title(), "Succès");
+    assert_eq!(h.livre(3).titre, "Les Misérables, Ⅰ");
+}
+
+#[test]
+fn add_book_author_typeahead_picks_suggestion() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_ADD_BOOK).fill(&["3", "Dune", "herb"]);
+    assert_snapshot!(h.render());
+
+    // Entrée choisit la suggestion sans envoyer le formulaire
+    h.press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_ADD_BOOK);
+    assert!(h.rendered_text().contains("Frank Herbert"));
+
+    h.press(KeyCode::Tab).type_text("1965").press(KeyCode::Enter);
+    assert_eq!(h.title(), "Succès");
+    assert_eq!(h.livre(3).auteur_id, 2);
+}
+
+#[test]
+fn add_book_author_tab_picks_and_moves_on() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_ADD_BOOK)
+        .fill(&["3", "Courrier sud", "saint", "1929"])
+        .press(KeyCode::Enter);
+
+    assert_eq!(h.title(), "Succès");
+    assert_eq!(h.livre(3).auteur_id, 1);
+}
+
+#[test]
+fn add_book_creates_unknown_author_inline() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_ADD_BOOK).fill(&["3", "Fondation", "Isaac Asimov"]);
+    assert!(h.rendered_text().contains("Créer l'auteur « Isaac Asimov »"));
+
+    h.press(KeyCode::Enter)
+        .press(KeyCode::Tab)
+        .type_text("1951")
+        .press(KeyCode::Enter);
+    assert_eq!(h.title(), "Succès");
+    assert_snapshot!(h.render());
+
+    assert_eq!(h.livre(3).auteur_id, 3);
+    let b = h.app.biblio.lock().unwrap();
+    assert!(b.get_auteurs().iter().any(|a| a.id == 3 && a.prenom == "Isaac" && a.nom == "Asimov"));
+}
+
+#[test]
+fn add_book_unknown_author_without_creating_shows_error() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_ADD_BOOK)
+        .fill(&["3", "Dune", "42", "1965"])
+        .press(KeyCode::Enter);
+
+    assert_eq!(h.title(), TITLE_ADD_BOOK);
+    assert!(h.rendered_text().contains("Choisissez une entrée de la liste"));
+    assert_eq!(h.app.biblio.lock().unwrap().get_livres().len(), 2);
+}
+
+#[test]
+fn esc_closes_author_suggestions_before_form() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_ADD_BOOK).fill(&["3", "Dune", "herb"]);
+
+    h.press(KeyCode::Esc);
+    assert_eq!(h.title(), TITLE_ADD_BOOK);
+    assert!(!h.rendered_text().contains("#2"));
+
+    h.press(KeyCode::Esc);
+    assert_eq!(h.title(), TITLE_MAIN_MENU);
+}
+
+#[test]
+fn add_book_with_empty_field_does_not_submit() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_ADD_BOOK)
+        .fill(&["3", "Dune"])
+        .press(KeyCode::Enter);
+
+    assert_eq!(h.title(), TITLE_ADD_BOOK);
+    assert_eq!(h.app.biblio.lock().unwrap().get_livres().len(), 2);
+}
+
+#[test]
+fn add_book_shows_field_errors_and_focuses_first() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_ADD_BOOK)
+        .fill(&["3", "Dune", "1", "année"])
+        .press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_ADD_BOOK);
+    assert!(h.rendered_text().contains("Nombre entier attendu"));
+
+    // Un ID déjà pris est refusé après l'envoi, le focus revient sur le champ
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_ADD_BOOK)
+        .fill(&["1", "Dune", "2", "1965"])
+        .press(KeyCode::Enter);
+    assert_snapshot!(h.render());
+    assert_eq!(h.app.biblio.lock().unwrap().get_livres().len(), 2);
+}
+
+#[test]
+fn add_author_flow() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_ADD_AUTHOR)
+        .fill(&["3", "Isaac", "Asimov"])
+        .press(KeyCode::Enter);
+
+    assert_eq!(h.title(), "Succès");
+    let b = h.app.biblio.lock().unwrap();
+    assert!(b.get_auteurs().iter().any(|a| a.id == 3 && a.nom == "Asimov"));
+}
+
+// --- Emprunt / retour ---
+
+#[test]
+fn borrow_flow() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_BORROW_BOOK).type_text("1").press(KeyCode::Enter);
+
+    assert_eq!(h.title(), "Succès");
+    assert_snapshot!(h.render());
+    assert!(h.livre(1).emprunte);
+}
+
+#[test]
+fn borrow_already_borrowed_book_shows_error() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_BORROW_BOOK).type_text("2").press(KeyCode::Enter);
+
+    assert_eq!(h.title(), "Erreur");
+    assert_snapshot!(h.render());
+
+    // Le formulaire reste ouvert sous le message pour corriger la saisie
+    h.press(KeyCode::Esc);
+    assert_eq!(h.title(), TITLE_BORROW_BOOK);
+}
+
+#[test]
+fn borrow_invalid_id_shows_error() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_BORROW_BOOK).type_text("abc").press(KeyCode::Enter);
+
+    // L'erreur de saisie s'affiche sous le champ, le formulaire reste ouvert
+    assert_eq!(h.title(), TITLE_BORROW_BOOK);
+    assert_snapshot!(h.render());
+
+    // Elle disparaît dès que la saisie redevient valide
+    for _ in 0..3 {
+        h.press(KeyCode::Backspace);
+    }
+    h.type_text("1");
+    assert!(!h.rendered_text().contains("Nombre entier attendu"));
+}
+
+#[test]
+fn borrow_unknown_book_shows_error() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_BORROW_BOOK).type_text("99").press(KeyCode::Enter);
+
+    assert_eq!(h.title(), "Erreur");
+    assert_snapshot!(h.render());
+}
+
+#[test]
+fn return_flow() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_RETURN_BOOK).type_text("2").press(KeyCode::Enter);
+
+    assert_eq!(h.title(), "Succès");
+    assert_snapshot!(h.render());
+    assert!(!h.livre(2).emprunte);
+}
+
+#[test]
+fn return_book_not_borrowed_shows_error() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_RETURN_BOOK).type_text("1").press(KeyCode::Enter);
+
+    assert_eq!(h.title(), "Erreur");
+    assert_snapshot!(h.render());
+}
+
+// --- Boucle d'événements ---
+
+fn autosaved() -> AppEvent {
+    AppEvent::Domain(DomainEvent::Autosaved {
+        fichier: "bibliotheque.json".to_string(),
+    })
+}
+
+#[test]
+fn key_events_are_routed_through_handle_event() {
+    let mut h = Harness::new();
+    h.send(AppEvent::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)));
+    assert_eq!(h.title(), TITLE_ADD_AUTHOR);
+}
+
+#[test]
+fn autosave_event_shows_status_in_main_menu() {
+    let mut h = Harness::new();
+    h.send(autosaved());
+    assert_snapshot!(h.render());
+}
+
+#[test]
+fn autosave_failure_shows_error_status() {
+    let mut h = Harness::new();
+    h.send(AppEvent::Domain(DomainEvent::AutosaveFailed {
+        erreur: "disque plein".to_string(),
+    }));
+    assert!(
+        h.rendered_text()
+            .contains("Échec de la sauvegarde automatique: disque plein")
+    );
+}
+
+#[test]
+fn status_line_expires_after_ticks() {
+    let mut h = Harness::new();
+    h.send(autosaved());
+    for _ in 0..19 {
+        h.send(AppEvent::Tick);
+    }
+    assert!(h.rendered_text().contains("Sauvegarde automatique"));
+
+    h.send(AppEvent::Tick);
+    assert!(!h.rendered_text().contains("Sauvegarde automatique"));
+}
+
+#[test]
+fn domain_events_reach_subscribed_screens_below_the_top() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_LIST_BOOKS).send(autosaved()).send(AppEvent::Resize);
+    assert_eq!(h.title(), TITLE_LIST_BOOKS);
+
+    h.press(KeyCode::Esc);
+    assert!(
+        h.rendered_text()
+            .contains("Sauvegarde automatique dans bibliotheque.json")
+    );
+}
+
+// --- Souris ---
+
+// Première ligne des entrées du menu (titre sur 3 lignes + bordure)
+const MENU_FIRST_ROW: u16 = 4;
+
+fn many_books() -> Bibliotheque {
+    let mut b = fixture();
+    for id in 3..=40 {
+        b.ajouter_livre(Livre::new(id, format!("Tome {}", id), 2, 2000)).unwrap();
+    }
+    b
+}
+
+#[test]
+fn click_selects_menu_entry_then_activates_it() {
+    let mut h = Harness::new();
+    h.click(10, MENU_FIRST_ROW + MENU_LIST_BOOKS as u16);
+    assert_eq!(h.title(), TITLE_MAIN_MENU);
+    assert_snapshot!(h.render());
+
+    h.click(10, MENU_FIRST_ROW + MENU_LIST_BOOKS as u16);
+    assert_eq!(h.title(), TITLE_LIST_BOOKS);
+}
+
+#[test]
+fn click_outside_menu_entries_does_nothing() {
+    let mut h = Harness::new();
+    h.click(10, 1).click(10, MENU_FIRST_ROW + 16);
+    h.press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_ADD_AUTHOR);
+}
+
+#[test]
+fn scroll_wheel_moves_menu_selection_without_wrapping() {
+    let mut h = Harness::new();
+    h.mouse(MouseEventKind::ScrollUp, 10, 10)
+        .mouse(MouseEventKind::ScrollDown, 10, 10)
+        .press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_ADD_BOOK);
+}
+
+#[test]
+fn click_selects_row_in_author_list() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_LIST_AUTHORS).click(10, 3);
+    assert_snapshot!(h.render());
+}
+
+#[test]
+fn scroll_wheel_scrolls_book_list() {
+    let mut h = Harness::with_biblio(many_books());
+    h.open_menu_item(MENU_LIST_BOOKS);
+    for _ in 0..20 {
+        h.mouse(MouseEventKind::ScrollDown, 10, 10);
+    }
+    assert_snapshot!(h.render());
+}
+
+#[test]
+fn click_focuses_form_field() {
+    let mut h = Harness::new();
+    // Champ "Auteur ID": troisième champ, 3 lignes par champ sous la bordure
+    h.open_menu_item(MENU_ADD_BOOK).click(10, 8).type_text("2");
+    assert_snapshot!(h.render());
+}
+
+#[test]
+fn click_on_ok_button_closes_message() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_BORROW_BOOK).type_text("99").press(KeyCode::Enter);
+    assert_eq!(h.title(), "Erreur");
+
+    h.click(0, 0);
+    assert_eq!(h.title(), "Erreur");
+
+    // Bouton centré au-dessus de la ligne d'aide du dialog
+    h.click(40, 13);
+    assert_eq!(h.title(), TITLE_BORROW_BOOK);
+}
+
+#[test]
+fn wide_terminal_shows_book_details() {
+    let mut h = Harness::with_size(fixture(), 120, 24);
+    h.open_menu_item(MENU_LIST_BOOKS).press(KeyCode::Down);
+    assert_snapshot!(h.render());
+}
+
+#[test]
+fn small_terminal_keeps_selection_visible() {
+    let mut h = Harness::with_size(many_books(), 60, 12);
+    h.open_menu_item(MENU_LIST_BOOKS);
+    for _ in 0..15 {
+        h.press(KeyCode::Down);
+    }
+    assert!(h.rendered_text().contains("#16 - Tome 16"));
+    assert_snapshot!(h.render());
+}
+
+#[test]
+fn page_down_moves_by_visible_lines() {
+    let mut h = Harness::with_size(many_books(), 80, 12);
+    h.open_menu_item(MENU_LIST_BOOKS).render();
+    // 12 lignes - aide - 2 bordures = 9 lignes visibles: deux pages sélectionnent le #19
+    h.press(KeyCode::PageDown).press(KeyCode::PageDown);
+    let text = h.rendered_text();
+    assert!(text.contains("#19 - Tome 19"));
+    assert!(!text.contains("#20 - Tome 20"));
+}
+
+#[test]
+fn resize_keeps_selection_visible() {
+    let mut h = Harness::with_biblio(many_books());
+    h.open_menu_item(MENU_LIST_BOOKS);
+    for _ in 0..19 {
+        h.press(KeyCode::Down);
+    }
+    h.render();
+    h.terminal.backend_mut().resize(80, 10);
+    h.send(AppEvent::Resize);
+    assert!(h.rendered_text().contains("#20 - Tome 20"));
+
+    // Assez haut pour les 40 livres: la liste remonte au début au lieu de laisser des lignes vides
+    h.terminal.backend_mut().resize(80, 50);
+    h.send(AppEvent::Resize);
+    assert!(h.rendered_text().contains("#1 - Le Petit Prince"));
+}
+
+#[test]
+fn ctrl_t_cycles_themes() {
+    let mut h = Harness::new();
+    let border = |h: &mut Harness| h.render().buffer()[(0, 0)].fg;
+    assert_eq!(border(&mut h), Theme::dark().primary);
+
+    h.press_with(KeyCode::Char('t'), KeyModifiers::CONTROL);
+    assert_eq!(border(&mut h), Theme::light().primary);
+
+    // Aussi depuis un autre écran, et on revient au premier thème après le dernier
+    h.open_menu_item(MENU_LIST_BOOKS);
+    h.press_with(KeyCode::Char('t'), KeyModifiers::CONTROL);
+    assert_eq!(h.title(), TITLE_LIST_BOOKS);
+    assert_eq!(h.app.themes[h.app.theme_index].name, "contraste");
+    h.press_with(KeyCode::Char('t'), KeyModifiers::CONTROL);
+    assert_eq!(h.app.theme_index, 0);
+}
+
+#[test]
+fn author_list_pluralizes_book_count() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_LIST_AUTHORS);
+    let text = h.rendered_text();
+    // Antoine a deux livres, Frank Herbert aucun
+    assert!(text.contains("de Saint-Exupery (2 livres)"));
+    assert!(text.contains("Frank Herbert (0 livre)"));
+}
+
+#[test]
+fn english_locale_translates_screens() {
+    let mut h = Harness::with_locale(Locale::En);
+    assert_eq!(h.title(), "Main Menu");
+    assert_snapshot!(h.render());
+
+    h.open_menu_item(MENU_LIST_AUTHORS);
+    let text = h.rendered_text();
+    assert!(text.contains("de Saint-Exupery (2 books)"));
+    assert!(text.contains("Frank Herbert (0 books)"));
+
+    // Un seul Harness à la fois: le second attendrait la langue tenue par le premier
+    drop(h);
+    let mut h = Harness::with_locale(Locale::En);
+    h.open_menu_item(MENU_BORROW_BOOK).type_text("2").press(KeyCode::Enter);
+    assert_eq!(h.title(), "Error");
+    assert!(h.rendered_text().contains("Error: This book is already borrowed"));
+}
+
+#[test]
+fn question_mark_opens_key_help_over_list() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_LIST_BOOKS).press(KeyCode::Char('?'));
+    assert_eq!(h.title(), TITLE_KEY_HELP);
+    assert_snapshot!(h.render());
+
+    h.press(KeyCode::Esc);
+    assert_eq!(h.title(), TITLE_LIST_BOOKS);
+}
+
+#[test]
+fn question_mark_is_typed_in_forms_f1_opens_help() {
+    let mut h = Harness::new();
+    h.open_menu_item(MENU_ADD_BOOK).type_text("Quoi?");
+    assert_eq!(h.title(), TITLE_ADD_BOOK);
+    assert!(h.rendered_text().contains("Quoi?"));
+
+    h.press(KeyCode::F(1));
+    assert_eq!(h.title(), TITLE_KEY_HELP);
+    assert!(h.rendered_text().contains("Shift+Tab"));
+    h.press(KeyCode::F(1));
+    assert_eq!(h.title(), TITLE_ADD_BOOK);
+}
+
+#[test]
+fn vim_keymap_goes_back_with_q() {
+    let mut h = Harness::new();
+    h.app.keymap = Keymap::preset("vim").unwrap();
+    h.open_menu_item(MENU_LIST_BOOKS).press(KeyCode::Char('q'));
+    assert_eq!(h.title(), TITLE_MAIN_MENU);
+}
+
+#[test]
+fn rebound_keys_show_in_help_bar() {
+    let mut keymap = Keymap::default();
+    keymap.bind(KeyContext::List, Action::Back, vec![KeyBinding::parse("backspace").unwrap()]);
+    // Assez large pour que la barre d'aide de la liste tienne en entier
+    let mut h = Harness::with_size(fixture(), 100, HEIGHT);
+    h.app.keymap = keymap;
+    h.open_menu_item(MENU_LIST_BOOKS);
+    assert!(h.rendered_text().contains("Backspace:Retour"));
+
+    h.press(KeyCode::Esc);
+    assert_eq!(h.title(), TITLE_LIST_BOOKS);
+    h.press(KeyCode::Backspace);
+    assert_eq!(h.title(), TITLE_MAIN_MENU);
+}
+
+// --- Palette de commandes ---
+
+fn open_palette(h: &mut Harness, query: &str) {
+    h.press_with(KeyCode::Char('p'), KeyModifiers::CONTROL).type_text(query);
+    assert_eq!(h.title(), TITLE_PALETTE);
+}
+
+#[test]
+fn palette_fuzzy_search_borrows_book() {
+    let mut h = Harness::new();
+    open_palette(&mut h, "emp petit");
+    assert_snapshot!(h.render());
+
+    h.press(KeyCode::Enter);
+    assert_eq!(h.title(), "Succès");
+    assert!(h.livre(1).emprunte);
+
+    // Le message remplace la palette: on revient au menu
+    h.press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_MAIN_MENU);
+}
+
+#[test]
+fn palette_offers_return_for_borrowed_books() {
+    let mut h = Harness::new();
+    open_palette(&mut h, "vol");
+    let text = h.rendered_text();
+    assert!(text.contains("Rendre « Vol de nuit »"));
+    assert!(!text.contains("Emprunter « Vol de nuit »"));
+}
+
+#[test]
+fn palette_jumps_to_author() {
+    let mut h = Harness::new();
+    open_palette(&mut h, "herbert");
+    h.press(KeyCode::Enter);
+    assert_eq!(h.title(), "👥 Liste des auteurs");
+
+    // L'en-tête de Frank Herbert est sélectionné
+    assert!(h.rendered_text().contains("> #2 - Frank Herbert"));
+
+    h.press(KeyCode::Esc);
+    assert_eq!(h.title(), TITLE_MAIN_MENU);
+}
+
+#[test]
+fn palette_runs_menu_and_global_commands() {
+    let mut h = Harness::new();
+    open_palette(&mut h, "theme");
+    h.press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_MAIN_MENU);
+    assert_eq!(h.app.theme_index, 1);
+
+    open_palette(&mut h, "ajouter livre");
+    h.press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_ADD_BOOK);
+}
+
+#[test]
+fn palette_navigation_and_closing() {
+    let mut h = Harness::new();
+    open_palette(&mut h, "zzz");
+    assert!(h.rendered_text().contains("Aucun résultat"));
+    h.press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_PALETTE);
+
+    // Ctrl+P referme la palette au lieu d'en ouvrir une seconde
+    h.press_with(KeyCode::Char('p'), KeyModifiers::CONTROL);
+    assert_eq!(h.title(), TITLE_MAIN_MENU);
+    assert_eq!(h.app.screen_stack.len(), 1);
+
+    // Flèche bas: deuxième résultat ("Ajouter un livre" dans l'ordre du menu)
+    open_palette(&mut h, "");
+    h.press(KeyCode::Down).press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_ADD_BOOK);
+}
+
+#[test]
+fn emacs_keymap_opens_palette_with_alt_x() {
+    let mut h = Harness::new();
+    h.app.keymap = Keymap::preset("emacs").unwrap();
+    h.open_menu_item(MENU_LIST_BOOKS);
+    h.press_with(KeyCode::Char('x'), KeyModifiers::ALT);
+    assert_eq!(h.title(), TITLE_PALETTE);
+}
+
+// --- Opérateurs et rôles ---
+
+fn session(nom: &str, role: Role) -> Session {
+    Session {
+        nom: nom.to_string(),
+        role,
+    }
+}
+
+#[test]
+fn login_screen_rejects_a_wrong_password_then_shows_the_operator() {
+    let mut operateurs = Operateurs::default();
+    operateurs.ajouter(None, "Claire", Role::Admin, "secret").unwrap();
+    let mut h = Harness::new();
+    h.app.push_screen(Box::new(LoginScreen::new(operateurs, "introuvable.json")));
+
+    h.fill(&["Claire", "faux"]).press(KeyCode::Enter);
+    assert_eq!(h.title(), "🔑 Connexion");
+    let text = h.rendered_text();
+    assert!(text.contains("Opérateur ou mot de passe incorrect"));
+    assert!(!text.contains("faux"));
+
+    // Le mot de passe refusé est effacé, le focus y est resté
+    h.type_text("secret").press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_MAIN_MENU);
+    assert_snapshot!(h.render());
+}
+
+#[test]
+fn login_screen_quits_instead_of_opening_the_palette() {
+    let mut h = Harness::new();
+    h.app.push_screen(Box::new(LoginScreen::new(Operateurs::default(), "introuvable.json")));
+    h.press_with(KeyCode::Char('p'), KeyModifiers::CONTROL);
+    assert_eq!(h.title(), "🔑 Connexion");
+    h.press(KeyCode::Esc);
+    assert!(h.app.should_quit);
+}
+
+#[test]
+fn first_operator_is_an_admin_logged_in_at_once() {
+    let fichier = std::env::temp_dir().join(format!("operateurs-ecran-{}.json", std::process::id()));
+    let fichier = fichier.to_str().unwrap().to_string();
+    let mut h = Harness::new();
+    h.app.push_screen(Box::new(AddOperatorScreen::new(&fichier)));
+
+    // Rôle laissé sur Administrateur, confirmation différente puis corrigée
+    h.fill(&["Claire", "", "secret", "secreT"]).press(KeyCode::Enter);
+    assert!(h.rendered_text().contains("Les deux mots de passe diffèrent"));
+    h.press(KeyCode::Backspace).type_text("t").press(KeyCode::Enter);
+
+    assert_eq!(h.title(), "Succès");
+    assert!(h.rendered_text().contains("Premier compte"));
+    assert_eq!(h.app.biblio.lock().unwrap().session(), Some(&session("Claire", Role::Admin)));
+    let operateurs = Operateurs::charger(&fichier).unwrap();
+    assert!(operateurs.authentifier("claire", "secret").is_some());
+    let _ = std::fs::remove_file(&fichier);
+}
+
+fn harness_benevole() -> Harness {
+    let h = Harness::new();
+    h.app.biblio.lock().unwrap().ouvrir_session(session("Paul", Role::Benevole));
+    h
+}
+
+#[test]
+fn volunteer_is_refused_files_and_accounts() {
+    for entree in [MENU_LOAD, MENU_OPERATORS, MENU_ENCRYPTION] {
+        let mut h = harness_benevole();
+        h.open_menu_item(entree);
+        assert_eq!(h.title(), "Erreur");
+        assert!(h.rendered_text().contains("Action non autorisée"));
+    }
+}
+
+#[test]
+fn volunteer_login_loads_the_library_file_then_borrows() {
+    let fichier = std::env::temp_dir().join(format!("connexion-benevole-{}.json", std::process::id()));
+    let fichier = fichier.to_str().unwrap().to_string();
+    fixture().ecraser(&fichier).unwrap();
+    let mut operateurs = Operateurs::default();
+    operateurs.ajouter(None, "Claire", Role::Admin, "secret").unwrap();
+    operateurs.ajouter(Some(&session("Claire", Role::Admin)), "Paul", Role::Benevole, "prêt").unwrap();
+
+    let mut h = Harness::with_biblio(Bibliotheque::new());
+    h.app.push_screen(Box::new(LoginScreen::new(operateurs, &fichier)));
+    h.fill(&["Paul", "prêt"]).press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_MAIN_MENU);
+    assert_eq!(h.app.biblio.lock().unwrap().get_livres().len(), 2);
+
+    h.open_menu_item(MENU_BORROW_BOOK).type_text("1").press(KeyCode::Enter);
+    assert_eq!(h.title(), "Succès");
+    assert!(h.livre(1).emprunte);
+    supprimer_fichier(&fichier);
+}
+
+#[test]
+fn external_change_before_login_neither_asks_nor_blocks_the_volunteer() {
+    let (fichier, _) = fichier_modifie_ailleurs("connexion-apres-changement");
+    let mut operateurs = Operateurs::default();
+    operateurs.ajouter(None, "Claire", Role::Admin, "secret").unwrap();
+    operateurs.ajouter(Some(&session("Claire", Role::Admin)), "Paul", Role::Benevole, "prêt").unwrap();
+    let mut h = Harness::with_biblio(Bibliotheque::new());
+    h.app.push_screen(Box::new(LoginScreen::new(operateurs, &fichier)));
+
+    // Pas de dialog de conflit par-dessus la connexion
+    h.send(modifie_ailleurs(&fichier, 2));
+    assert_eq!(h.title(), "🔑 Connexion");
+
+    h.fill(&["Paul", "prêt"]).press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_MAIN_MENU);
+    assert_eq!(h.app.biblio.lock().unwrap().get_livres().len(), 3);
+    supprimer_fichier(&fichier);
+}
+
+#[test]
+fn loan_detail_names_the_operator() {
+    let mut h = harness_benevole();
+    h.open_menu_item(MENU_LIST_BOOKS)
+        .press(KeyCode::Enter)
+        .press(KeyCode::Char('b'))
+        .press(KeyCode::Enter);
+    assert_eq!(h.title(), TITLE_BOOK_DETAIL);
+    assert!(h.rendered_text().contains("prêté par Paul"));
+}
+
+// --- Fichier chiffré ---
+
+#[test]
+fn passphrase_change_encrypts_the_file_at_once() {
+    let fichier = std::env::temp_dir().join(format!("chiffrement-ecran-{}.json", std::process::id()));
+    let fichier = fichier.to_str().unwrap().to_string();
+    let mut h = Harness::new();
+    h.app.push_screen(Box::new(PassphraseScreen::changer(&fichier)));
+    assert_snapshot!(h.render());
+
+    h.fill(&["phrase", "phrasE"]).press(KeyCode::Enter);
+    assert!(h.rendered_text().contains("Les deux phrases diffèrent"));
+    h.press(KeyCode::Backspace).type_text("e").press(KeyCode::Enter);
+    assert_eq!(h.title(), "Succès");
+    assert!(!std::fs::read_to_string(&fichier).unwrap().contains("Petit Prince"));
+
+    // Un autre poste ouvre le fichier: phrase demandée, une mauvaise est refusée
+    let mut autre = Harness::with_biblio(Bibliotheque::new());
+    autre.app.push_screen(Box::new(PassphraseScreen::deverrouiller(&fichier)));
+    autre.type_text("faux").press(KeyCode::Enter);
+    assert_eq!(autre.title(), "🔒 Fichier chiffré");
+    assert!(autre.rendered_text().contains("Phrase secrète incorrecte"));
+    autre.type_text("phrase").press(KeyCode::Enter);
+    assert_eq!(autre.title(), "Succès");
+    assert_eq!(autre.app.biblio.lock().unwrap().get_livres().len(), 2);
+    supprimer_fichier(&fichier);
+}
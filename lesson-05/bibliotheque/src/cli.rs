@@ -0,0 +1,134 @@
+use crate::app::FICHIER_BIBLIOTHEQUE;
+use crate::i18n::{t, tf};
+use crate::services::Bibliotheque;
+use crate::services::rapports::{self, DOSSIER_RAPPORTS, FormatRapport, Mois, TypeRapport};
+use chrono::Local;
+use std::path::{Path, PathBuf};
+
+// Phrase secrète d'un fichier chiffré: pas d'invite hors de l'interface
+const VARIABLE_PHRASE: &str = "BIBLIOTHEQUE_PHRASE";
+
+// Sous-commandes, exécutées sans ouvrir l'interface:
+//   bibliotheque rapport <type> [--format html|md] [--mois AAAA-MM] [--sortie FICHIER]
+#[derive(Debug, PartialEq)]
+pub enum Commande {
+    Aide,
+    Rapport {
+        type_rapport: TypeRapport,
+        format: FormatRapport,
+        mois: Option<Mois>,
+        sortie: Option<PathBuf>,
+    },
+}
+
+pub fn analyser(args: &[String]) -> Result<Commande, String> {
+    match args.first().map(String::as_str) {
+        Some("aide" | "help" | "--help" | "-h") => Ok(Commande::Aide),
+        Some("rapport" | "report") => analyser_rapport(&args[1..]),
+        Some(autre) => Err(tf("cli.unknown_command", &[("commande", &autre)])),
+        None => Ok(Commande::Aide),
+    }
+}
+
+fn analyser_rapport(args: &[String]) -> Result<Commande, String> {
+    let nom = args.first().ok_or_else(|| t("cli.missing_report").to_string())?;
+    let type_rapport = TypeRapport::depuis_nom(nom).ok_or_else(|| tf("cli.unknown_report", &[("rapport", nom)]))?;
+
+    let mut format = None;
+    let mut mois = None;
+    let mut sortie: Option<PathBuf> = None;
+    let mut options = args[1..].iter();
+    while let Some(option) = options.next() {
+        let valeur = options
+            .next()
+            .ok_or_else(|| tf("cli.missing_value", &[("option", option)]))?;
+        match option.as_str() {
+            "--format" => {
+                format = Some(
+                    FormatRapport::depuis_nom(valeur).ok_or_else(|| tf("cli.unknown_format", &[("format", valeur)]))?,
+                )
+            }
+            "--mois" | "--month" => mois = Some(Mois::analyser(valeur).ok_or_else(|| t("report.invalid_month").to_string())?),
+            "--sortie" | "--output" => sortie = Some(PathBuf::from(valeur)),
+            _ => return Err(tf("cli.unknown_option", &[("option", option)])),
+        }
+    }
+
+    // Sans --format, l'extension du fichier de sortie décide; HTML par défaut
+    let format = format
+        .or_else(|| {
+            let extension = sortie.as_ref()?.extension()?.to_str()?;
+            FormatRapport::depuis_nom(extension)
+        })
+        .unwrap_or(FormatRapport::Html);
+    Ok(Commande::Rapport {
+        type_rapport,
+        format,
+        mois,
+        sortie,
+    })
+}
+
+pub fn executer(args: &[String]) -> Result<(), String> {
+    match analyser(args)? {
+        Commande::Aide => println!("{}", t("cli.usage")),
+        Commande::Rapport {
+            type_rapport,
+            format,
+            mois,
+            sortie,
+        } => {
+            let biblio = match std::env::var(VARIABLE_PHRASE) {
+                Ok(phrase) => Bibliotheque::charger_avec_phrase(FICHIER_BIBLIOTHEQUE, &phrase),
+                Err(_) => Bibliotheque::charger(FICHIER_BIBLIOTHEQUE),
+            }
+            .map_err(|e| tf("menu.load_failed", &[("erreur", &e)]))?;
+            let aujourdhui = Local::now().date_naive();
+            let mois = mois.unwrap_or(Mois::de(aujourdhui));
+            let chemin = sortie.unwrap_or_else(|| {
+                Path::new(DOSSIER_RAPPORTS).join(rapports::nom_fichier(type_rapport, format, mois, aujourdhui))
+            });
+            let chemin = rapports::ecrire(type_rapport, format, &biblio, mois, aujourdhui, &chemin)
+                .map_err(|e| e.to_string())?;
+            println!("{}", tf("report.written", &[("fichier", &chemin.display())]));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(ligne: &str) -> Vec<String> {
+        ligne.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn report_command_with_options() {
+        assert_eq!(
+            analyser(&args("rapport emprunts --mois 2024-03 --sortie mars.md")),
+            Ok(Commande::Rapport {
+                type_rapport: TypeRapport::EmpruntsDuMois,
+                format: FormatRapport::Markdown,
+                mois: Some(Mois { annee: 2024, mois: 3 }),
+                sortie: Some(PathBuf::from("mars.md")),
+            })
+        );
+        assert!(matches!(
+            analyser(&args("rapport retards")),
+            Ok(Commande::Rapport { format: FormatRapport::Html, sortie: None, .. })
+        ));
+        assert_eq!(analyser(&[]), Ok(Commande::Aide));
+    }
+
+    #[test]
+    fn bad_arguments_are_explained() {
+        assert!(analyser(&args("rapport")).is_err());
+        assert!(analyser(&args("rapport inconnu")).is_err());
+        assert!(analyser(&args("rapport catalogue --format pdf")).is_err());
+        assert!(analyser(&args("rapport catalogue --mois")).is_err());
+        assert!(analyser(&args("rapport catalogue --verbeux oui")).is_err());
+        assert!(analyser(&args("exporter")).is_err());
+    }
+}
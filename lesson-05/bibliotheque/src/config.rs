@@ -0,0 +1,249 @@
+use crate::app::FICHIER_CACHE_ISBN;
+use crate::app::keymap::{Action, KeyBinding, KeyContext, Keymap};
+use crate::i18n::{Locale, tf};
+use crate::services::metadonnees::{CacheMetadonnees, FichierNotices, FournisseurMetadonnees, OPEN_LIBRARY_URL, OpenLibrary};
+use crate::ui::theme::Theme;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::sync::Arc;
+
+pub const FICHIER_CONFIG: &str = "bibliotheque.toml";
+
+// Configuration utilisateur, lue au démarrage
+//
+// locale = "en"
+// theme = "sepia"
+//
+// [themes.sepia]
+// primary = "#c08040"
+// fg = "yellow"
+//
+// keymap = "vim"
+// notices = "https://openlibrary.org"
+//
+// [keys.list]
+// down = ["j", "ctrl+n"]
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    // Langue de l'interface ("fr", "en"); à défaut celle de l'environnement (LANG)
+    pub locale: Option<String>,
+    // Thème au démarrage (préréglage ou thème personnalisé)
+    pub theme: Option<String>,
+    pub themes: BTreeMap<String, Theme>,
+    // Jeu de raccourcis de départ: "default", "vim" ou "emacs"
+    pub keymap: Option<String>,
+    // Raccourcis redéfinis: contexte -> action -> touches
+    pub keys: BTreeMap<String, BTreeMap<String, Vec<String>>>,
+    // Source des notices par ISBN: serveur compatible Open Library (par défaut
+    // openlibrary.org), ou fichier de réponses enregistrées pour travailler hors ligne
+    pub notices: Option<String>,
+}
+
+impl Config {
+    // Fichier absent = configuration par défaut
+    pub fn charger(chemin: &str) -> Result<Config, String> {
+        match fs::read_to_string(chemin) {
+            Ok(contenu) => Config::depuis_toml(&contenu)
+                .map_err(|e| tf("config.invalid", &[("fichier", &chemin), ("erreur", &e)])),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(tf("config.unreadable", &[("fichier", &chemin), ("erreur", &e)])),
+        }
+    }
+
+    pub fn depuis_toml(contenu: &str) -> Result<Config, String> {
+        let mut config: Config = toml::from_str(contenu).map_err(|e| e.message().to_string())?;
+        for (name, theme) in &mut config.themes {
+            theme.name = name.clone();
+        }
+        // Vérifie dès le chargement que le thème et la langue demandés existent
+        config.themes()?;
+        config.locale()?;
+        config.keymap()?;
+        Ok(config)
+    }
+
+    // Langue demandée dans le fichier, None pour suivre l'environnement
+    pub fn locale(&self) -> Result<Option<Locale>, String> {
+        match &self.locale {
+            None => Ok(None),
+            Some(code) => Locale::from_code(code)
+                .map(Some)
+                .ok_or_else(|| tf("config.unknown_locale", &[("langue", code)])),
+        }
+    }
+
+    // Préréglage choisi, puis les touches redéfinies dans [keys.<contexte>]
+    pub fn keymap(&self) -> Result<Keymap, String> {
+        let nom = self.keymap.as_deref().unwrap_or("default");
+        let mut keymap =
+            Keymap::preset(nom).ok_or_else(|| tf("keymap.unknown_preset", &[("nom", &nom)]))?;
+
+        for (contexte, actions) in &self.keys {
+            let context = KeyContext::from_name(contexte)
+                .ok_or_else(|| tf("keymap.unknown_context", &[("contexte", contexte)]))?;
+            for (nom_action, touches) in actions {
+                let action = Action::from_name(nom_action)
+                    .ok_or_else(|| tf("keymap.unknown_action", &[("action", nom_action)]))?;
+                let touches = touches
+                    .iter()
+                    .map(|touche| KeyBinding::parse(touche))
+                    .collect::<Result<Vec<_>, _>>()?;
+                keymap.bind(context, action, touches);
+            }
+        }
+
+        Ok(keymap)
+    }
+
+    // Les réponses d'un serveur sont gardées sur disque; un fichier est relu à chaque recherche
+    pub fn fournisseur_notices(&self) -> Arc<dyn FournisseurMetadonnees> {
+        let source = self.notices.as_deref().unwrap_or(OPEN_LIBRARY_URL);
+        if source.starts_with("http://") || source.starts_with("https://") {
+            Arc::new(CacheMetadonnees::new(OpenLibrary::new(source), FICHIER_CACHE_ISBN))
+        } else {
+            Arc::new(FichierNotices::new(source))
+        }
+    }
+
+    // Thèmes disponibles (préréglages puis thèmes personnalisés) et index du thème de départ
+    // Un thème personnalisé du même nom qu'un préréglage le remplace
+    pub fn themes(&self) -> Result<(Vec<Theme>, usize), String> {
+        let mut themes = Theme::presets();
+        for theme in self.themes.values() {
+            match themes.iter_mut().find(|t| t.name == theme.name) {
+                Some(preset) => *preset = theme.clone(),
+                None => themes.push(theme.clone()),
+            }
+        }
+
+        let index = match &self.theme {
+            None => 0,
+            Some(name) => themes
+                .iter()
+                .position(|t| &t.name == name)
+                .ok_or_else(|| tf("config.unknown_theme", &[("theme", name)]))?,
+        };
+
+        Ok((themes, index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::LangueDeTest;
+    use ratatui::style::Color;
+
+    #[test]
+    fn empty_config_uses_presets() {
+        let (themes, index) = Config::depuis_toml("").unwrap().themes().unwrap();
+        assert_eq!(themes, Theme::presets());
+        assert_eq!(index, 0);
+    }
+
+    #[test]
+    fn custom_theme_falls_back_to_dark_colors() {
+        let config = Config::depuis_toml(
+            r##"
+            theme = "sepia"
+
+            [themes.sepia]
+            primary = "#c08040"
+            fg = "light yellow"
+            "##,
+        )
+        .unwrap();
+
+        let (themes, index) = config.themes().unwrap();
+        let sepia = &themes[index];
+        assert_eq!(sepia.name, "sepia");
+        assert_eq!(sepia.primary, Color::Rgb(0xc0, 0x80, 0x40));
+        assert_eq!(sepia.fg, Color::LightYellow);
+        assert_eq!(sepia.secondary, Theme::dark().secondary);
+    }
+
+    #[test]
+    fn custom_theme_overrides_preset() {
+        let config = Config::depuis_toml("[themes.clair]\nprimary = \"magenta\"").unwrap();
+        let (themes, _) = config.themes().unwrap();
+        assert_eq!(themes.len(), Theme::presets().len());
+        assert_eq!(themes[1].primary, Color::Magenta);
+    }
+
+    #[test]
+    fn unknown_theme_is_an_error() {
+        let _langue = LangueDeTest::francais();
+        let err = Config::depuis_toml("theme = \"rose\"").unwrap_err();
+        assert_eq!(err, "Thème inconnu: rose");
+    }
+
+    #[test]
+    fn locale_from_config() {
+        let _langue = LangueDeTest::francais();
+        assert_eq!(Config::depuis_toml("").unwrap().locale(), Ok(None));
+        assert_eq!(Config::depuis_toml("locale = \"en\"").unwrap().locale(), Ok(Some(Locale::En)));
+        assert_eq!(Config::depuis_toml("locale = \"de\"").unwrap_err(), "Langue inconnue: de");
+    }
+
+    #[test]
+    fn keymap_preset_with_overrides() {
+        let config = Config::depuis_toml(
+            r#"
+            keymap = "emacs"
+
+            [keys.list]
+            down = ["n"]
+            "#,
+        )
+        .unwrap();
+
+        let keymap = config.keymap().unwrap();
+        assert_eq!(keymap.name, "emacs");
+        assert_eq!(
+            keymap.keys(KeyContext::List, Action::Down),
+            vec![KeyBinding::parse("n").unwrap()]
+        );
+        // Les autres actions gardent les touches du préréglage
+        assert_eq!(
+            keymap.keys(KeyContext::List, Action::Up),
+            Keymap::preset("emacs").unwrap().keys(KeyContext::List, Action::Up)
+        );
+    }
+
+    #[test]
+    fn invalid_keymap_is_an_error() {
+        let _langue = LangueDeTest::francais();
+        assert_eq!(Config::depuis_toml("keymap = \"nano\"").unwrap_err(), "Jeu de raccourcis inconnu: nano");
+        assert_eq!(
+            Config::depuis_toml("[keys.menu]\ndown = [\"j\"]").unwrap_err(),
+            "Contexte de raccourcis inconnu: menu"
+        );
+        assert_eq!(
+            Config::depuis_toml("[keys.list]\nfly = [\"j\"]").unwrap_err(),
+            "Action inconnue: fly"
+        );
+        assert_eq!(
+            Config::depuis_toml("[keys.list]\ndown = [\"hyper+j\"]").unwrap_err(),
+            "Touche inconnue: hyper+j"
+        );
+    }
+
+    #[test]
+    fn notices_from_a_local_file() {
+        let config = Config::depuis_toml(&format!(
+            "notices = \"{}/fixtures/openlibrary.json\"",
+            env!("CARGO_MANIFEST_DIR")
+        ))
+        .unwrap();
+        let notice = config.fournisseur_notices().chercher("9782266320481").unwrap().unwrap();
+        assert_eq!(notice.titre, "Dune");
+    }
+
+    #[test]
+    fn invalid_color_is_an_error() {
+        assert!(Config::depuis_toml("[themes.x]\nprimary = \"pas une couleur\"").is_err());
+    }
+}
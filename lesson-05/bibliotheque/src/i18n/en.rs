@@ -0,0 +1,409 @@
+// English catalogue
+pub const CATALOGUE: &[(&str, &str)] = &[
+    // Commun
+    ("common.success", "Success"),
+    ("common.error", "Error"),
+    ("common.warning", "Warning"),
+    ("common.error_detail", "Error: {erreur}"),
+    ("common.close", "Close"),
+    ("common.yes", "Yes"),
+    ("common.no", "No"),
+    ("common.date_format", "%Y-%m-%d"),
+    // Barres d'aide
+    ("help.navigate", "Navigate"),
+    ("help.select", "Select"),
+    ("help.theme", "Theme"),
+    ("help.quit", "Quit"),
+    ("help.scroll", "Scroll"),
+    ("help.back", "Back"),
+    ("help.next_field", "Next field"),
+    ("help.add", "Add"),
+    ("help.generate", "Generate"),
+    ("help.cancel", "Cancel"),
+    ("help.borrow", "Borrow"),
+    ("help.return", "Return"),
+    ("help.help", "Help"),
+    ("help.run", "Run"),
+    ("help.palette", "Commands"),
+    ("help.details", "Details"),
+    ("help.edit", "Edit"),
+    ("help.delete", "Delete"),
+    ("help.save", "Save"),
+    ("help.confirm", "Confirm"),
+    ("help.mark", "Mark"),
+    ("help.borrow_return", "Borrow/Return"),
+    ("help.filter", "Filter"),
+    ("help.lookup", "Look up"),
+    ("help.lookup_isbn", "ISBN lookup"),
+    ("help.mark_seen", "Mark seen"),
+    ("help.report", "Report"),
+    ("help.close_inventory", "Close"),
+    ("help.later", "Later"),
+    ("help.login", "Log in"),
+    ("help.unlock", "Unlock"),
+    // Menu principal
+    ("menu.title", "Main Menu"),
+    ("menu.app_name", "Library"),
+    ("menu.add_author", "Add an author"),
+    ("menu.add_book", "Add a book"),
+    ("menu.list_books", "List books"),
+    ("menu.borrow", "Borrow a book"),
+    ("menu.return", "Return a book"),
+    ("menu.scan", "Scan mode (barcode)"),
+    ("menu.inventory", "Shelf stocktake"),
+    ("menu.shelf_read", "Shelf reading"),
+    ("menu.list_authors", "List authors"),
+    ("menu.reports", "Reports"),
+    ("menu.labels", "Labels"),
+    ("menu.operators", "Operators"),
+    ("menu.encryption", "Encryption"),
+    ("menu.save", "Save"),
+    ("menu.load", "Load"),
+    ("menu.quit", "Quit"),
+    ("menu.saved", "Library saved to {fichier}"),
+    ("menu.save_failed", "Could not save: {erreur}"),
+    ("menu.loaded", "Library loaded from {fichier}"),
+    ("menu.load_failed", "Could not load: {erreur}"),
+    ("menu.session", "{nom} ({role})"),
+    ("autosave.done", "Autosaved to {fichier}"),
+    ("autosave.failed", "Autosave failed: {erreur}"),
+    ("autosave.changed_elsewhere", "{fichier} changed by another workstation"),
+    // Save conflicts
+    ("conflict.title", "Changed by another workstation"),
+    ("conflict.message", "{fichier} was saved elsewhere (revision {revision}) while you have unsaved changes."),
+    ("conflict.reload_failed", "{fichier} was saved elsewhere (revision {revision}) and could not be reloaded: {erreur}"),
+    ("conflict.reload", "Reload (discard my changes)"),
+    ("conflict.merge", "Merge both versions"),
+    ("conflict.overwrite", "Overwrite with my version"),
+    ("conflict.reloaded", "Library reloaded from {fichier}"),
+    ("conflict.merged", "Versions merged and saved to {fichier}"),
+    ("conflict.kept.one", "{n} conflict: your version was kept"),
+    ("conflict.kept.other", "{n} conflicts: your version was kept"),
+    ("conflict.overwritten", "Your version replaced {fichier}"),
+    // Auteurs
+    ("author.add.title", "Add an author"),
+    ("author.field.id", "ID"),
+    ("author.field.first_name", "First name"),
+    ("author.field.last_name", "Last name"),
+    ("author.added", "Author added!"),
+    ("author.list.title", "Authors"),
+    ("author.list.empty", "No authors in the library"),
+    ("author.list.books.one", "{n} book"),
+    ("author.list.books.other", "{n} books"),
+    ("author.list.missing_book", "Book ID {id} (not found)"),
+    // Livres
+    ("book.add.title", "Add a book"),
+    ("book.field.id", "ID"),
+    ("book.field.title", "Title"),
+    ("book.field.author", "Author"),
+    ("book.field.year", "Year"),
+    ("book.field.isbn", "ISBN (optional)"),
+    ("book.field.series", "Series (optional)"),
+    ("book.field.volume", "Volume"),
+    ("book.field.call_number", "Call number (optional)"),
+    ("book.field.shelf", "Shelf (optional)"),
+    ("book.field.position", "Position on the shelf"),
+    ("book.added", "Book added and linked to its author!"),
+    ("book.added_with_author", "Book added, author “{nom}” created (#{id})"),
+    ("book.create_author", "➕ Create author “{nom}”"),
+    ("book.create_series", "➕ Create series “{nom}”"),
+    ("book.series_created", "Series “{nom}” created (#{id})"),
+    ("book.series_missing", "A volume belongs to a series"),
+    ("book.create_shelf", "➕ Create shelf “{nom}”"),
+    ("book.shelf_created", "Shelf “{rayon}” created (#{id})"),
+    ("book.shelf_missing", "A position is on a shelf"),
+    ("book.shelf_format", "New shelf: “Room / Shelf”"),
+    ("book.lookup.pending", "looking up {isbn}…"),
+    ("book.lookup.not_found", "No record found for {isbn}"),
+    ("book.lookup.unavailable", "ISBN lookup unavailable"),
+    ("book.list.title", "Books"),
+    ("book.list.empty", "No books in the library"),
+    ("book.list.line", "#{id} - {titre} ({annee}) - Author ID: {auteur}"),
+    ("book.list.volume", "[{serie}, vol. {numero}]"),
+    ("book.status.available", "Available"),
+    ("book.status.borrowed", "Borrowed"),
+    ("book.details.title", "Details"),
+    ("book.details.none", "No book selected"),
+    ("book.details.id", "ID: "),
+    ("book.details.author", "Author: "),
+    ("book.details.year", "Year: "),
+    ("book.details.isbn", "ISBN: "),
+    ("book.details.series", "Series: "),
+    ("book.details.call_number", "Call number: "),
+    ("book.details.location", "Location: "),
+    ("book.details.location_value", "{rayon}, position {position}"),
+    ("book.details.volume", "{serie}, vol. {numero}"),
+    ("book.details.status", "Status: "),
+    ("book.details.unknown_author", "Author ID {id} (not found)"),
+    ("book.borrow.title", "Borrow a book"),
+    ("book.borrow.field", "ID of the book to borrow"),
+    ("book.borrowed", "Book borrowed!"),
+    ("book.return.title", "Return a book"),
+    ("book.return.field", "ID of the book to return"),
+    ("book.returned", "Book returned!"),
+    ("book.edit.title", "Edit book"),
+    ("book.updated", "Book updated!"),
+    ("book.updated_with_author", "Book updated, author “{nom}” created (#{id})"),
+    ("book.detail.title", "Book details"),
+    ("book.detail.not_found", "This book is no longer in the catalogue"),
+    ("book.detail.borrowed_on", "Borrowed on: "),
+    ("book.detail.due", "Due: "),
+    ("book.detail.overdue", " (overdue)"),
+    ("book.detail.history", "Loan history"),
+    ("book.detail.no_history", "Never borrowed"),
+    ("book.detail.loan_returned", "{debut} → {retour}"),
+    ("book.detail.loan_current", "{debut} → on loan, due {echeance}"),
+    ("book.detail.loan_by", "{emprunt} (lent by {operateur})"),
+    ("book.detail.volumes", "Volumes in the series"),
+    ("book.detail.volume_line", "{numero}. #{id} {titre}"),
+    ("book.next_volume.title", "Next volume"),
+    ("book.next_volume.confirm", "Book returned. Volume {numero}, “{titre}” (#{id}), is available: borrow it?"),
+    ("book.next_volume.borrowed", "Volume {numero} “{titre}” borrowed"),
+    ("book.delete.title", "Delete book"),
+    ("book.delete.confirm", "Delete “{titre}” from the catalogue?"),
+    ("book.deleted", "Book “{titre}” deleted"),
+    ("book.list.marked.one", "{n} marked"),
+    ("book.list.marked.other", "{n} marked"),
+    ("book.batch.borrow_title", "Loans"),
+    ("book.batch.return_title", "Returns"),
+    ("book.batch.ok.one", "{n} succeeded"),
+    ("book.batch.ok.other", "{n} succeeded"),
+    ("book.batch.failed.one", "{n} failed"),
+    ("book.batch.failed.other", "{n} failed"),
+    ("book.batch.line", "#{id} {titre}"),
+    ("book.batch.line_error", "#{id} {titre}: {erreur}"),
+    ("scan.title", "Scan mode"),
+    ("scan.field", "Barcode or ISBN"),
+    ("scan.waiting", "Scan a book to borrow or return it"),
+    ("scan.borrowed", "Borrowed: {titre}"),
+    ("scan.returned", "Returned: {titre}"),
+    ("scan.returned_next", "Returned: {titre} · next volume available: #{id}"),
+    ("scan.unknown", "No book for code {code}"),
+    ("scan.ambiguous", "{n} copies share ISBN {code}: scan the copy"),
+    ("scan.source.scanner", "scanner"),
+    ("scan.source.keyboard", "keyboard"),
+    ("scan.log.title", "Session log"),
+    ("scan.log.empty", "Nothing scanned yet"),
+    ("scan.log.borrowed.one", "{n} loan"),
+    ("scan.log.borrowed.other", "{n} loans"),
+    ("scan.log.returned.one", "{n} return"),
+    ("scan.log.returned.other", "{n} returns"),
+    ("scan.log.errors.one", "{n} error"),
+    ("scan.log.errors.other", "{n} errors"),
+    // Inventaire
+    ("inventory.title", "Stocktake"),
+    ("inventory.since", "session of {date}"),
+    ("inventory.field", "Barcode, ID or title of the book on the shelf"),
+    ("inventory.progress", "{vus} / {total} books seen"),
+    ("inventory.seen", "Seen: {titre}"),
+    ("inventory.seen_borrowed", "Seen: {titre} (yet marked as borrowed)"),
+    ("inventory.already_seen", "Already marked: {titre}"),
+    ("inventory.unknown", "Unknown code noted: {code}"),
+    ("inventory.no_match", "No book matches \"{recherche}\""),
+    ("inventory.choose", "Which book is on the shelf?"),
+    ("inventory.log.title", "Marked"),
+    ("inventory.log.empty", "Scan or type the books found on the shelves"),
+    ("inventory.report.title", "Stocktake report"),
+    ("inventory.report.summary", "Session of {date}: {vus} of {total} books seen"),
+    ("inventory.report.missing.one", "{n} missing book"),
+    ("inventory.report.missing.other", "{n} missing books"),
+    ("inventory.report.borrowed_on_shelf.one", "{n} book marked as borrowed but on the shelf"),
+    ("inventory.report.borrowed_on_shelf.other", "{n} books marked as borrowed but on the shelf"),
+    ("inventory.report.unknown.one", "{n} unknown code"),
+    ("inventory.report.unknown.other", "{n} unknown codes"),
+    ("inventory.report.none", "None"),
+    ("inventory.close.title", "Close the stocktake"),
+    ("inventory.close.confirm", "Close the session? The marks will be cleared."),
+    ("inventory.closed", "Stocktake closed: {manquants} missing, {en_rayon} borrowed on shelf, {inconnus} unknown"),
+    ("shelf.picker.title", "Filter by location"),
+    ("shelf.picker.all", "All books"),
+    ("shelf.picker.books.one", "{n} book"),
+    ("shelf.picker.books.other", "{n} books"),
+    ("shelf.picker.empty", "No shelves yet: create one from the book form"),
+    ("shelf.read.title", "Shelf reading"),
+    ("shelf.read.choose", "Shelf reading: which shelf?"),
+    ("shelf.read.empty", "No books on this shelf"),
+    ("shelf.read.no_call_number", "no call number"),
+    ("shelf.read.misfiled", "misfiled"),
+    ("shelf.read.misfiled_count.one", "{n} misfiled"),
+    ("shelf.read.misfiled_count.other", "{n} misfiled"),
+    ("report.title", "Reports"),
+    ("report.field.kind", "Report"),
+    ("report.field.format", "Format"),
+    ("report.field.month", "Month of loans (YYYY-MM)"),
+    ("report.field.folder", "Folder"),
+    ("report.invalid_month", "Expected month: YYYY-MM or MM/YYYY"),
+    ("report.written", "Report written to {fichier}"),
+    ("report.kind.catalogue", "Catalogue"),
+    ("report.kind.loans", "Loans of the month"),
+    ("report.kind.overdue", "Overdue books"),
+    ("report.kind.bibliographies", "Bibliographies by author"),
+    ("report.month_format", "%Y-%m"),
+    ("report.generated_on", "Generated on {date}"),
+    ("report.empty", "No entries"),
+    ("report.column.id", "ID"),
+    ("report.column.title", "Title"),
+    ("report.column.author", "Author"),
+    ("report.column.year", "Year"),
+    ("report.column.isbn", "ISBN"),
+    ("report.column.series", "Series"),
+    ("report.column.call_number", "Call number"),
+    ("report.column.status", "Status"),
+    ("report.column.borrowed_on", "Borrowed on"),
+    ("report.column.due", "Due"),
+    ("report.column.returned_on", "Returned on"),
+    ("report.column.days_late", "Days late"),
+    ("report.catalogue.title", "Catalogue"),
+    ("report.catalogue.section", "Books"),
+    ("report.catalogue.books.one", "{n} book in the catalogue"),
+    ("report.catalogue.books.other", "{n} books in the catalogue"),
+    ("report.catalogue.borrowed.one", "{n} book borrowed"),
+    ("report.catalogue.borrowed.other", "{n} books borrowed"),
+    ("report.loans.title", "Loans for {mois}"),
+    ("report.loans.started", "Loans"),
+    ("report.loans.returned", "Returns"),
+    ("report.loans.ongoing", "ongoing"),
+    ("report.loans.count.one", "{n} loan"),
+    ("report.loans.count.other", "{n} loans"),
+    ("report.loans.returns.one", "{n} return"),
+    ("report.loans.returns.other", "{n} returns"),
+    ("report.loans.still_out.one", "{n} loan of the month not yet returned"),
+    ("report.loans.still_out.other", "{n} loans of the month not yet returned"),
+    ("report.overdue.title", "Overdue on {date}"),
+    ("report.overdue.section", "Overdue books"),
+    ("report.overdue.count.one", "{n} overdue book"),
+    ("report.overdue.count.other", "{n} overdue books"),
+    ("report.bibliographies.title", "Bibliographies"),
+    ("report.bibliographies.authors.one", "{n} author"),
+    ("report.bibliographies.authors.other", "{n} authors"),
+    ("report.template.unexpected", "tag {{/{nom}}} without an open section"),
+    ("report.template.unclosed", "section {{#{nom}}} is not closed"),
+    ("report.template.unterminated", "unclosed tag: {{{debut}"),
+    ("labels.title", "Labels"),
+    ("labels.field.kind", "Labels"),
+    ("labels.field.format", "Format"),
+    ("labels.field.paper", "Label sheet"),
+    ("labels.field.start", "First free label"),
+    ("labels.field.folder", "Folder"),
+    ("labels.kind.spine", "Spine (call number and title)"),
+    ("labels.kind.barcode", "Copy barcodes"),
+    ("labels.paper", "{nom}: {n} labels, {largeur} × {hauteur} mm"),
+    ("labels.start_beyond_sheet", "The {nom} sheet only has {n} labels"),
+    ("labels.count.one", "{n} label"),
+    ("labels.count.other", "{n} labels"),
+    ("labels.sheets.one", "{n} sheet"),
+    ("labels.sheets.other", "{n} sheets"),
+    ("labels.written", "{etiquettes} on {planches}, in {dossier}"),
+    ("labels.skipped.one", "{n} book without a call number skipped"),
+    ("labels.skipped.other", "{n} books without a call number skipped"),
+    ("labels.none", "No book to label"),
+    ("labels.invalid_code", "Cannot print {code} as Code128"),
+    // Operators
+    ("role.admin", "Administrator"),
+    ("role.librarian", "Librarian"),
+    ("role.volunteer", "Volunteer"),
+    ("login.title", "Log in"),
+    ("login.field.name", "Operator"),
+    ("login.field.password", "Password"),
+    ("login.failed", "Wrong operator or password"),
+    ("operators.title", "Add an operator"),
+    ("operators.field.name", "Name"),
+    ("operators.field.role", "Role"),
+    ("operators.field.password", "Password"),
+    ("operators.field.confirm", "Confirm password"),
+    ("operators.mismatch", "The passwords differ"),
+    ("operators.added", "Operator {nom} added ({role})"),
+    ("operators.first", "First account: you are logged in as {nom}. Login will be required on next start."),
+    ("passphrase.unlock_title", "Encrypted file"),
+    ("passphrase.change_title", "File passphrase"),
+    ("passphrase.field.phrase", "Passphrase"),
+    ("passphrase.field.new", "New passphrase (empty: plain file)"),
+    ("passphrase.field.confirm", "Confirm"),
+    ("passphrase.mismatch", "The passphrases differ"),
+    ("passphrase.changed", "{fichier} is encrypted with the new passphrase"),
+    ("passphrase.removed", "{fichier} is saved unencrypted"),
+    ("cli.usage", "Usage: bibliotheque [report <catalogue|emprunts|retards|bibliographies> [--format html|md] [--month YYYY-MM] [--output FILE]]\nWithout arguments, opens the interface. Encrypted file: passphrase in BIBLIOTHEQUE_PHRASE."),
+    ("cli.unknown_command", "Unknown command: {commande} (see `bibliotheque help`)"),
+    ("cli.missing_report", "Which report? catalogue, emprunts, retards or bibliographies"),
+    ("cli.unknown_report", "Unknown report: {rapport} (catalogue, emprunts, retards or bibliographies)"),
+    ("cli.unknown_format", "Unknown format: {format} (html or md)"),
+    ("cli.unknown_option", "Unknown option: {option}"),
+    ("cli.missing_value", "Missing value after {option}"),
+    // Erreurs du domaine
+    ("error.book_not_found", "Book not found"),
+    ("error.author_not_found", "Author not found"),
+    ("error.series_not_found", "Series not found"),
+    ("error.shelf_not_found", "Shelf not found"),
+    ("error.report_template", "Invalid report template: {detail}"),
+    ("error.report_file", "Cannot write the report: {detail}"),
+    ("error.labels_file", "Cannot write the labels: {detail}"),
+    ("error.save_conflict", "File changed by another workstation since it was last loaded (revision {revision})"),
+    ("error.save_not_loaded", "The file already exists but was never loaded: load it before saving"),
+    ("error.not_allowed", "Not allowed for the {role} role"),
+    ("error.operators_admin_only", "Only an administrator can create accounts"),
+    ("error.first_operator_admin", "The first account must be an administrator"),
+    ("error.operator_exists", "An operator named {nom} already exists"),
+    ("error.operators_file", "Unusable operators file: {detail}"),
+    ("error.password_hash", "Cannot hash the password: {detail}"),
+    ("error.passphrase_required", "Encrypted file: passphrase required"),
+    ("error.passphrase_wrong", "Wrong passphrase"),
+    ("error.encrypted_unreadable", "Encrypted file is damaged or in an unknown format"),
+    ("error.already_borrowed", "This book is already borrowed"),
+    ("error.not_borrowed", "This book is not borrowed"),
+    ("error.no_inventory", "No stocktake in progress"),
+    ("error.metadata_access", "Record source unreachable: {detail}"),
+    ("error.metadata_format", "Unreadable record: {detail}"),
+    // Formulaires
+    ("form.required", "Required"),
+    ("form.integer", "Whole number expected"),
+    ("form.range", "Must be between {min} and {max}"),
+    ("form.isbn", "Invalid ISBN (10 or 13 digits)"),
+    ("form.isbn_checksum", "Wrong ISBN check digit"),
+    ("form.email", "Invalid email address"),
+    ("form.date", "Invalid date (YYYY-MM-DD)"),
+    ("form.id_taken", "This ID is already taken"),
+    ("form.lookup", "Pick an entry from the list"),
+    // Palette de commandes
+    ("palette.title", "Command palette"),
+    ("palette.search", "Search"),
+    ("palette.empty", "No results"),
+    ("palette.kind.command", "Command"),
+    ("palette.kind.book", "Book"),
+    ("palette.kind.author", "Author"),
+    ("palette.borrow", "Borrow “{titre}”"),
+    ("palette.return", "Return “{titre}”"),
+    ("palette.show_book", "Show “{titre}”"),
+    ("palette.show_author", "Show {prenom} {nom}"),
+    // Raccourcis clavier
+    ("keyhelp.title", "Keyboard shortcuts"),
+    ("action.quit", "Quit the application"),
+    ("action.next_theme", "Next theme"),
+    ("action.help", "Show shortcuts"),
+    ("action.palette", "Command palette"),
+    ("action.up", "Move up"),
+    ("action.down", "Move down"),
+    ("action.page_up", "Previous page"),
+    ("action.page_down", "Next page"),
+    ("action.select", "Confirm"),
+    ("action.back", "Back / cancel"),
+    ("action.mark", "Mark / unmark the book"),
+    ("action.borrow", "Borrow the book"),
+    ("action.return", "Return the book"),
+    ("action.edit", "Edit the book"),
+    ("action.delete", "Delete the book"),
+    ("action.next_field", "Next field"),
+    ("action.prev_field", "Previous field"),
+    ("action.submit", "Submit the form"),
+    ("action.lookup", "Prefill from a record (ISBN)"),
+    ("action.report", "Show the report"),
+    ("action.filter", "Filter the list"),
+    ("keymap.unknown_key", "Unknown key: {touche}"),
+    ("keymap.unknown_action", "Unknown action: {action}"),
+    ("keymap.unknown_context", "Unknown shortcut context: {contexte}"),
+    ("keymap.unknown_preset", "Unknown key binding preset: {nom}"),
+    // Configuration
+    ("config.invalid", "Invalid configuration in {fichier}: {erreur}"),
+    ("config.unreadable", "Cannot read {fichier}: {erreur}"),
+    ("config.unknown_theme", "Unknown theme: {theme}"),
+    ("config.unknown_locale", "Unknown language: {langue}"),
+];
@@ -0,0 +1,409 @@
+// Catalogue français (langue par défaut)
+pub const CATALOGUE: &[(&str, &str)] = &[
+    // Commun
+    ("common.success", "Succès"),
+    ("common.error", "Erreur"),
+    ("common.warning", "Attention"),
+    ("common.error_detail", "Erreur: {erreur}"),
+    ("common.close", "Fermer"),
+    ("common.yes", "Oui"),
+    ("common.no", "Non"),
+    ("common.date_format", "%d/%m/%Y"),
+    // Barres d'aide
+    ("help.navigate", "Naviguer"),
+    ("help.select", "Choisir"),
+    ("help.theme", "Thème"),
+    ("help.quit", "Quitter"),
+    ("help.scroll", "Défiler"),
+    ("help.back", "Retour"),
+    ("help.next_field", "Champ suivant"),
+    ("help.add", "Ajouter"),
+    ("help.generate", "Générer"),
+    ("help.cancel", "Annuler"),
+    ("help.borrow", "Emprunter"),
+    ("help.return", "Retourner"),
+    ("help.help", "Aide"),
+    ("help.run", "Exécuter"),
+    ("help.palette", "Commandes"),
+    ("help.details", "Fiche"),
+    ("help.edit", "Modifier"),
+    ("help.delete", "Supprimer"),
+    ("help.save", "Enregistrer"),
+    ("help.confirm", "Confirmer"),
+    ("help.mark", "Marquer"),
+    ("help.borrow_return", "Emprunter/Rendre"),
+    ("help.filter", "Filtrer"),
+    ("help.lookup", "Chercher"),
+    ("help.lookup_isbn", "Notice ISBN"),
+    ("help.mark_seen", "Pointer"),
+    ("help.report", "Rapport"),
+    ("help.close_inventory", "Clôturer"),
+    ("help.later", "Plus tard"),
+    ("help.login", "Se connecter"),
+    ("help.unlock", "Déverrouiller"),
+    // Menu principal
+    ("menu.title", "Menu Principal"),
+    ("menu.app_name", "Bibliothèque"),
+    ("menu.add_author", "Ajouter un auteur"),
+    ("menu.add_book", "Ajouter un livre"),
+    ("menu.list_books", "Lister les livres"),
+    ("menu.borrow", "Emprunter un livre"),
+    ("menu.return", "Retourner un livre"),
+    ("menu.scan", "Mode scan (code-barres)"),
+    ("menu.inventory", "Inventaire des rayons"),
+    ("menu.shelf_read", "Lecture de rayon"),
+    ("menu.list_authors", "Lister les auteurs"),
+    ("menu.reports", "Rapports"),
+    ("menu.labels", "Étiquettes"),
+    ("menu.operators", "Opérateurs"),
+    ("menu.encryption", "Chiffrement"),
+    ("menu.save", "Sauvegarder"),
+    ("menu.load", "Charger"),
+    ("menu.quit", "Quitter"),
+    ("menu.saved", "Bibliothèque sauvegardée dans {fichier}"),
+    ("menu.save_failed", "Erreur lors de la sauvegarde: {erreur}"),
+    ("menu.loaded", "Bibliothèque chargée depuis {fichier}"),
+    ("menu.load_failed", "Erreur lors du chargement: {erreur}"),
+    ("menu.session", "{nom} ({role})"),
+    ("autosave.done", "Sauvegarde automatique dans {fichier}"),
+    ("autosave.failed", "Échec de la sauvegarde automatique: {erreur}"),
+    ("autosave.changed_elsewhere", "{fichier} modifié par un autre poste"),
+    // Conflit de sauvegarde
+    ("conflict.title", "Modifié par un autre poste"),
+    ("conflict.message", "{fichier} a été enregistré ailleurs (révision {revision}) alors que vous avez des modifications non sauvegardées."),
+    ("conflict.reload_failed", "{fichier} a été enregistré ailleurs (révision {revision}) et n'a pas pu être rechargé: {erreur}"),
+    ("conflict.reload", "Recharger (abandonner mes modifications)"),
+    ("conflict.merge", "Fusionner les deux versions"),
+    ("conflict.overwrite", "Écraser avec ma version"),
+    ("conflict.reloaded", "Bibliothèque rechargée depuis {fichier}"),
+    ("conflict.merged", "Versions fusionnées et enregistrées dans {fichier}"),
+    ("conflict.kept.one", "{n} conflit: votre version a été gardée"),
+    ("conflict.kept.other", "{n} conflits: votre version a été gardée"),
+    ("conflict.overwritten", "Votre version a remplacé {fichier}"),
+    // Auteurs
+    ("author.add.title", "Ajouter un auteur"),
+    ("author.field.id", "ID"),
+    ("author.field.first_name", "Prénom"),
+    ("author.field.last_name", "Nom"),
+    ("author.added", "Auteur ajouté avec succès!"),
+    ("author.list.title", "Liste des auteurs"),
+    ("author.list.empty", "Aucun auteur dans la bibliothèque"),
+    ("author.list.books.one", "{n} livre"),
+    ("author.list.books.other", "{n} livres"),
+    ("author.list.missing_book", "Livre ID {id} (non trouvé)"),
+    // Livres
+    ("book.add.title", "Ajouter un livre"),
+    ("book.field.id", "ID"),
+    ("book.field.title", "Titre"),
+    ("book.field.author", "Auteur"),
+    ("book.field.year", "Année"),
+    ("book.field.isbn", "ISBN (facultatif)"),
+    ("book.field.series", "Série (facultatif)"),
+    ("book.field.volume", "Tome"),
+    ("book.field.call_number", "Cote (facultatif)"),
+    ("book.field.shelf", "Rayon (facultatif)"),
+    ("book.field.position", "Position dans le rayon"),
+    ("book.added", "Livre ajouté et associé avec succès!"),
+    ("book.added_with_author", "Livre ajouté, auteur « {nom} » créé (#{id})"),
+    ("book.create_author", "➕ Créer l'auteur « {nom} »"),
+    ("book.create_series", "➕ Créer la série « {nom} »"),
+    ("book.series_created", "Série « {nom} » créée (#{id})"),
+    ("book.series_missing", "Un tome appartient à une série"),
+    ("book.create_shelf", "➕ Créer le rayon « {nom} »"),
+    ("book.shelf_created", "Rayon « {rayon} » créé (#{id})"),
+    ("book.shelf_missing", "Une position se prend dans un rayon"),
+    ("book.shelf_format", "Nouveau rayon: « Salle / Rayon »"),
+    ("book.lookup.pending", "recherche de la notice {isbn}…"),
+    ("book.lookup.not_found", "Aucune notice trouvée pour {isbn}"),
+    ("book.lookup.unavailable", "Recherche par ISBN indisponible"),
+    ("book.list.title", "Liste des livres"),
+    ("book.list.empty", "Aucun livre dans la bibliothèque"),
+    ("book.list.line", "#{id} - {titre} ({annee}) - Auteur ID: {auteur}"),
+    ("book.list.volume", "[{serie}, tome {numero}]"),
+    ("book.status.available", "Disponible"),
+    ("book.status.borrowed", "Emprunté"),
+    ("book.details.title", "Détails"),
+    ("book.details.none", "Aucun livre sélectionné"),
+    ("book.details.id", "ID: "),
+    ("book.details.author", "Auteur: "),
+    ("book.details.year", "Année: "),
+    ("book.details.isbn", "ISBN: "),
+    ("book.details.series", "Série: "),
+    ("book.details.call_number", "Cote: "),
+    ("book.details.location", "Emplacement: "),
+    ("book.details.location_value", "{rayon}, position {position}"),
+    ("book.details.volume", "{serie}, tome {numero}"),
+    ("book.details.status", "Statut: "),
+    ("book.details.unknown_author", "Auteur ID {id} (non trouvé)"),
+    ("book.borrow.title", "Emprunter un livre"),
+    ("book.borrow.field", "ID du livre à emprunter"),
+    ("book.borrowed", "Livre emprunté avec succès!"),
+    ("book.return.title", "Retourner un livre"),
+    ("book.return.field", "ID du livre à retourner"),
+    ("book.returned", "Livre retourné avec succès!"),
+    ("book.edit.title", "Modifier le livre"),
+    ("book.updated", "Livre modifié avec succès!"),
+    ("book.updated_with_author", "Livre modifié, auteur « {nom} » créé (#{id})"),
+    ("book.detail.title", "Fiche du livre"),
+    ("book.detail.not_found", "Ce livre ne fait plus partie du catalogue"),
+    ("book.detail.borrowed_on", "Emprunté le: "),
+    ("book.detail.due", "À rendre le: "),
+    ("book.detail.overdue", " (en retard)"),
+    ("book.detail.history", "Historique des emprunts"),
+    ("book.detail.no_history", "Jamais emprunté"),
+    ("book.detail.loan_returned", "{debut} → {retour}"),
+    ("book.detail.loan_current", "{debut} → en cours, à rendre le {echeance}"),
+    ("book.detail.loan_by", "{emprunt} (prêté par {operateur})"),
+    ("book.detail.volumes", "Tomes de la série"),
+    ("book.detail.volume_line", "{numero}. #{id} {titre}"),
+    ("book.next_volume.title", "Tome suivant"),
+    ("book.next_volume.confirm", "Livre retourné. Le tome {numero}, « {titre} » (#{id}), est disponible: l'emprunter ?"),
+    ("book.next_volume.borrowed", "Tome {numero} « {titre} » emprunté"),
+    ("book.delete.title", "Supprimer le livre"),
+    ("book.delete.confirm", "Supprimer « {titre} » du catalogue ?"),
+    ("book.deleted", "Livre « {titre} » supprimé"),
+    ("book.list.marked.one", "{n} marqué"),
+    ("book.list.marked.other", "{n} marqués"),
+    ("book.batch.borrow_title", "Emprunts"),
+    ("book.batch.return_title", "Retours"),
+    ("book.batch.ok.one", "{n} réussi"),
+    ("book.batch.ok.other", "{n} réussis"),
+    ("book.batch.failed.one", "{n} échec"),
+    ("book.batch.failed.other", "{n} échecs"),
+    ("book.batch.line", "#{id} {titre}"),
+    ("book.batch.line_error", "#{id} {titre}: {erreur}"),
+    ("scan.title", "Mode scan"),
+    ("scan.field", "Code-barres ou ISBN"),
+    ("scan.waiting", "Scannez un livre pour l'emprunter ou le retourner"),
+    ("scan.borrowed", "Emprunté: {titre}"),
+    ("scan.returned", "Retourné: {titre}"),
+    ("scan.returned_next", "Retourné: {titre} · tome suivant disponible: #{id}"),
+    ("scan.unknown", "Aucun livre pour le code {code}"),
+    ("scan.ambiguous", "{n} exemplaires ont l'ISBN {code}: scannez l'exemplaire"),
+    ("scan.source.scanner", "scanner"),
+    ("scan.source.keyboard", "clavier"),
+    ("scan.log.title", "Journal de la session"),
+    ("scan.log.empty", "Aucun scan pour l'instant"),
+    ("scan.log.borrowed.one", "{n} emprunt"),
+    ("scan.log.borrowed.other", "{n} emprunts"),
+    ("scan.log.returned.one", "{n} retour"),
+    ("scan.log.returned.other", "{n} retours"),
+    ("scan.log.errors.one", "{n} erreur"),
+    ("scan.log.errors.other", "{n} erreurs"),
+    // Inventaire
+    ("inventory.title", "Inventaire"),
+    ("inventory.since", "session du {date}"),
+    ("inventory.field", "Code-barres, ID ou titre du livre en rayon"),
+    ("inventory.progress", "{vus} / {total} livres vus"),
+    ("inventory.seen", "Vu: {titre}"),
+    ("inventory.seen_borrowed", "Vu: {titre} (pourtant marqué emprunté)"),
+    ("inventory.already_seen", "Déjà pointé: {titre}"),
+    ("inventory.unknown", "Code inconnu noté: {code}"),
+    ("inventory.no_match", "Aucun livre ne correspond à « {recherche} »"),
+    ("inventory.choose", "Quel livre est en rayon?"),
+    ("inventory.log.title", "Pointage"),
+    ("inventory.log.empty", "Scannez ou saisissez les livres trouvés en rayon"),
+    ("inventory.report.title", "Rapport d'inventaire"),
+    ("inventory.report.summary", "Session du {date}: {vus} sur {total} livres vus"),
+    ("inventory.report.missing.one", "{n} livre manquant"),
+    ("inventory.report.missing.other", "{n} livres manquants"),
+    ("inventory.report.borrowed_on_shelf.one", "{n} livre marqué emprunté mais en rayon"),
+    ("inventory.report.borrowed_on_shelf.other", "{n} livres marqués empruntés mais en rayon"),
+    ("inventory.report.unknown.one", "{n} code inconnu"),
+    ("inventory.report.unknown.other", "{n} codes inconnus"),
+    ("inventory.report.none", "Aucun"),
+    ("inventory.close.title", "Clôturer l'inventaire"),
+    ("inventory.close.confirm", "Clôturer la session? Le pointage sera effacé."),
+    ("inventory.closed", "Inventaire clôturé: {manquants} manquant(s), {en_rayon} emprunté(s) en rayon, {inconnus} inconnu(s)"),
+    ("shelf.picker.title", "Filtrer par emplacement"),
+    ("shelf.picker.all", "Tous les livres"),
+    ("shelf.picker.books.one", "{n} livre"),
+    ("shelf.picker.books.other", "{n} livres"),
+    ("shelf.picker.empty", "Aucun rayon: un rayon se crée depuis le formulaire du livre"),
+    ("shelf.read.title", "Lecture de rayon"),
+    ("shelf.read.choose", "Lecture de rayon: quel rayon?"),
+    ("shelf.read.empty", "Aucun livre dans ce rayon"),
+    ("shelf.read.no_call_number", "sans cote"),
+    ("shelf.read.misfiled", "mal rangé"),
+    ("shelf.read.misfiled_count.one", "{n} mal rangé"),
+    ("shelf.read.misfiled_count.other", "{n} mal rangés"),
+    ("report.title", "Rapports"),
+    ("report.field.kind", "Rapport"),
+    ("report.field.format", "Format"),
+    ("report.field.month", "Mois des emprunts (AAAA-MM)"),
+    ("report.field.folder", "Dossier"),
+    ("report.invalid_month", "Mois attendu: AAAA-MM ou MM/AAAA"),
+    ("report.written", "Rapport écrit dans {fichier}"),
+    ("report.kind.catalogue", "Catalogue"),
+    ("report.kind.loans", "Emprunts du mois"),
+    ("report.kind.overdue", "Retards"),
+    ("report.kind.bibliographies", "Bibliographies par auteur"),
+    ("report.month_format", "%m/%Y"),
+    ("report.generated_on", "Généré le {date}"),
+    ("report.empty", "Aucune entrée"),
+    ("report.column.id", "ID"),
+    ("report.column.title", "Titre"),
+    ("report.column.author", "Auteur"),
+    ("report.column.year", "Année"),
+    ("report.column.isbn", "ISBN"),
+    ("report.column.series", "Série"),
+    ("report.column.call_number", "Cote"),
+    ("report.column.status", "Statut"),
+    ("report.column.borrowed_on", "Emprunté le"),
+    ("report.column.due", "Échéance"),
+    ("report.column.returned_on", "Rendu le"),
+    ("report.column.days_late", "Jours de retard"),
+    ("report.catalogue.title", "Catalogue"),
+    ("report.catalogue.section", "Livres"),
+    ("report.catalogue.books.one", "{n} livre au catalogue"),
+    ("report.catalogue.books.other", "{n} livres au catalogue"),
+    ("report.catalogue.borrowed.one", "{n} livre emprunté"),
+    ("report.catalogue.borrowed.other", "{n} livres empruntés"),
+    ("report.loans.title", "Emprunts du mois {mois}"),
+    ("report.loans.started", "Emprunts"),
+    ("report.loans.returned", "Retours"),
+    ("report.loans.ongoing", "en cours"),
+    ("report.loans.count.one", "{n} emprunt"),
+    ("report.loans.count.other", "{n} emprunts"),
+    ("report.loans.returns.one", "{n} retour"),
+    ("report.loans.returns.other", "{n} retours"),
+    ("report.loans.still_out.one", "{n} emprunt du mois pas encore rendu"),
+    ("report.loans.still_out.other", "{n} emprunts du mois pas encore rendus"),
+    ("report.overdue.title", "Retards au {date}"),
+    ("report.overdue.section", "Livres en retard"),
+    ("report.overdue.count.one", "{n} livre en retard"),
+    ("report.overdue.count.other", "{n} livres en retard"),
+    ("report.bibliographies.title", "Bibliographies"),
+    ("report.bibliographies.authors.one", "{n} auteur"),
+    ("report.bibliographies.authors.other", "{n} auteurs"),
+    ("report.template.unexpected", "balise {{/{nom}}} sans section ouverte"),
+    ("report.template.unclosed", "section {{#{nom}}} non fermée"),
+    ("report.template.unterminated", "balise non fermée: {{{debut}"),
+    ("labels.title", "Étiquettes"),
+    ("labels.field.kind", "Étiquettes"),
+    ("labels.field.format", "Format"),
+    ("labels.field.paper", "Planche"),
+    ("labels.field.start", "Première étiquette libre"),
+    ("labels.field.folder", "Dossier"),
+    ("labels.kind.spine", "Dos (cote et titre)"),
+    ("labels.kind.barcode", "Codes-barres d'exemplaire"),
+    ("labels.paper", "{nom}: {n} étiquettes de {largeur} × {hauteur} mm"),
+    ("labels.start_beyond_sheet", "La planche {nom} n'a que {n} étiquettes"),
+    ("labels.count.one", "{n} étiquette"),
+    ("labels.count.other", "{n} étiquettes"),
+    ("labels.sheets.one", "{n} planche"),
+    ("labels.sheets.other", "{n} planches"),
+    ("labels.written", "{etiquettes} sur {planches}, dans {dossier}"),
+    ("labels.skipped.one", "{n} livre sans cote ignoré"),
+    ("labels.skipped.other", "{n} livres sans cote ignorés"),
+    ("labels.none", "Aucun livre à étiqueter"),
+    ("labels.invalid_code", "Code impossible à imprimer en Code128: {code}"),
+    // Opérateurs
+    ("role.admin", "Administrateur"),
+    ("role.librarian", "Bibliothécaire"),
+    ("role.volunteer", "Bénévole"),
+    ("login.title", "Connexion"),
+    ("login.field.name", "Opérateur"),
+    ("login.field.password", "Mot de passe"),
+    ("login.failed", "Opérateur ou mot de passe incorrect"),
+    ("operators.title", "Ajouter un opérateur"),
+    ("operators.field.name", "Nom"),
+    ("operators.field.role", "Rôle"),
+    ("operators.field.password", "Mot de passe"),
+    ("operators.field.confirm", "Confirmation du mot de passe"),
+    ("operators.mismatch", "Les deux mots de passe diffèrent"),
+    ("operators.added", "Opérateur {nom} ajouté ({role})"),
+    ("operators.first", "Premier compte: vous êtes connecté en tant que {nom}. La connexion sera demandée au prochain démarrage."),
+    ("passphrase.unlock_title", "Fichier chiffré"),
+    ("passphrase.change_title", "Phrase secrète du fichier"),
+    ("passphrase.field.phrase", "Phrase secrète"),
+    ("passphrase.field.new", "Nouvelle phrase (vide: fichier en clair)"),
+    ("passphrase.field.confirm", "Confirmation"),
+    ("passphrase.mismatch", "Les deux phrases diffèrent"),
+    ("passphrase.changed", "{fichier} est chiffré avec la nouvelle phrase secrète"),
+    ("passphrase.removed", "{fichier} est enregistré en clair"),
+    ("cli.usage", "Usage: bibliotheque [rapport <catalogue|emprunts|retards|bibliographies> [--format html|md] [--mois AAAA-MM] [--sortie FICHIER]]\nSans argument, ouvre l'interface. Fichier chiffré: phrase secrète dans BIBLIOTHEQUE_PHRASE."),
+    ("cli.unknown_command", "Commande inconnue: {commande} (voir `bibliotheque aide`)"),
+    ("cli.missing_report", "Quel rapport? catalogue, emprunts, retards ou bibliographies"),
+    ("cli.unknown_report", "Rapport inconnu: {rapport} (catalogue, emprunts, retards ou bibliographies)"),
+    ("cli.unknown_format", "Format inconnu: {format} (html ou md)"),
+    ("cli.unknown_option", "Option inconnue: {option}"),
+    ("cli.missing_value", "Valeur manquante après {option}"),
+    // Erreurs du domaine
+    ("error.book_not_found", "Livre non trouvé"),
+    ("error.author_not_found", "Auteur non trouvé"),
+    ("error.series_not_found", "Série non trouvée"),
+    ("error.shelf_not_found", "Rayon non trouvé"),
+    ("error.report_template", "Modèle de rapport invalide: {detail}"),
+    ("error.report_file", "Écriture du rapport impossible: {detail}"),
+    ("error.labels_file", "Écriture des étiquettes impossible: {detail}"),
+    ("error.save_conflict", "Fichier modifié par un autre poste depuis le dernier chargement (révision {revision})"),
+    ("error.save_not_loaded", "Le fichier existe déjà mais n'a pas été chargé: chargez-le avant de sauvegarder"),
+    ("error.not_allowed", "Action non autorisée pour le rôle {role}"),
+    ("error.operators_admin_only", "Seul un administrateur peut créer des comptes"),
+    ("error.first_operator_admin", "Le premier compte doit être un administrateur"),
+    ("error.operator_exists", "Un opérateur nommé {nom} existe déjà"),
+    ("error.operators_file", "Fichier des opérateurs inutilisable: {detail}"),
+    ("error.password_hash", "Hachage du mot de passe impossible: {detail}"),
+    ("error.passphrase_required", "Fichier chiffré: phrase secrète requise"),
+    ("error.passphrase_wrong", "Phrase secrète incorrecte"),
+    ("error.encrypted_unreadable", "Fichier chiffré endommagé ou d'un format inconnu"),
+    ("error.already_borrowed", "Ce livre est déjà emprunté"),
+    ("error.not_borrowed", "Ce livre n'est pas emprunté"),
+    ("error.no_inventory", "Aucun inventaire en cours"),
+    ("error.metadata_access", "Source de notices inaccessible: {detail}"),
+    ("error.metadata_format", "Notice illisible: {detail}"),
+    // Formulaires
+    ("form.required", "Champ obligatoire"),
+    ("form.integer", "Nombre entier attendu"),
+    ("form.range", "Doit être entre {min} et {max}"),
+    ("form.isbn", "ISBN invalide (10 ou 13 chiffres)"),
+    ("form.isbn_checksum", "Clé de contrôle ISBN incorrecte"),
+    ("form.email", "Adresse e-mail invalide"),
+    ("form.date", "Date invalide (AAAA-MM-JJ)"),
+    ("form.id_taken", "Cet ID est déjà utilisé"),
+    ("form.lookup", "Choisissez une entrée de la liste"),
+    // Palette de commandes
+    ("palette.title", "Palette de commandes"),
+    ("palette.search", "Rechercher"),
+    ("palette.empty", "Aucun résultat"),
+    ("palette.kind.command", "Commande"),
+    ("palette.kind.book", "Livre"),
+    ("palette.kind.author", "Auteur"),
+    ("palette.borrow", "Emprunter « {titre} »"),
+    ("palette.return", "Rendre « {titre} »"),
+    ("palette.show_book", "Voir « {titre} »"),
+    ("palette.show_author", "Voir {prenom} {nom}"),
+    // Raccourcis clavier
+    ("keyhelp.title", "Raccourcis clavier"),
+    ("action.quit", "Quitter l'application"),
+    ("action.next_theme", "Thème suivant"),
+    ("action.help", "Afficher les raccourcis"),
+    ("action.palette", "Palette de commandes"),
+    ("action.up", "Monter"),
+    ("action.down", "Descendre"),
+    ("action.page_up", "Page précédente"),
+    ("action.page_down", "Page suivante"),
+    ("action.select", "Valider"),
+    ("action.back", "Retour / annuler"),
+    ("action.mark", "Marquer / démarquer le livre"),
+    ("action.borrow", "Emprunter le livre"),
+    ("action.return", "Retourner le livre"),
+    ("action.edit", "Modifier le livre"),
+    ("action.delete", "Supprimer le livre"),
+    ("action.next_field", "Champ suivant"),
+    ("action.prev_field", "Champ précédent"),
+    ("action.submit", "Envoyer le formulaire"),
+    ("action.lookup", "Préremplir depuis une notice (ISBN)"),
+    ("action.report", "Afficher le rapport"),
+    ("action.filter", "Filtrer la liste"),
+    ("keymap.unknown_key", "Touche inconnue: {touche}"),
+    ("keymap.unknown_action", "Action inconnue: {action}"),
+    ("keymap.unknown_context", "Contexte de raccourcis inconnu: {contexte}"),
+    ("keymap.unknown_preset", "Jeu de raccourcis inconnu: {nom}"),
+    // Configuration
+    ("config.invalid", "Configuration invalide dans {fichier}: {erreur}"),
+    ("config.unreadable", "Impossible de lire {fichier}: {erreur}"),
+    ("config.unknown_theme", "Thème inconnu: {theme}"),
+    ("config.unknown_locale", "Langue inconnue: {langue}"),
+];
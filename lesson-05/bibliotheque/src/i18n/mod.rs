@@ -0,0 +1,247 @@
+// Traduction des textes de l'interface
+//
+// Chaque langue est un catalogue statique (clé, texte). Les textes peuvent contenir
+// des paramètres nommés ({fichier}) remplacés par `tf`, et les pluriels utilisent
+// deux clés `.one` / `.other` choisies par `tn`.
+
+mod en;
+mod fr;
+
+use chrono::NaiveDate;
+use std::env;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    Fr,
+    En,
+}
+
+impl Locale {
+    // Accepte "fr", "en", "fr_FR.UTF-8", "en-US"...
+    pub fn from_code(code: &str) -> Option<Locale> {
+        let langue = code.split(['_', '-', '.']).next().unwrap_or("");
+        match langue.to_lowercase().as_str() {
+            "fr" => Some(Locale::Fr),
+            "en" => Some(Locale::En),
+            _ => None,
+        }
+    }
+
+    // Même priorité que les outils POSIX: LC_ALL, puis LC_MESSAGES, puis LANG
+    pub fn from_env() -> Locale {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|valeur| !valeur.is_empty())
+            .and_then(|valeur| Locale::from_code(&valeur))
+            .unwrap_or_default()
+    }
+
+    fn from_u8(code: u8) -> Locale {
+        match code {
+            1 => Locale::En,
+            _ => Locale::Fr,
+        }
+    }
+
+    fn catalogue(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::Fr => fr::CATALOGUE,
+            Locale::En => en::CATALOGUE,
+        }
+    }
+
+    // Le français compte 0 au singulier ("0 livre"), l'anglais non ("0 books")
+    fn is_singular(self, n: usize) -> bool {
+        match self {
+            Locale::Fr => n <= 1,
+            Locale::En => n == 1,
+        }
+    }
+}
+
+// Pour tout le processus: les threads de fond (recherche ISBN, sauvegarde automatique)
+// traduisent aussi leurs messages d'erreur
+static LOCALE: AtomicU8 = AtomicU8::new(Locale::Fr as u8);
+
+// Choisie une fois au démarrage, avant de lancer les threads
+pub fn set_locale(locale: Locale) {
+    LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+pub fn locale() -> Locale {
+    Locale::from_u8(LOCALE.load(Ordering::Relaxed))
+}
+
+// Les tests tournent en parallèle et la langue est commune au processus: un test dont
+// les textes en dépendent la tient jusqu'à sa fin. Les tests en français la partagent,
+// un test qui change de langue la prend seul et remet le français en la rendant
+#[cfg(test)]
+static LANGUE_DE_TEST: std::sync::RwLock<()> = std::sync::RwLock::new(());
+
+#[cfg(test)]
+thread_local! {
+    // Un thread qui la tient déjà ne la redemande pas (ex: deux Harness dans un test):
+    // sa seconde demande attendrait derrière un test qui change de langue
+    static EN_FRANCAIS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+#[cfg(test)]
+pub enum LangueDeTest {
+    Francais(#[allow(dead_code)] Option<std::sync::RwLockReadGuard<'static, ()>>),
+    Fixee(#[allow(dead_code)] std::sync::RwLockWriteGuard<'static, ()>),
+}
+
+#[cfg(test)]
+impl LangueDeTest {
+    pub fn francais() -> Self {
+        let deja = EN_FRANCAIS.replace(EN_FRANCAIS.get() + 1) > 0;
+        let verrou = (!deja).then(|| LANGUE_DE_TEST.read().unwrap_or_else(std::sync::PoisonError::into_inner));
+        LangueDeTest::Francais(verrou)
+    }
+
+    pub fn fixer(locale: Locale) -> Self {
+        let verrou = LANGUE_DE_TEST.write().unwrap_or_else(std::sync::PoisonError::into_inner);
+        set_locale(locale);
+        LangueDeTest::Fixee(verrou)
+    }
+}
+
+#[cfg(test)]
+impl Drop for LangueDeTest {
+    fn drop(&mut self) {
+        match self {
+            LangueDeTest::Francais(_) => EN_FRANCAIS.set(EN_FRANCAIS.get() - 1),
+            LangueDeTest::Fixee(_) => set_locale(Locale::Fr),
+        }
+    }
+}
+
+// Texte dans la langue courante, à défaut en français, à défaut la clé elle-même
+fn lookup(cle: &str) -> Option<&'static str> {
+    let trouver = |locale: Locale| {
+        locale
+            .catalogue()
+            .iter()
+            .find(|(k, _)| *k == cle)
+            .map(|(_, texte)| *texte)
+    };
+    trouver(locale()).or_else(|| trouver(Locale::Fr))
+}
+
+pub fn t(cle: &'static str) -> &'static str {
+    lookup(cle).unwrap_or(cle)
+}
+
+// Texte avec paramètres: tf("menu.saved", &[("fichier", &nom)])
+pub fn tf(cle: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    remplacer(t(cle), args)
+}
+
+// Pluriel: tn("author.list.books", 3) utilise "author.list.books.other" avec {n} = 3
+pub fn tn(cle: &'static str, n: usize) -> String {
+    let suffixe = if locale().is_singular(n) { "one" } else { "other" };
+    let cle_complete = format!("{}.{}", cle, suffixe);
+    let texte = lookup(&cle_complete).unwrap_or(cle);
+    remplacer(texte, &[("n", &n)])
+}
+
+// Date au format de la langue: 06/04/1943 en français, 1943-04-06 en anglais
+pub fn date(date: NaiveDate) -> String {
+    date.format(t("common.date_format")).to_string()
+}
+
+fn remplacer(texte: &str, args: &[(&str, &dyn Display)]) -> String {
+    args.iter().fold(texte.to_string(), |acc, (nom, valeur)| {
+        acc.replace(&format!("{{{}}}", nom), &valeur.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::ErreurBibliotheque;
+    use std::collections::BTreeSet;
+
+    fn cles(locale: Locale) -> BTreeSet<&'static str> {
+        locale.catalogue().iter().map(|(k, _)| *k).collect()
+    }
+
+    // Paramètres {nom} présents dans un texte
+    fn parametres(texte: &str) -> BTreeSet<&str> {
+        texte
+            .split('{')
+            .skip(1)
+            .filter_map(|morceau| morceau.split_once('}').map(|(nom, _)| nom))
+            .collect()
+    }
+
+    #[test]
+    fn catalogues_have_the_same_keys_and_parameters() {
+        assert_eq!(cles(Locale::Fr), cles(Locale::En));
+        for (cle, fr) in Locale::Fr.catalogue() {
+            let en = Locale::En.catalogue().iter().find(|(k, _)| k == cle).unwrap().1;
+            assert_eq!(parametres(fr), parametres(en), "paramètres différents pour {}", cle);
+        }
+    }
+
+    #[test]
+    fn catalogues_have_no_duplicate_keys() {
+        for locale in [Locale::Fr, Locale::En] {
+            assert_eq!(cles(locale).len(), locale.catalogue().len());
+        }
+    }
+
+    #[test]
+    fn locale_from_code() {
+        assert_eq!(Locale::from_code("fr_FR.UTF-8"), Some(Locale::Fr));
+        assert_eq!(Locale::from_code("en-US"), Some(Locale::En));
+        assert_eq!(Locale::from_code("EN"), Some(Locale::En));
+        assert_eq!(Locale::from_code("C"), None);
+    }
+
+    #[test]
+    fn lookup_with_parameters() {
+        let _langue = LangueDeTest::fixer(Locale::En);
+        assert_eq!(tf("menu.saved", &[("fichier", &"a.json")]), "Library saved to a.json");
+        set_locale(Locale::Fr);
+        assert_eq!(tf("menu.saved", &[("fichier", &"a.json")]), "Bibliothèque sauvegardée dans a.json");
+    }
+
+    #[test]
+    fn plural_rules_depend_on_locale() {
+        let _langue = LangueDeTest::fixer(Locale::Fr);
+        assert_eq!(tn("author.list.books", 0), "0 livre");
+        assert_eq!(tn("author.list.books", 1), "1 livre");
+        assert_eq!(tn("author.list.books", 3), "3 livres");
+
+        set_locale(Locale::En);
+        assert_eq!(tn("author.list.books", 0), "0 books");
+        assert_eq!(tn("author.list.books", 1), "1 book");
+        assert_eq!(tn("author.list.books", 3), "3 books");
+    }
+
+    #[test]
+    fn dates_follow_locale() {
+        let jour = NaiveDate::from_ymd_opt(1943, 4, 6).unwrap();
+        let _langue = LangueDeTest::fixer(Locale::En);
+        assert_eq!(date(jour), "1943-04-06");
+        set_locale(Locale::Fr);
+        assert_eq!(date(jour), "06/04/1943");
+    }
+
+    #[test]
+    fn worker_threads_translate_in_the_process_locale() {
+        let _langue = LangueDeTest::fixer(Locale::En);
+        let texte = std::thread::spawn(|| ErreurBibliotheque::DejaEmprunte.to_string()).join().unwrap();
+        assert_eq!(texte, "This book is already borrowed");
+    }
+
+    #[test]
+    fn unknown_key_falls_back_to_the_key() {
+        assert_eq!(t("nope.missing"), "nope.missing");
+    }
+}
@@ -1,33 +1,65 @@
 mod app;
+mod cli;
+mod config;
+mod i18n;
 mod models;
 mod services;
 mod ui;
 
-use app::App;
+use app::event::EventHandler;
+use app::recherche_isbn::RechercheIsbn;
+use app::{App, FICHIER_BIBLIOTHEQUE, FICHIER_OPERATEURS, autosave, surveillance};
+use config::{Config, FICHIER_CONFIG};
 use crossterm::{
-    event::{self, Event},
+    event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 use services::Bibliotheque;
+use services::operateurs::Operateurs;
+use ui::screens::{LoginScreen, ouvrir_au_demarrage};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use std::io::Write;
 use std::{io, panic};
 
+const TICK_RATE: Duration = Duration::from_millis(250);
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(30);
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Langue de l'environnement, éventuellement remplacée par la configuration
+    i18n::set_locale(i18n::Locale::from_env());
+
+    // Lue avant de passer en mode TUI pour que l'erreur reste visible
+    let config = Config::charger(FICHIER_CONFIG)?;
+    let (themes, theme_index) = config.themes()?;
+    let keymap = config.keymap()?;
+    if let Some(locale) = config.locale()? {
+        i18n::set_locale(locale);
+    }
+    let operateurs = Operateurs::charger(FICHIER_OPERATEURS)?;
+
+    // Sous-commande (ex: `bibliotheque rapport retards`): pas d'interface
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        return Ok(cli::executer(&args)?);
+    }
+
     // Setup panic hook pour restaurer le terminal en cas de panic
     let original_hook = panic::take_hook();
     panic::set_hook(Box::new(move |panic_info| {
         let _ = disable_raw_mode();
-        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, DisableBracketedPaste);
         original_hook(panic_info);
     }));
 
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    // Bracketed paste: un collage arrive comme un seul événement, pas comme des frappes
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
@@ -37,15 +69,52 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // - Mutex = garantit l'accès exclusif lors des modifications
     let biblio = Arc::new(Mutex::new(Bibliotheque::new()));
 
+    // Canal d'événements: entrées terminal, ticks et threads de fond
+    let events = EventHandler::new(TICK_RATE);
+    autosave::spawn(
+        biblio.clone(),
+        FICHIER_BIBLIOTHEQUE.to_string(),
+        AUTOSAVE_INTERVAL,
+        events.sender(),
+    );
+    // Signale les sauvegardes faites par un autre processus sur le même fichier
+    // (ignorées tant que le fichier n'est pas chargé, donc avant la connexion)
+    surveillance::spawn(
+        biblio.clone(),
+        FICHIER_BIBLIOTHEQUE.to_string(),
+        WATCH_INTERVAL,
+        events.sender(),
+    );
+
+    // Recherche des notices par ISBN dans un thread par requête
+    let recherche_isbn = RechercheIsbn::new(config.fournisseur_notices(), events.sender());
+
     // Créer l'application
-    let mut app = App::new(biblio);
+    let mut app = App::with_themes(biblio, themes, theme_index)
+        .with_keymap(keymap)
+        .with_recherche_isbn(recherche_isbn);
+    // Connexion demandée dès qu'un compte d'opérateur existe; le fichier est chargé
+    // une fois l'opérateur connecté, sinon tout de suite
+    if !operateurs.est_vide() {
+        app.push_screen(Box::new(LoginScreen::new(operateurs, FICHIER_BIBLIOTHEQUE)));
+    } else {
+        let ecran = ouvrir_au_demarrage(&mut app.biblio.lock().unwrap(), FICHIER_BIBLIOTHEQUE);
+        if let Some(ecran) = ecran {
+            app.push_screen(ecran);
+        }
+    }
 
     // Event loop
-    let res = run_app(&mut terminal, &mut app);
+    let res = run_app(&mut terminal, &mut app, &events);
 
     // Cleanup
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        DisableBracketedPaste
+    )?;
     terminal.show_cursor()?;
 
     res
@@ -54,16 +123,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
+    events: &EventHandler,
 ) -> Result<(), Box<dyn std::error::Error>> {
     loop {
         terminal.draw(|f| app.render(f))?;
-
-        if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                app.handle_input(key)?;
-            }
+        if std::mem::take(&mut app.bell) {
+            terminal.backend_mut().write_all(b"\x07")?;
+            terminal.backend_mut().flush()?;
         }
 
+        // Bloque jusqu'au prochain événement (au pire un tick)
+        app.handle_event(events.next()?)?;
+
         if app.should_quit {
             break;
         }
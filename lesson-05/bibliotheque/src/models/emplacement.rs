@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+// Meuble de rangement dans une salle (ex: salle "Jeunesse", rayon "Étagère B")
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Rayon {
+    pub id: u32,
+    pub salle: String,
+    pub nom: String,
+}
+
+impl Rayon {
+    pub fn new(id: u32, salle: String, nom: String) -> Self {
+        Rayon { id, salle, nom }
+    }
+}
+
+// Place d'un livre: son rayon et sa position sur le rayon (1 = le premier)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Emplacement {
+    pub rayon_id: u32,
+    pub position: u32,
+}
+
+// Ordre de rangement de deux cotes Dewey ou CDU (ex: "843.912 SAI")
+// Les indices décimaux se comparent caractère par caractère: 843.12 avant 843.9
+pub fn comparer_cotes(a: &str, b: &str) -> Ordering {
+    a.trim().to_uppercase().cmp(&b.trim().to_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn call_numbers_sort_like_on_the_shelf() {
+        let mut cotes = vec!["843.9 SAI", "500 AST", "843.12 HER", "843.9 ABC", "082 DIC"];
+        cotes.sort_by(|a, b| comparer_cotes(a, b));
+        assert_eq!(cotes, vec!["082 DIC", "500 AST", "843.12 HER", "843.9 ABC", "843.9 SAI"]);
+        assert_eq!(comparer_cotes("843.9 sai", " 843.9 SAI"), Ordering::Equal);
+    }
+}
@@ -0,0 +1,48 @@
+use chrono::{Days, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+// Durée d'un emprunt avant la date de retour prévue
+pub const DUREE_EMPRUNT_JOURS: u64 = 21;
+
+// Un emprunt d'un livre, en cours tant que `retour` est vide
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Emprunt {
+    pub debut: NaiveDate,
+    pub echeance: NaiveDate,
+    pub retour: Option<NaiveDate>,
+    // Opérateur connecté lors de l'emprunt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operateur: Option<String>,
+}
+
+impl Emprunt {
+    pub fn new(debut: NaiveDate) -> Self {
+        Emprunt {
+            debut,
+            echeance: debut + Days::new(DUREE_EMPRUNT_JOURS),
+            retour: None,
+            operateur: None,
+        }
+    }
+
+    pub fn en_retard(&self, aujourdhui: NaiveDate) -> bool {
+        self.retour.is_none() && aujourdhui > self.echeance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn due_date_and_overdue() {
+        let debut = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let emprunt = Emprunt::new(debut);
+        assert_eq!(emprunt.echeance, NaiveDate::from_ymd_opt(2024, 3, 22).unwrap());
+        assert!(!emprunt.en_retard(emprunt.echeance));
+        assert!(emprunt.en_retard(NaiveDate::from_ymd_opt(2024, 3, 23).unwrap()));
+
+        let rendu = Emprunt { retour: Some(debut), ..emprunt };
+        assert!(!rendu.en_retard(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()));
+    }
+}
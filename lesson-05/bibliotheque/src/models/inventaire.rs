@@ -0,0 +1,37 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+// Session d'inventaire: les livres vus en rayon depuis le début du pointage
+// Gardée dans la sauvegarde pour reprendre la session après un redémarrage
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Inventaire {
+    pub debut: NaiveDate,
+    // IDs des livres pointés, dans l'ordre du pointage
+    pub vus: Vec<u32>,
+    // Codes pointés qui ne correspondent à aucun livre du catalogue
+    pub inconnus: Vec<String>,
+}
+
+impl Inventaire {
+    pub fn new(debut: NaiveDate) -> Self {
+        Inventaire {
+            debut,
+            vus: Vec::new(),
+            inconnus: Vec::new(),
+        }
+    }
+}
+
+// Écarts entre le catalogue et ce qui a été trouvé en rayon
+#[derive(Debug, Clone, PartialEq)]
+pub struct RapportInventaire {
+    pub debut: NaiveDate,
+    // Livres du catalogue pointés, et nombre total de livres
+    pub vus: usize,
+    pub total: usize,
+    // Ni vus ni empruntés: sortis sans enregistrement, perdus ou mal rangés
+    pub manquants: Vec<u32>,
+    // Marqués empruntés mais trouvés en rayon: retour oublié
+    pub empruntes_en_rayon: Vec<u32>,
+    pub inconnus: Vec<String>,
+}
@@ -1,8 +1,10 @@
 use serde::{Serialize, Deserialize};
 
+use super::{Emplacement, Emprunt, Tome};
+
 // OWNERSHIP: Clone permet de dupliquer un Livre (copie profonde des Strings)
 // Utile quand on veut créer une copie indépendante sans transférer l'ownership
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Livre {
     pub id: u32,
 
@@ -13,10 +15,31 @@ pub struct Livre {
 
     pub annee: u32,
 
+    // ISBN sans tirets ni espaces; plusieurs exemplaires peuvent partager le même
+    #[serde(default)]
+    pub isbn: Option<String>,
+
+    // Série à laquelle appartient le livre, avec son numéro de tome
+    #[serde(default)]
+    pub tome: Option<Tome>,
+
+    // Cote de classement (Dewey ou CDU), qui fixe l'ordre sur le rayon
+    #[serde(default)]
+    pub cote: Option<String>,
+
+    // Rayon et position où le livre doit se trouver
+    #[serde(default)]
+    pub emplacement: Option<Emplacement>,
+
     // MUTABILITÉ: Ce champ sera modifié lors de l'emprunt/retour
     // même si on a une référence &mut vers le Livre
     #[serde(default)]
     pub emprunte: bool,
+
+    // Emprunts passés et en cours, du plus ancien au plus récent
+    // (absent des fichiers sauvegardés avant son ajout)
+    #[serde(default)]
+    pub historique: Vec<Emprunt>,
 }
 
 impl Livre {
@@ -30,10 +53,20 @@ impl Livre {
             titre,
             auteur_id,
             annee,
+            isbn: None,
+            tome: None,
+            cote: None,
+            emplacement: None,
             emprunte: false,
+            historique: Vec::new(),
         }
     }
 
+    // L'emprunt pas encore rendu, s'il y en a un
+    pub fn emprunt_en_cours(&self) -> Option<&Emprunt> {
+        self.historique.last().filter(|e| e.retour.is_none())
+    }
+
     // LIFETIME: &self a un lifetime implicite 'a: fn afficher<'a>(&'a self)
     // La référence est valide pendant l'appel de la fonction
     // OWNERSHIP: Emprunt immuable, on ne modifie pas et on ne prend pas l'ownership
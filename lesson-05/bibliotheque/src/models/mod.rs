@@ -2,4 +2,19 @@ pub mod livre;
 pub use livre::Livre;
 
 pub mod auteur;
-pub use auteur::Auteur;
\ No newline at end of file
+pub use auteur::Auteur;
+
+pub mod emprunt;
+pub use emprunt::Emprunt;
+
+pub mod serie;
+pub use serie::{Serie, Tome};
+
+pub mod inventaire;
+pub use inventaire::{Inventaire, RapportInventaire};
+
+pub mod emplacement;
+pub use emplacement::{Emplacement, Rayon};
+
+pub mod operateur;
+pub use operateur::{Operateur, Permission, Role, Session};
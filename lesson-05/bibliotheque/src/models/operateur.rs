@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+// Rôle d'un opérateur, du plus au moins privilégié
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Admin,
+    Bibliothecaire,
+    Benevole,
+}
+
+// Ce qu'une opération de la bibliothèque demande à l'opérateur connecté
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    // Emprunts et retours
+    Emprunter,
+    // Pointage des livres pendant un inventaire déjà ouvert
+    Pointer,
+    // Ajout et modification des livres, auteurs, séries et rayons; ouverture et clôture
+    // des sessions d'inventaire
+    Cataloguer,
+    Supprimer,
+    // Charger un autre fichier, écraser la version enregistrée par un autre poste
+    Fichiers,
+    // Comptes des opérateurs
+    Administrer,
+}
+
+impl Role {
+    pub const TOUS: [Role; 3] = [Role::Admin, Role::Bibliothecaire, Role::Benevole];
+
+    // Clé de traduction du nom du rôle
+    pub fn cle(self) -> &'static str {
+        match self {
+            Role::Admin => "role.admin",
+            Role::Bibliothecaire => "role.librarian",
+            Role::Benevole => "role.volunteer",
+        }
+    }
+
+    pub fn peut(self, permission: Permission) -> bool {
+        match self {
+            Role::Admin => true,
+            Role::Bibliothecaire => permission != Permission::Administrer,
+            Role::Benevole => matches!(permission, Permission::Emprunter | Permission::Pointer),
+        }
+    }
+}
+
+// Compte d'un opérateur, enregistré dans le fichier des opérateurs
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Operateur {
+    pub nom: String,
+    pub role: Role,
+    // Hash Argon2 au format PHC (algorithme, paramètres et sel compris), jamais le mot de passe
+    pub mot_de_passe: String,
+}
+
+// Opérateur connecté à l'interface
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    pub nom: String,
+    pub role: Role,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn volunteers_only_lend_and_scan() {
+        assert!(Role::Benevole.peut(Permission::Emprunter));
+        assert!(Role::Benevole.peut(Permission::Pointer));
+        assert!(!Role::Benevole.peut(Permission::Supprimer));
+        assert!(!Role::Benevole.peut(Permission::Fichiers));
+        assert!(Role::Bibliothecaire.peut(Permission::Supprimer));
+        assert!(!Role::Bibliothecaire.peut(Permission::Administrer));
+        assert!(Role::Admin.peut(Permission::Administrer));
+    }
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+// Série ou collection (ex: Fondation, Harry Potter); ses livres sont rangés par tome
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Serie {
+    pub id: u32,
+    pub nom: String,
+}
+
+impl Serie {
+    pub fn new(id: u32, nom: String) -> Self {
+        Serie { id, nom }
+    }
+}
+
+// Place d'un livre dans une série
+// Plusieurs exemplaires d'un même tome portent le même numéro
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tome {
+    pub serie_id: u32,
+    pub numero: u32,
+}
@@ -1,14 +1,49 @@
 use serde::Deserialize;
 use serde::Serialize;
 
-use crate::models::{Auteur, Livre};
+use super::chiffrement::{self, Cle};
+use super::stockage::{self, Verrou};
+use super::{ErreurBibliotheque, ErreurChargement, ErreurSauvegarde, fusion};
+use crate::models::emplacement::comparer_cotes;
+use crate::models::{
+    Auteur, Emplacement, Emprunt, Inventaire, Livre, Permission, RapportInventaire, Rayon, Serie, Session, Tome,
+};
+use std::cmp::Ordering;
+use chrono::{Local, NaiveDate};
+use std::collections::HashMap;
 use std::fs;
-use std::io;
+use std::path::Path;
 
 #[derive(Serialize, Deserialize)]
 pub struct Bibliotheque {
     livres: Vec<Livre>,
     auteurs: Vec<Auteur>,
+    // Absent des fichiers sauvegardés avant l'ajout des séries
+    #[serde(default)]
+    series: Vec<Serie>,
+    #[serde(default)]
+    rayons: Vec<Rayon>,
+    // Session d'inventaire en cours
+    #[serde(default)]
+    inventaire: Option<Inventaire>,
+    // Incrémentée à chaque sauvegarde: un processus qui a chargé une révision
+    // plus ancienne sait que le fichier a été réécrit depuis
+    #[serde(default)]
+    revision: u64,
+
+    // Modifications non sauvegardées (utilisé par la sauvegarde automatique)
+    #[serde(skip)]
+    modifiee: bool,
+    // Contenu du fichier au dernier chargement ou à la dernière sauvegarde (base des
+    // fusions); None tant que la bibliothèque n'a été ni chargée ni sauvegardée
+    #[serde(skip)]
+    synchro: Option<String>,
+    // Opérateur connecté; sans session (aucun compte créé, ligne de commande) tout est permis
+    #[serde(skip)]
+    session: Option<Session>,
+    // Clé du fichier chiffré; None: fichier enregistré en clair
+    #[serde(skip)]
+    cle: Option<Cle>,
 }
 
 impl Bibliotheque {
@@ -16,15 +51,75 @@ impl Bibliotheque {
         Bibliotheque {
             livres: Vec::new(),
             auteurs: Vec::new(),
+            series: Vec::new(),
+            rayons: Vec::new(),
+            inventaire: None,
+            revision: 0,
+            modifiee: false,
+            synchro: None,
+            session: None,
+            cle: None,
+        }
+    }
+
+    pub fn est_modifiee(&self) -> bool {
+        self.modifiee
+    }
+
+    // Chargée depuis le fichier ou sauvegardée au moins une fois: le fichier est le sien
+    pub fn est_synchronisee(&self) -> bool {
+        self.synchro.is_some()
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    pub fn ouvrir_session(&mut self, session: Session) {
+        self.session = Some(session);
+    }
+
+    pub fn session(&self) -> Option<&Session> {
+        self.session.as_ref()
+    }
+
+    // Vérifiée au début de chaque opération qui modifie la bibliothèque
+    pub fn autoriser(&self, permission: Permission) -> Result<(), ErreurBibliotheque> {
+        match &self.session {
+            Some(session) if !session.role.peut(permission) => Err(ErreurBibliotheque::NonAutorise(session.role)),
+            _ => Ok(()),
         }
     }
 
     // MUTABILITÉ: &mut self car on modifie le Vec interne
     // OWNERSHIP: `livre` est MOVE dans cette fonction (pas de &)
     // puis MOVE dans le Vec via push - très efficace, pas de copie!
-    pub fn ajouter_livre(&mut self, livre: Livre) {
+    pub fn ajouter_livre(&mut self, livre: Livre) -> Result<(), ErreurBibliotheque> {
+        self.autoriser(Permission::Cataloguer)?;
         self.livres.push(livre);
-        println!("Livre ajouté avec succès");
+        self.modifiee = true;
+        Ok(())
+    }
+
+    // Plusieurs opérations appliquées ensemble (ex: un livre et l'auteur créé pour lui):
+    // si l'une échoue, le catalogue revient à son état d'avant, rien n'est fait à moitié
+    pub fn en_une_fois<T>(
+        &mut self,
+        operations: impl FnOnce(&mut Bibliotheque) -> Result<T, ErreurBibliotheque>,
+    ) -> Result<T, ErreurBibliotheque> {
+        let avant = (
+            self.livres.clone(),
+            self.auteurs.clone(),
+            self.series.clone(),
+            self.rayons.clone(),
+            self.inventaire.clone(),
+            self.modifiee,
+        );
+        let resultat = operations(self);
+        if resultat.is_err() {
+            (self.livres, self.auteurs, self.series, self.rayons, self.inventaire, self.modifiee) = avant;
+        }
+        resultat
     }
 
     // LIFETIME: &self a un lifetime implicite qui garantit que la référence
@@ -47,7 +142,14 @@ impl Bibliotheque {
     }
 
     // MUTABILITÉ: &mut self car on va modifier un Livre dans le Vec
-    pub fn emprunter_livre(&mut self, id: u32) -> Result<(), String> {
+    pub fn emprunter_livre(&mut self, id: u32) -> Result<(), ErreurBibliotheque> {
+        self.emprunter_livre_le(id, aujourdhui())
+    }
+
+    // L'emprunt est daté pour l'historique et la date de retour prévue
+    fn emprunter_livre_le(&mut self, id: u32, date: NaiveDate) -> Result<(), ErreurBibliotheque> {
+        self.autoriser(Permission::Emprunter)?;
+        let operateur = self.session.as_ref().map(|s| s.nom.clone());
         // MUTABILITÉ: iter_mut() retourne un itérateur de &mut Livre
         // Ceci est crucial car on doit modifier le champ `emprunte`
         // LIFETIME: les &mut Livre ont un lifetime lié à &mut self
@@ -56,36 +158,129 @@ impl Bibliotheque {
             // On peut modifier ses champs sans prendre ownership du Livre
             Some(livre) => {
                 if livre.emprunte {
-                    Err(String::from("Ce livre est déjà emprunté"))
+                    Err(ErreurBibliotheque::DejaEmprunte)
                 } else {
                     // MUTABILITÉ: On modifie le champ via la référence mutable
                     livre.emprunte = true;
+                    livre.historique.push(Emprunt { operateur, ..Emprunt::new(date) });
+                    self.modifiee = true;
                     Ok(())
                 }
             }
-            None => Err(String::from("Livre non trouvé")),
+            None => Err(ErreurBibliotheque::LivreNonTrouve),
         }
     }
 
     // Même pattern que emprunter_livre
     // MUTABILITÉ: &mut self + iter_mut() pour modifier un Livre
-    pub fn retourner_livre(&mut self, id: u32) -> Result<(), String> {
+    pub fn retourner_livre(&mut self, id: u32) -> Result<(), ErreurBibliotheque> {
+        self.retourner_livre_le(id, aujourdhui())
+    }
+
+    fn retourner_livre_le(&mut self, id: u32, date: NaiveDate) -> Result<(), ErreurBibliotheque> {
+        self.autoriser(Permission::Emprunter)?;
         match self.livres.iter_mut().find(|l| l.id == id) {
             Some(livre) => {
                 if !livre.emprunte {
-                    Err(String::from("Ce livre n'est pas emprunté"))
+                    Err(ErreurBibliotheque::PasEmprunte)
                 } else {
                     livre.emprunte = false;
+                    // Un livre marqué emprunté dans un ancien fichier n'a pas d'emprunt daté
+                    if let Some(emprunt) = livre.historique.last_mut().filter(|e| e.retour.is_none()) {
+                        emprunt.retour = Some(date);
+                    }
+                    self.modifiee = true;
                     Ok(())
                 }
             }
-            None => Err(String::from("Livre non trouvé")),
+            None => Err(ErreurBibliotheque::LivreNonTrouve),
         }
     }
 
-    pub fn ajouter_auteur(&mut self, auteur: Auteur) {
+    // Livres correspondant à un code-barres: l'ISBN (tous les exemplaires de l'édition)
+    // ou l'ID d'un exemplaire, qui sert de code-barres d'exemplaire
+    pub fn chercher_code(&self, code: &str) -> Vec<&Livre> {
+        let code: String = code
+            .chars()
+            .filter(|c| *c != '-' && !c.is_whitespace())
+            .collect::<String>()
+            .to_uppercase();
+        let par_isbn: Vec<&Livre> = self
+            .livres
+            .iter()
+            .filter(|l| l.isbn.as_deref() == Some(code.as_str()))
+            .collect();
+        if !par_isbn.is_empty() {
+            return par_isbn;
+        }
+        match code.parse::<u32>() {
+            Ok(id) => self.livres.iter().filter(|l| l.id == id).collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    // Change titre, auteur, année et ISBN; le livre passe dans la liste du nouvel auteur
+    pub fn modifier_livre(
+        &mut self,
+        id: u32,
+        titre: String,
+        auteur_id: u32,
+        annee: u32,
+        isbn: Option<String>,
+    ) -> Result<(), ErreurBibliotheque> {
+        self.autoriser(Permission::Cataloguer)?;
+        if !self.auteurs.iter().any(|a| a.id == auteur_id) {
+            return Err(ErreurBibliotheque::AuteurNonTrouve);
+        }
+        let livre = self
+            .livres
+            .iter_mut()
+            .find(|l| l.id == id)
+            .ok_or(ErreurBibliotheque::LivreNonTrouve)?;
+
+        let ancien_auteur = livre.auteur_id;
+        livre.titre = titre;
+        livre.auteur_id = auteur_id;
+        livre.annee = annee;
+        livre.isbn = isbn;
+
+        if ancien_auteur != auteur_id {
+            if let Some(auteur) = self.trouver_auteur_mut(ancien_auteur) {
+                auteur.livres.retain(|&l| l != id);
+            }
+            self.associer_livre_auteur(id, auteur_id)?;
+        }
+        self.modifiee = true;
+        Ok(())
+    }
+
+    // Retire le livre du catalogue et de la liste de son auteur
+    pub fn supprimer_livre(&mut self, id: u32) -> Result<Livre, ErreurBibliotheque> {
+        self.autoriser(Permission::Supprimer)?;
+        let position = self
+            .livres
+            .iter()
+            .position(|l| l.id == id)
+            .ok_or(ErreurBibliotheque::LivreNonTrouve)?;
+        let livre = self.livres.remove(position);
+
+        for auteur in &mut self.auteurs {
+            auteur.livres.retain(|&l| l != id);
+        }
+        self.modifiee = true;
+        Ok(livre)
+    }
+
+    pub fn ajouter_auteur(&mut self, auteur: Auteur) -> Result<(), ErreurBibliotheque> {
+        self.autoriser(Permission::Cataloguer)?;
         self.auteurs.push(auteur);
-        println!("Auteur ajouté avec succès");
+        self.modifiee = true;
+        Ok(())
+    }
+
+    // Premier ID libre pour un nouvel auteur
+    pub fn prochain_id_auteur(&self) -> u32 {
+        self.auteurs.iter().map(|a| a.id).max().unwrap_or(0) + 1
     }
 
     pub fn lister_auteurs(&self) {
@@ -100,47 +295,529 @@ impl Bibliotheque {
         }
     }
 
-    pub fn trouver_auteur_mut(&mut self, id: u32) -> Option<&mut Auteur> {
+    // Privée: les modifications passent par les opérations qui vérifient le rôle
+    fn trouver_auteur_mut(&mut self, id: u32) -> Option<&mut Auteur> {
         self.auteurs.iter_mut().find(|a| a.id == id)
     }
 
-    pub fn associer_livre_auteur(&mut self, livre_id: u32, auteur_id: u32) -> Result<(), String> {
+    pub fn associer_livre_auteur(&mut self, livre_id: u32, auteur_id: u32) -> Result<(), ErreurBibliotheque> {
+        self.autoriser(Permission::Cataloguer)?;
         let livre = self
             .livres
             .iter()
             .find(|l| l.id == livre_id)
-            .ok_or_else(|| String::from("Livre non trouvé"))?
+            .ok_or(ErreurBibliotheque::LivreNonTrouve)?
             .clone();
 
         let auteur = self
             .trouver_auteur_mut(auteur_id)
-            .ok_or_else(|| String::from("Auteur non trouvé"))?;
+            .ok_or(ErreurBibliotheque::AuteurNonTrouve)?;
 
         auteur.add_livre(livre.id);
+        self.modifiee = true;
         Ok(())
     }
 
-    pub fn sauvegarder(&self, fichier: &str) -> io::Result<()> {
-        // OWNERSHIP: to_string_pretty emprunte &self.livres et retourne une
-        // nouvelle String dont on prend ownership
-        let json = serde_json::to_string_pretty(&self)?;
+    pub fn ajouter_serie(&mut self, serie: Serie) -> Result<(), ErreurBibliotheque> {
+        self.autoriser(Permission::Cataloguer)?;
+        self.series.push(serie);
+        self.modifiee = true;
+        Ok(())
+    }
+
+    // Premier ID libre pour une nouvelle série
+    pub fn prochain_id_serie(&self) -> u32 {
+        self.series.iter().map(|s| s.id).max().unwrap_or(0) + 1
+    }
 
-        // OWNERSHIP: `json` est MOVE dans fs::write (String implements AsRef<[u8]>)
-        fs::write(fichier, json)?;
-        println!("Bibliothèque sauvegardée dans {}", fichier);
+    pub fn trouver_serie(&self, id: u32) -> Option<&Serie> {
+        self.series.iter().find(|s| s.id == id)
+    }
+
+    // Range le livre dans une série (ou l'en retire avec None)
+    pub fn ranger_livre(&mut self, livre_id: u32, tome: Option<Tome>) -> Result<(), ErreurBibliotheque> {
+        self.autoriser(Permission::Cataloguer)?;
+        if let Some(tome) = tome
+            && self.trouver_serie(tome.serie_id).is_none()
+        {
+            return Err(ErreurBibliotheque::SerieNonTrouvee);
+        }
+        let livre = self
+            .livres
+            .iter_mut()
+            .find(|l| l.id == livre_id)
+            .ok_or(ErreurBibliotheque::LivreNonTrouve)?;
+        livre.tome = tome;
+        self.modifiee = true;
         Ok(())
     }
 
+    // Livres d'une série dans l'ordre des tomes (exemplaires d'un même tome par ID)
+    pub fn volumes(&self, serie_id: u32) -> Vec<&Livre> {
+        let mut volumes: Vec<&Livre> = self
+            .livres
+            .iter()
+            .filter(|l| l.tome.is_some_and(|t| t.serie_id == serie_id))
+            .collect();
+        volumes.sort_by_key(|l| (l.tome.map(|t| t.numero), l.id));
+        volumes
+    }
+
+    // Catalogue dans l'ordre d'affichage des listes: les livres d'une série sont
+    // regroupés, dans l'ordre des tomes, à la place du premier d'entre eux
+    pub fn livres_groupes(&self) -> Vec<&Livre> {
+        let mut livres = Vec::with_capacity(self.livres.len());
+        let mut series_vues = Vec::new();
+        for livre in &self.livres {
+            match livre.tome {
+                None => livres.push(livre),
+                Some(tome) if !series_vues.contains(&tome.serie_id) => {
+                    series_vues.push(tome.serie_id);
+                    livres.extend(self.volumes(tome.serie_id));
+                }
+                Some(_) => {}
+            }
+        }
+        livres
+    }
+
+    // Après le retour d'un tome: un exemplaire disponible du tome suivant de la série
+    pub fn tome_suivant(&self, livre_id: u32) -> Option<&Livre> {
+        let tome = self.livres.iter().find(|l| l.id == livre_id)?.tome?;
+        let volumes = self.volumes(tome.serie_id);
+        let suivant = volumes
+            .iter()
+            .filter_map(|l| l.tome)
+            .map(|t| t.numero)
+            .find(|&numero| numero > tome.numero)?;
+        volumes
+            .into_iter()
+            .find(|l| l.tome.is_some_and(|t| t.numero == suivant) && !l.emprunte)
+    }
+
+    pub fn ajouter_rayon(&mut self, rayon: Rayon) -> Result<(), ErreurBibliotheque> {
+        self.autoriser(Permission::Cataloguer)?;
+        self.rayons.push(rayon);
+        self.modifiee = true;
+        Ok(())
+    }
+
+    // Premier ID libre pour un nouveau rayon
+    pub fn prochain_id_rayon(&self) -> u32 {
+        self.rayons.iter().map(|r| r.id).max().unwrap_or(0) + 1
+    }
+
+    pub fn trouver_rayon(&self, id: u32) -> Option<&Rayon> {
+        self.rayons.iter().find(|r| r.id == id)
+    }
+
+    // Salles, dans l'ordre de création de leur premier rayon
+    pub fn salles(&self) -> Vec<&str> {
+        let mut salles: Vec<&str> = Vec::new();
+        for rayon in &self.rayons {
+            if !salles.contains(&rayon.salle.as_str()) {
+                salles.push(&rayon.salle);
+            }
+        }
+        salles
+    }
+
+    // Cote et emplacement d'un livre (None les retire)
+    pub fn placer_livre(
+        &mut self,
+        livre_id: u32,
+        cote: Option<String>,
+        emplacement: Option<Emplacement>,
+    ) -> Result<(), ErreurBibliotheque> {
+        self.autoriser(Permission::Cataloguer)?;
+        if let Some(emplacement) = emplacement
+            && self.trouver_rayon(emplacement.rayon_id).is_none()
+        {
+            return Err(ErreurBibliotheque::RayonNonTrouve);
+        }
+        let livre = self
+            .livres
+            .iter_mut()
+            .find(|l| l.id == livre_id)
+            .ok_or(ErreurBibliotheque::LivreNonTrouve)?;
+        livre.cote = cote;
+        livre.emplacement = emplacement;
+        self.modifiee = true;
+        Ok(())
+    }
+
+    // Position après le dernier livre du rayon
+    pub fn position_libre(&self, rayon_id: u32) -> u32 {
+        self.livres_du_rayon(rayon_id)
+            .last()
+            .and_then(|l| l.emplacement)
+            .map_or(1, |e| e.position + 1)
+    }
+
+    // Livres d'un rayon dans l'ordre où ils sont posés
+    pub fn livres_du_rayon(&self, rayon_id: u32) -> Vec<&Livre> {
+        let mut livres: Vec<&Livre> = self
+            .livres
+            .iter()
+            .filter(|l| l.emplacement.is_some_and(|e| e.rayon_id == rayon_id))
+            .collect();
+        livres.sort_by_key(|l| (l.emplacement.map(|e| e.position), l.id));
+        livres
+    }
+
+    // Livres mal rangés d'un rayon: ceux qui sortent de la plus longue suite de cotes
+    // déjà dans l'ordre, soit le moins de livres possible à déplacer
+    // Les livres sans cote ne sont pas vérifiés
+    pub fn mal_ranges(&self, rayon_id: u32) -> Vec<u32> {
+        let cotes: Vec<(u32, &str)> = self
+            .livres_du_rayon(rayon_id)
+            .iter()
+            .filter_map(|l| Some((l.id, l.cote.as_deref()?)))
+            .collect();
+
+        // longueur[i]: plus longue suite ordonnée qui se termine par le livre i
+        let mut longueur = vec![1; cotes.len()];
+        let mut precedent = vec![None; cotes.len()];
+        for i in 0..cotes.len() {
+            for j in 0..i {
+                if comparer_cotes(cotes[j].1, cotes[i].1) != Ordering::Greater && longueur[j] + 1 > longueur[i] {
+                    longueur[i] = longueur[j] + 1;
+                    precedent[i] = Some(j);
+                }
+            }
+        }
+
+        let mut bien_ranges = vec![false; cotes.len()];
+        let mut courant = (0..cotes.len()).max_by_key(|&i| longueur[i]);
+        while let Some(i) = courant {
+            bien_ranges[i] = true;
+            courant = precedent[i];
+        }
+        cotes
+            .iter()
+            .zip(bien_ranges)
+            .filter(|(_, bien_range)| !bien_range)
+            .map(|((id, _), _)| *id)
+            .collect()
+    }
+
+    // Démarre une session d'inventaire, ou garde celle en cours pour la reprendre
+    pub fn commencer_inventaire(&mut self) -> Result<&Inventaire, ErreurBibliotheque> {
+        self.commencer_inventaire_le(aujourdhui())
+    }
+
+    // Ouvrir une session demande de cataloguer, reprendre celle en cours seulement de pointer
+    pub fn commencer_inventaire_le(&mut self, date: NaiveDate) -> Result<&Inventaire, ErreurBibliotheque> {
+        if self.inventaire.is_none() {
+            self.autoriser(Permission::Cataloguer)?;
+            self.modifiee = true;
+        } else {
+            self.autoriser(Permission::Pointer)?;
+        }
+        Ok(self.inventaire.get_or_insert_with(|| Inventaire::new(date)))
+    }
+
+    pub fn inventaire(&self) -> Option<&Inventaire> {
+        self.inventaire.as_ref()
+    }
+
+    // Livre vu en rayon; false s'il avait déjà été pointé
+    pub fn pointer_livre(&mut self, id: u32) -> Result<bool, ErreurBibliotheque> {
+        self.autoriser(Permission::Pointer)?;
+        if !self.livres.iter().any(|l| l.id == id) {
+            return Err(ErreurBibliotheque::LivreNonTrouve);
+        }
+        let inventaire = self.inventaire.as_mut().ok_or(ErreurBibliotheque::AucunInventaire)?;
+        if inventaire.vus.contains(&id) {
+            return Ok(false);
+        }
+        inventaire.vus.push(id);
+        self.modifiee = true;
+        Ok(true)
+    }
+
+    // Code trouvé en rayon qui ne correspond à aucun livre; false s'il était déjà noté
+    pub fn pointer_inconnu(&mut self, code: &str) -> Result<bool, ErreurBibliotheque> {
+        self.autoriser(Permission::Pointer)?;
+        let inventaire = self.inventaire.as_mut().ok_or(ErreurBibliotheque::AucunInventaire)?;
+        if inventaire.inconnus.iter().any(|c| c == code) {
+            return Ok(false);
+        }
+        inventaire.inconnus.push(code.to_string());
+        self.modifiee = true;
+        Ok(true)
+    }
+
+    // Rapport de la session en cours, livres dans l'ordre du catalogue
+    pub fn rapport_inventaire(&self) -> Option<RapportInventaire> {
+        let inventaire = self.inventaire.as_ref()?;
+        let (vus, non_vus): (Vec<&Livre>, Vec<&Livre>) =
+            self.livres.iter().partition(|l| inventaire.vus.contains(&l.id));
+        Some(RapportInventaire {
+            debut: inventaire.debut,
+            vus: vus.len(),
+            total: self.livres.len(),
+            manquants: non_vus.iter().filter(|l| !l.emprunte).map(|l| l.id).collect(),
+            empruntes_en_rayon: vus.iter().filter(|l| l.emprunte).map(|l| l.id).collect(),
+            inconnus: inventaire.inconnus.clone(),
+        })
+    }
+
+    // Clôture la session et renvoie son rapport final (None sans inventaire en cours)
+    pub fn terminer_inventaire(&mut self) -> Result<Option<RapportInventaire>, ErreurBibliotheque> {
+        self.autoriser(Permission::Cataloguer)?;
+        let Some(rapport) = self.rapport_inventaire() else {
+            return Ok(None);
+        };
+        self.inventaire = None;
+        self.modifiee = true;
+        Ok(Some(rapport))
+    }
+
+    // MUTABILITÉ: &mut self car une sauvegarde réussie remet `modifiee` à false
+    // Refusée (Conflit) si un autre processus a enregistré le fichier depuis notre
+    // dernier chargement: il faut alors recharger, fusionner ou écraser
+    // Refusée (NonCharge) si le fichier existe mais n'a jamais été chargé
+    pub fn sauvegarder(&mut self, fichier: &str) -> Result<(), ErreurSauvegarde> {
+        let chemin = Path::new(fichier);
+        // Gardé jusqu'à la fin de la fonction: personne n'écrit entre la vérification et l'écriture
+        let _verrou = Verrou::prendre(chemin)?;
+        if let Some(contenu) = stockage::lire_si_existe(chemin)? {
+            let revision = stockage::revision(&contenu)?;
+            if self.synchro.is_none() {
+                return Err(ErreurSauvegarde::NonCharge);
+            }
+            if revision != self.revision {
+                return Err(ErreurSauvegarde::Conflit { revision });
+            }
+        }
+        self.ecrire(chemin)
+    }
+
+    // Remplace le fichier par notre version, quoi qu'il contienne
+    // Refusée (NonCharge) comme une sauvegarde si le fichier n'a jamais été chargé
+    pub fn ecraser(&mut self, fichier: &str) -> Result<(), ErreurSauvegarde> {
+        self.autoriser(Permission::Fichiers)?;
+        let chemin = Path::new(fichier);
+        let _verrou = Verrou::prendre(chemin)?;
+        if let Some(contenu) = stockage::lire_si_existe(chemin)? {
+            if self.synchro.is_none() {
+                return Err(ErreurSauvegarde::NonCharge);
+            }
+            self.revision = self.revision.max(stockage::revision(&contenu)?);
+        }
+        self.ecrire(chemin)
+    }
+
+    // Fusionne nos modifications avec la version du fichier, puis enregistre le résultat
+    // Renvoie le nombre de conflits (notre version a été gardée, même par-dessus celle
+    // de l'autre poste: d'où la même permission que pour écraser)
+    pub fn fusionner_fichier(&mut self, fichier: &str) -> Result<usize, ErreurSauvegarde> {
+        self.autoriser(Permission::Fichiers)?;
+        let chemin = Path::new(fichier);
+        let _verrou = Verrou::prendre(chemin)?;
+        let Some(contenu) = stockage::lire_si_existe(chemin)? else {
+            return self.ecrire(chemin).map(|()| 0);
+        };
+        // Sans chargement, pas de base commune: la fusion ne saurait rien retirer
+        let Some(base) = &self.synchro else {
+            return Err(ErreurSauvegarde::NonCharge);
+        };
+        let base: Bibliotheque = serde_json::from_str(base)?;
+        let distante = Bibliotheque::depuis_contenu(&contenu, self.cle.clone())?;
+        let conflits = self.fusionner_avec(&base, distante);
+        self.ecrire(chemin)?;
+        Ok(conflits)
+    }
+
+    // Écrit la révision suivante; à appeler sous le verrou
+    fn ecrire(&mut self, chemin: &Path) -> Result<(), ErreurSauvegarde> {
+        self.revision += 1;
+        // OWNERSHIP: to_string_pretty emprunte &self et retourne une
+        // nouvelle String dont on prend ownership
+        let ecriture = serde_json::to_string_pretty(&self)
+            .map_err(ErreurSauvegarde::from)
+            .and_then(|json| {
+                match &self.cle {
+                    Some(cle) => stockage::ecrire_atomique(chemin, &chiffrement::chiffrer(cle, self.revision, &json))?,
+                    None => stockage::ecrire_atomique(chemin, &json)?,
+                }
+                Ok(json)
+            });
+        match ecriture {
+            Ok(json) => {
+                self.synchro = Some(json);
+                self.modifiee = false;
+                Ok(())
+            }
+            Err(e) => {
+                self.revision -= 1;
+                Err(e)
+            }
+        }
+    }
+
+    // Fusion à trois (voir services::fusion); le résultat prend la révision distante
+    fn fusionner_avec(&mut self, base: &Bibliotheque, distante: Bibliotheque) -> usize {
+        // Nos ajouts qui ont pris le même ID qu'un ajout distant sont renumérotés
+        // après les plus grands IDs, références comprises
+        let livres = renumeroter(
+            fusion::ids_en_collision(&base.livres, &self.livres, &distante.livres),
+            self.livres.iter().chain(&distante.livres).map(|l| l.id),
+        );
+        let auteurs = renumeroter(
+            fusion::ids_en_collision(&base.auteurs, &self.auteurs, &distante.auteurs),
+            self.auteurs.iter().chain(&distante.auteurs).map(|a| a.id),
+        );
+        let series = renumeroter(
+            fusion::ids_en_collision(&base.series, &self.series, &distante.series),
+            self.series.iter().chain(&distante.series).map(|s| s.id),
+        );
+        let rayons = renumeroter(
+            fusion::ids_en_collision(&base.rayons, &self.rayons, &distante.rayons),
+            self.rayons.iter().chain(&distante.rayons).map(|r| r.id),
+        );
+        let nouvel_id = |table: &HashMap<u32, u32>, id: u32| table.get(&id).copied().unwrap_or(id);
+        for livre in &mut self.livres {
+            livre.id = nouvel_id(&livres, livre.id);
+            livre.auteur_id = nouvel_id(&auteurs, livre.auteur_id);
+            if let Some(tome) = &mut livre.tome {
+                tome.serie_id = nouvel_id(&series, tome.serie_id);
+            }
+            if let Some(emplacement) = &mut livre.emplacement {
+                emplacement.rayon_id = nouvel_id(&rayons, emplacement.rayon_id);
+            }
+        }
+        for auteur in &mut self.auteurs {
+            auteur.id = nouvel_id(&auteurs, auteur.id);
+            for livre_id in &mut auteur.livres {
+                *livre_id = nouvel_id(&livres, *livre_id);
+            }
+        }
+        for serie in &mut self.series {
+            serie.id = nouvel_id(&series, serie.id);
+        }
+        for rayon in &mut self.rayons {
+            rayon.id = nouvel_id(&rayons, rayon.id);
+        }
+        if let Some(inventaire) = &mut self.inventaire {
+            for livre_id in &mut inventaire.vus {
+                *livre_id = nouvel_id(&livres, *livre_id);
+            }
+        }
+
+        let (livres, conflits_livres) = fusion::fusionner(&base.livres, &self.livres, &distante.livres);
+        let (auteurs, conflits_auteurs) = fusion::fusionner(&base.auteurs, &self.auteurs, &distante.auteurs);
+        let (series, conflits_series) = fusion::fusionner(&base.series, &self.series, &distante.series);
+        let (rayons, conflits_rayons) = fusion::fusionner(&base.rayons, &self.rayons, &distante.rayons);
+        let (inventaire, conflits_inventaire) =
+            fusion::fusionner_valeur(&base.inventaire, &self.inventaire, &distante.inventaire);
+        self.livres = livres;
+        self.auteurs = auteurs;
+        self.series = series;
+        self.rayons = rayons;
+        self.inventaire = inventaire;
+
+        // Les livres de chaque auteur se déduisent des livres fusionnés
+        let livres = &self.livres;
+        for auteur in &mut self.auteurs {
+            auteur.livres.retain(|id| livres.iter().any(|l| l.id == *id && l.auteur_id == auteur.id));
+            for livre in livres.iter().filter(|l| l.auteur_id == auteur.id) {
+                if !auteur.livres.contains(&livre.id) {
+                    auteur.livres.push(livre.id);
+                }
+            }
+        }
+
+        self.revision = distante.revision;
+        self.modifiee = true;
+        conflits_livres + conflits_auteurs + conflits_series + conflits_rayons + conflits_inventaire
+    }
+
     // LIFETIME: Même principe que sauvegarder pour &str
     // Pas de &self car c'est une fonction associée (constructeur alternatif)
-    pub fn charger(fichier: &str) -> io::Result<Self> {
+    // Un fichier chiffré demande la phrase secrète (ErreurChargement::PhraseRequise)
+    pub fn charger(fichier: &str) -> Result<Self, ErreurChargement> {
         let contenu = fs::read_to_string(fichier)?;
-        let bibliotheque: Bibliotheque = serde_json::from_str(&contenu)?;
+        Bibliotheque::depuis_contenu(&contenu, None)
+    }
+
+    // Fichier chiffré ou non; les sauvegardes suivantes gardent le même chiffrement
+    pub fn charger_avec_phrase(fichier: &str, phrase: &str) -> Result<Self, ErreurChargement> {
+        let contenu = fs::read_to_string(fichier)?;
+        let cle = chiffrement::cle_du_fichier(&contenu, phrase)?;
+        Bibliotheque::depuis_contenu(&contenu, cle)
+    }
 
-        println!("Bibliothèque chargée depuis {}", fichier);
+    fn depuis_contenu(contenu: &str, cle: Option<Cle>) -> Result<Self, ErreurChargement> {
+        let (clair, cle) = match chiffrement::dechiffrer(contenu, cle.as_ref())? {
+            Some(clair) => (clair, cle),
+            // Fichier en clair: il le reste
+            None => (contenu.to_string(), None),
+        };
+        let mut bibliotheque: Bibliotheque = serde_json::from_str(&clair)?;
+        // La base des fusions est le contenu en clair
+        bibliotheque.synchro = Some(clair);
+        bibliotheque.cle = cle;
         Ok(bibliotheque)
     }
 
+    // Charge un fichier à la place de la bibliothèque en mémoire, en gardant l'opérateur connecté
+    // Le premier chargement est permis à tous; ensuite il remplacerait le fichier déjà
+    // ouvert ou les modifications locales (Permission::Fichiers)
+    pub fn ouvrir(&mut self, fichier: &str) -> Result<(), ErreurChargement> {
+        self.autoriser_ouverture()?;
+        self.recharger(fichier)
+    }
+
+    pub fn ouvrir_avec_phrase(&mut self, fichier: &str, phrase: &str) -> Result<(), ErreurChargement> {
+        self.autoriser_ouverture()?;
+        let nouvelle = Bibliotheque::charger_avec_phrase(fichier, phrase)?;
+        self.remplacer(nouvelle);
+        Ok(())
+    }
+
+    fn autoriser_ouverture(&self) -> Result<(), ErreurBibliotheque> {
+        if self.est_synchronisee() || self.modifiee {
+            self.autoriser(Permission::Fichiers)?;
+        }
+        Ok(())
+    }
+
+    // Relit le fichier déjà ouvert avec la clé connue, quand un autre poste l'a enregistré
+    // (rechargement d'office ou choix du dialog de conflit)
+    pub fn recharger(&mut self, fichier: &str) -> Result<(), ErreurChargement> {
+        let contenu = fs::read_to_string(fichier)?;
+        let nouvelle = Bibliotheque::depuis_contenu(&contenu, self.cle.clone())?;
+        self.remplacer(nouvelle);
+        Ok(())
+    }
+
+    fn remplacer(&mut self, nouvelle: Bibliotheque) {
+        *self = Bibliotheque {
+            session: self.session.take(),
+            ..nouvelle
+        };
+    }
+
+    // Nouvelle phrase secrète (avec un nouveau sel), ou retour au fichier en clair si elle est vide
+    // Enregistrée tout de suite: si l'écriture échoue, l'ancienne clé reste en place et la
+    // sauvegarde automatique ne chiffre pas avec une phrase refusée. Sur un conflit, la
+    // nouvelle clé est gardée pour la version rechargée, fusionnée ou écrasée
+    pub fn changer_phrase(&mut self, fichier: &str, phrase: &str) -> Result<(), ErreurSauvegarde> {
+        self.autoriser(Permission::Fichiers)?;
+        let ancienne = std::mem::replace(&mut self.cle, (!phrase.is_empty()).then(|| Cle::nouvelle(phrase)));
+        let modifiee = std::mem::replace(&mut self.modifiee, true);
+        match self.sauvegarder(fichier) {
+            Err(e) if !matches!(e, ErreurSauvegarde::Conflit { .. }) => {
+                self.cle = ancienne;
+                self.modifiee = modifiee;
+                Err(e)
+            }
+            resultat => resultat,
+        }
+    }
+
     fn afficher_auteur(&self, auteur: &Auteur) {
         println!("Auteur #{} - {} {}", auteur.id, auteur.prenom, auteur.nom);
         if auteur.livres.is_empty() {
@@ -164,4 +841,440 @@ impl Bibliotheque {
     pub fn get_auteurs(&self) -> &Vec<Auteur> {
         &self.auteurs
     }
+
+    pub fn get_series(&self) -> &Vec<Serie> {
+        &self.series
+    }
+
+    pub fn get_rayons(&self) -> &Vec<Rayon> {
+        &self.rayons
+    }
+}
+
+// Nouveaux IDs des éléments en collision, après le plus grand ID utilisé
+fn renumeroter(collisions: Vec<u32>, ids: impl Iterator<Item = u32>) -> HashMap<u32, u32> {
+    let max = ids.max().unwrap_or(0);
+    collisions.into_iter().zip(max + 1..).collect()
+}
+
+fn aujourdhui() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Role;
+
+    fn date(jour: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 5, jour).unwrap()
+    }
+
+    fn biblio() -> Bibliotheque {
+        let mut b = Bibliotheque::new();
+        b.ajouter_auteur(Auteur::new(1, "Antoine".to_string(), "de Saint-Exupery".to_string())).unwrap();
+        b.ajouter_auteur(Auteur::new(2, "Frank".to_string(), "Herbert".to_string())).unwrap();
+        b.ajouter_livre(Livre::new(1, "Le Petit Prince".to_string(), 1, 1943)).unwrap();
+        b.associer_livre_auteur(1, 1).unwrap();
+        b
+    }
+
+    #[test]
+    fn loans_are_recorded_in_history() {
+        let mut b = biblio();
+        b.emprunter_livre_le(1, date(1)).unwrap();
+        b.retourner_livre_le(1, date(10)).unwrap();
+        b.emprunter_livre_le(1, date(12)).unwrap();
+
+        let livre = &b.get_livres()[0];
+        assert_eq!(livre.historique.len(), 2);
+        assert_eq!(livre.historique[0].retour, Some(date(10)));
+        assert_eq!(livre.emprunt_en_cours().map(|e| e.debut), Some(date(12)));
+    }
+
+    #[test]
+    fn edit_moves_book_to_new_author() {
+        let mut b = biblio();
+        b.modifier_livre(1, "Dune".to_string(), 2, 1965, None).unwrap();
+
+        assert_eq!(b.get_livres()[0].titre, "Dune");
+        assert!(b.get_auteurs()[0].livres.is_empty());
+        assert_eq!(b.get_auteurs()[1].livres, vec![1]);
+        assert_eq!(
+            b.modifier_livre(1, "Dune".to_string(), 9, 1965, None),
+            Err(ErreurBibliotheque::AuteurNonTrouve)
+        );
+    }
+
+    #[test]
+    fn barcode_matches_isbn_then_copy_id() {
+        let mut b = biblio();
+        let mut exemplaire = Livre::new(2, "Le Petit Prince".to_string(), 1, 1943);
+        exemplaire.isbn = Some("9782070612758".to_string());
+        b.ajouter_livre(exemplaire.clone()).unwrap();
+        b.ajouter_livre(Livre { id: 3, ..exemplaire }).unwrap();
+
+        let ids = |code: &str| b.chercher_code(code).iter().map(|l| l.id).collect::<Vec<_>>();
+        assert_eq!(ids("978-2-07-061275-8"), vec![2, 3]);
+        assert_eq!(ids("1"), vec![1]);
+        assert_eq!(ids(" 3 "), vec![3]);
+        assert!(ids("42").is_empty());
+        assert!(ids("abc").is_empty());
+    }
+
+    #[test]
+    fn delete_removes_book_from_author() {
+        let mut b = biblio();
+        assert_eq!(b.supprimer_livre(1).unwrap().titre, "Le Petit Prince");
+        assert!(b.get_livres().is_empty());
+        assert!(b.get_auteurs()[0].livres.is_empty());
+        assert_eq!(b.supprimer_livre(1).err(), Some(ErreurBibliotheque::LivreNonTrouve));
+    }
+
+    #[test]
+    fn failed_batch_restores_the_catalogue() {
+        let mut b = biblio();
+        let resultat = b.en_une_fois(|b| {
+            b.ajouter_auteur(Auteur::new(3, "Isaac".to_string(), "Asimov".to_string()))?;
+            b.ajouter_serie(Serie::new(1, "Fondation".to_string()))?;
+            b.modifier_livre(9, "Fondation".to_string(), 3, 1951, None)
+        });
+        assert_eq!(resultat, Err(ErreurBibliotheque::LivreNonTrouve));
+        assert_eq!(b.get_auteurs().len(), 2);
+        assert!(b.get_series().is_empty());
+
+        b.en_une_fois(|b| b.ajouter_serie(Serie::new(1, "Fondation".to_string()))).unwrap();
+        assert_eq!(b.get_series().len(), 1);
+    }
+
+    #[test]
+    fn volunteer_lends_but_cannot_delete() {
+        let mut b = biblio();
+        b.ouvrir_session(Session {
+            nom: "Paul".to_string(),
+            role: Role::Benevole,
+        });
+        assert_eq!(b.supprimer_livre(1).err(), Some(ErreurBibliotheque::NonAutorise(Role::Benevole)));
+        assert_eq!(
+            b.ajouter_auteur(Auteur::new(3, "Isaac".to_string(), "Asimov".to_string())),
+            Err(ErreurBibliotheque::NonAutorise(Role::Benevole))
+        );
+
+        // L'emprunt garde le nom de l'opérateur qui l'a enregistré
+        b.emprunter_livre_le(1, date(1)).unwrap();
+        let emprunt = b.get_livres()[0].emprunt_en_cours().unwrap();
+        assert_eq!(emprunt.operateur.as_deref(), Some("Paul"));
+    }
+
+    #[test]
+    fn volunteer_opens_the_file_once_but_cannot_load_another() {
+        let fichier = fichier_temporaire("ouverture-benevole");
+        biblio().sauvegarder(&fichier).unwrap();
+        let mut b = Bibliotheque::new();
+        b.ouvrir_session(Session {
+            nom: "Paul".to_string(),
+            role: Role::Benevole,
+        });
+        assert_eq!(b.ouvrir(&fichier), Ok(()));
+        assert_eq!(b.get_livres().len(), 1);
+
+        assert_eq!(b.ouvrir(&fichier), Err(ErreurChargement::NonAutorise(Role::Benevole)));
+        assert_eq!(b.ecraser(&fichier), Err(ErreurSauvegarde::NonAutorise(Role::Benevole)));
+        // Le rechargement après un enregistrement d'un autre poste reste permis
+        assert_eq!(b.recharger(&fichier), Ok(()));
+        supprimer(&fichier);
+    }
+
+    // Fondation: tomes 1 et 2 (deux exemplaires), 3; un livre hors série entre deux
+    fn biblio_serie() -> Bibliotheque {
+        let mut b = biblio();
+        b.ajouter_serie(Serie::new(1, "Fondation".to_string())).unwrap();
+        for (id, numero) in [(10, 3), (11, 1), (12, 2), (13, 2)] {
+            b.ajouter_livre(Livre {
+                tome: Some(Tome { serie_id: 1, numero }),
+                ..Livre::new(id, format!("Fondation {}", numero), 2, 1951)
+            })
+            .unwrap();
+            if id == 10 {
+                b.ajouter_livre(Livre::new(20, "Dune".to_string(), 2, 1965)).unwrap();
+            }
+        }
+        b
+    }
+
+    #[test]
+    fn series_are_grouped_in_volume_order() {
+        let b = biblio_serie();
+        let ids: Vec<u32> = b.livres_groupes().iter().map(|l| l.id).collect();
+        assert_eq!(ids, vec![1, 11, 12, 13, 10, 20]);
+        assert_eq!(b.volumes(1).len(), 4);
+        assert!(b.volumes(2).is_empty());
+    }
+
+    #[test]
+    fn next_volume_is_an_available_copy() {
+        let mut b = biblio_serie();
+        assert_eq!(b.tome_suivant(11).map(|l| l.id), Some(12));
+        b.emprunter_livre(12).unwrap();
+        assert_eq!(b.tome_suivant(11).map(|l| l.id), Some(13));
+        b.emprunter_livre(13).unwrap();
+        assert_eq!(b.tome_suivant(11).map(|l| l.id), None);
+
+        // Dernier tome, livre hors série
+        assert_eq!(b.tome_suivant(10).map(|l| l.id), None);
+        assert_eq!(b.tome_suivant(20).map(|l| l.id), None);
+    }
+
+    #[test]
+    fn shelving_a_book_requires_an_existing_series() {
+        let mut b = biblio_serie();
+        let tome = Some(Tome { serie_id: 1, numero: 4 });
+        b.ranger_livre(20, tome).unwrap();
+        assert_eq!(b.tome_suivant(10).map(|l| l.id), Some(20));
+
+        let inconnue = Some(Tome { serie_id: 9, numero: 1 });
+        assert_eq!(b.ranger_livre(1, inconnue), Err(ErreurBibliotheque::SerieNonTrouvee));
+        b.ranger_livre(20, None).unwrap();
+        assert_eq!(b.volumes(1).len(), 4);
+    }
+
+    #[test]
+    fn inventory_report_lists_discrepancies() {
+        let mut b = biblio();
+        b.ajouter_livre(Livre::new(2, "Dune".to_string(), 2, 1965)).unwrap();
+        b.ajouter_livre(Livre::new(3, "Vol de nuit".to_string(), 1, 1931)).unwrap();
+        b.emprunter_livre(2).unwrap();
+        b.emprunter_livre(3).unwrap();
+        assert_eq!(b.pointer_livre(1), Err(ErreurBibliotheque::AucunInventaire));
+
+        b.commencer_inventaire_le(date(1)).unwrap();
+        assert_eq!(b.pointer_livre(2), Ok(true));
+        assert_eq!(b.pointer_livre(2), Ok(false));
+        assert_eq!(b.pointer_livre(9), Err(ErreurBibliotheque::LivreNonTrouve));
+        assert_eq!(b.pointer_inconnu("9780000000002"), Ok(true));
+        assert_eq!(b.pointer_inconnu("9780000000002"), Ok(false));
+
+        // Le livre 1 n'a pas été vu, le 2 est en rayon alors qu'il est marqué emprunté,
+        // le 3 est emprunté et absent: normal
+        let rapport = b.terminer_inventaire().unwrap().unwrap();
+        assert_eq!((rapport.debut, rapport.vus, rapport.total), (date(1), 1, 3));
+        assert_eq!(rapport.manquants, vec![1]);
+        assert_eq!(rapport.empruntes_en_rayon, vec![2]);
+        assert_eq!(rapport.inconnus, vec!["9780000000002".to_string()]);
+        assert!(b.inventaire().is_none());
+    }
+
+    #[test]
+    fn volunteer_scans_an_inventory_opened_by_a_librarian() {
+        let mut b = biblio();
+        b.ouvrir_session(Session {
+            nom: "Paul".to_string(),
+            role: Role::Benevole,
+        });
+        assert_eq!(b.commencer_inventaire_le(date(1)).err(), Some(ErreurBibliotheque::NonAutorise(Role::Benevole)));
+
+        b.ouvrir_session(Session {
+            nom: "Claire".to_string(),
+            role: Role::Bibliothecaire,
+        });
+        b.commencer_inventaire_le(date(1)).unwrap();
+        b.ouvrir_session(Session {
+            nom: "Paul".to_string(),
+            role: Role::Benevole,
+        });
+        assert!(b.commencer_inventaire_le(date(2)).is_ok());
+        assert_eq!(b.pointer_livre(1), Ok(true));
+        assert_eq!(b.terminer_inventaire(), Err(ErreurBibliotheque::NonAutorise(Role::Benevole)));
+        assert!(b.inventaire().is_some());
+    }
+
+    #[test]
+    fn inventory_session_survives_a_reload() {
+        let mut b = biblio();
+        b.commencer_inventaire_le(date(1)).unwrap();
+        b.pointer_livre(1).unwrap();
+
+        let mut b: Bibliotheque = serde_json::from_str(&serde_json::to_string(&b).unwrap()).unwrap();
+        // Reprise de la session existante, pas de nouvelle session
+        assert_eq!(b.commencer_inventaire_le(date(2)).unwrap().debut, date(1));
+        assert_eq!(b.rapport_inventaire().unwrap().vus, 1);
+    }
+
+    // Rayon 1: livres 10 à 14 posés dans cet ordre, avec leurs cotes
+    fn biblio_rayon(cotes: &[&str]) -> Bibliotheque {
+        let mut b = biblio();
+        b.ajouter_rayon(Rayon::new(1, "Adultes".to_string(), "Étagère A".to_string())).unwrap();
+        for (i, cote) in cotes.iter().enumerate() {
+            let id = 10 + i as u32;
+            b.ajouter_livre(Livre::new(id, format!("Livre {}", id), 1, 2000)).unwrap();
+            let emplacement = Emplacement { rayon_id: 1, position: i as u32 + 1 };
+            b.placer_livre(id, Some(cote.to_string()), Some(emplacement)).unwrap();
+        }
+        b
+    }
+
+    #[test]
+    fn shelf_keeps_books_in_position_order() {
+        let mut b = biblio_rayon(&["100", "200"]);
+        assert_eq!(b.position_libre(1), 3);
+        b.placer_livre(1, None, Some(Emplacement { rayon_id: 1, position: 0 })).unwrap();
+        let ids: Vec<u32> = b.livres_du_rayon(1).iter().map(|l| l.id).collect();
+        assert_eq!(ids, vec![1, 10, 11]);
+
+        let inconnu = Some(Emplacement { rayon_id: 9, position: 1 });
+        assert_eq!(b.placer_livre(1, None, inconnu), Err(ErreurBibliotheque::RayonNonTrouve));
+        assert_eq!(b.position_libre(9), 1);
+    }
+
+    #[test]
+    fn misfiled_books_are_the_fewest_to_move() {
+        assert!(biblio_rayon(&["100", "200", "300"]).mal_ranges(1).is_empty());
+        // Un livre égaré au milieu d'un rayon bien rangé
+        assert_eq!(biblio_rayon(&["100", "200", "800", "300", "400"]).mal_ranges(1), vec![12]);
+        // Deux livres posés trop tôt
+        assert_eq!(biblio_rayon(&["500", "510", "100", "200", "300"]).mal_ranges(1), vec![10, 11]);
+    }
+
+    fn fichier_temporaire(nom: &str) -> String {
+        let fichier = std::env::temp_dir().join(format!("bibliotheque-{}-{}.json", nom, std::process::id()));
+        fichier.to_str().unwrap().to_string()
+    }
+
+    fn supprimer(fichier: &str) {
+        let _ = fs::remove_file(fichier);
+        let _ = fs::remove_file(format!("{}.lock", fichier));
+    }
+
+    // Deux postes ouverts sur le même fichier, chacun ajoute un livre avec l'ID 2
+    fn deux_postes(fichier: &str) -> (Bibliotheque, Bibliotheque) {
+        biblio().sauvegarder(fichier).unwrap();
+        let mut premier = Bibliotheque::charger(fichier).unwrap();
+        let mut second = Bibliotheque::charger(fichier).unwrap();
+        premier.ajouter_livre(Livre::new(2, "Dune".to_string(), 2, 1965)).unwrap();
+        premier.associer_livre_auteur(2, 2).unwrap();
+        second.ajouter_livre(Livre::new(2, "Vol de nuit".to_string(), 1, 1931)).unwrap();
+        second.associer_livre_auteur(2, 1).unwrap();
+        premier.sauvegarder(fichier).unwrap();
+        (premier, second)
+    }
+
+    #[test]
+    fn save_over_another_process_is_refused_then_merged() {
+        let fichier = fichier_temporaire("fusion");
+        let (_, mut second) = deux_postes(&fichier);
+        assert_eq!(second.sauvegarder(&fichier), Err(ErreurSauvegarde::Conflit { revision: 2 }));
+        assert!(second.est_modifiee());
+
+        assert_eq!(second.fusionner_fichier(&fichier), Ok(0));
+        let fusion = Bibliotheque::charger(&fichier).unwrap();
+        assert_eq!(fusion.revision(), 3);
+        let titres: Vec<(u32, &str)> = fusion.get_livres().iter().map(|l| (l.id, l.titre.as_str())).collect();
+        // Notre ajout a pris l'ID suivant, y compris dans la liste de son auteur
+        assert_eq!(titres, vec![(1, "Le Petit Prince"), (2, "Dune"), (3, "Vol de nuit")]);
+        assert_eq!(fusion.get_auteurs()[0].livres, vec![1, 3]);
+        assert_eq!(fusion.get_auteurs()[1].livres, vec![2]);
+        // Plus de conflit: on repart de la version fusionnée
+        second.ajouter_serie(Serie::new(1, "Dune".to_string())).unwrap();
+        assert_eq!(second.sauvegarder(&fichier), Ok(()));
+        supprimer(&fichier);
+    }
+
+    #[test]
+    fn save_over_a_file_never_loaded_is_refused_without_conflict() {
+        let fichier = fichier_temporaire("jamais-charge");
+        biblio().sauvegarder(&fichier).unwrap();
+        let mut nouvelle = Bibliotheque::new();
+        nouvelle.ajouter_livre(Livre::new(1, "Dune".to_string(), 2, 1965)).unwrap();
+        assert_eq!(nouvelle.sauvegarder(&fichier), Err(ErreurSauvegarde::NonCharge));
+        assert_eq!(Bibliotheque::charger(&fichier).unwrap().get_livres()[0].titre, "Le Petit Prince");
+
+        nouvelle.recharger(&fichier).unwrap();
+        assert_eq!(nouvelle.sauvegarder(&fichier), Ok(()));
+        supprimer(&fichier);
+    }
+
+    #[test]
+    fn volunteer_cannot_merge_over_another_process() {
+        let fichier = fichier_temporaire("fusion-benevole");
+        let (_, mut second) = deux_postes(&fichier);
+        second.ouvrir_session(Session {
+            nom: "Paul".to_string(),
+            role: Role::Benevole,
+        });
+        assert_eq!(second.fusionner_fichier(&fichier), Err(ErreurSauvegarde::NonAutorise(Role::Benevole)));
+        assert_eq!(Bibliotheque::charger(&fichier).unwrap().revision(), 2);
+        assert!(second.est_modifiee());
+        supprimer(&fichier);
+    }
+
+    #[test]
+    fn overwrite_and_merge_refuse_a_file_never_loaded() {
+        let fichier = fichier_temporaire("ecrasement-jamais-charge");
+        biblio().sauvegarder(&fichier).unwrap();
+        let mut vide = Bibliotheque::new();
+        assert_eq!(vide.ecraser(&fichier), Err(ErreurSauvegarde::NonCharge));
+        assert_eq!(vide.fusionner_fichier(&fichier), Err(ErreurSauvegarde::NonCharge));
+        assert_eq!(Bibliotheque::charger(&fichier).unwrap().get_livres().len(), 1);
+        supprimer(&fichier);
+    }
+
+    #[test]
+    fn overwrite_keeps_only_the_local_version() {
+        let fichier = fichier_temporaire("ecrasement");
+        let (_, mut second) = deux_postes(&fichier);
+        assert_eq!(second.ecraser(&fichier), Ok(()));
+
+        let fichier_ecrase = Bibliotheque::charger(&fichier).unwrap();
+        assert_eq!(fichier_ecrase.revision(), 3);
+        let titres: Vec<&str> = fichier_ecrase.get_livres().iter().map(|l| l.titre.as_str()).collect();
+        assert_eq!(titres, vec!["Le Petit Prince", "Vol de nuit"]);
+        supprimer(&fichier);
+    }
+
+    #[test]
+    fn encrypted_file_needs_its_passphrase_and_stays_encrypted() {
+        let fichier = fichier_temporaire("chiffre");
+        let mut b = biblio();
+        b.changer_phrase(&fichier, "phrase secrète").unwrap();
+        assert!(!fs::read_to_string(&fichier).unwrap().contains("Petit Prince"));
+
+        assert_eq!(Bibliotheque::charger(&fichier).err(), Some(ErreurChargement::PhraseRequise));
+        assert_eq!(
+            Bibliotheque::charger_avec_phrase(&fichier, "autre").err(),
+            Some(ErreurChargement::PhraseIncorrecte)
+        );
+        let mut autre_poste = Bibliotheque::charger_avec_phrase(&fichier, "phrase secrète").unwrap();
+        assert_eq!(autre_poste.get_livres()[0].titre, "Le Petit Prince");
+
+        // Sauvegarde, fusion et relecture gardent la clé sans redemander la phrase
+        autre_poste.ajouter_livre(Livre::new(2, "Dune".to_string(), 2, 1965)).unwrap();
+        autre_poste.sauvegarder(&fichier).unwrap();
+        assert!(!fs::read_to_string(&fichier).unwrap().contains("Dune"));
+        b.ajouter_serie(Serie::new(1, "Fondation".to_string())).unwrap();
+        assert_eq!(b.fusionner_fichier(&fichier), Ok(0));
+        autre_poste.recharger(&fichier).unwrap();
+        assert_eq!((autre_poste.get_livres().len(), autre_poste.get_series().len()), (2, 1));
+
+        // Phrase vide: retour au fichier en clair
+        b.changer_phrase(&fichier, "").unwrap();
+        assert_eq!(Bibliotheque::charger(&fichier).unwrap().get_livres().len(), 2);
+        supprimer(&fichier);
+    }
+
+    #[test]
+    fn failed_passphrase_change_keeps_the_previous_key() {
+        let fichier = fichier_temporaire("phrase-refusee");
+        biblio().sauvegarder(&fichier).unwrap();
+        // Jamais chargé: l'enregistrement est refusé, le fichier reste en clair
+        let mut b = biblio();
+        assert_eq!(b.changer_phrase(&fichier, "phrase"), Err(ErreurSauvegarde::NonCharge));
+        assert!(b.cle.is_none());
+
+        // La sauvegarde suivante n'est pas chiffrée avec la phrase refusée
+        b.recharger(&fichier).unwrap();
+        b.ajouter_livre(Livre::new(2, "Dune".to_string(), 2, 1965)).unwrap();
+        b.sauvegarder(&fichier).unwrap();
+        assert!(fs::read_to_string(&fichier).unwrap().contains("Dune"));
+        supprimer(&fichier);
+    }
 }
@@ -0,0 +1,164 @@
+use super::ErreurChargement;
+use argon2::Argon2;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// Chiffrement optionnel du fichier de la bibliothèque par une phrase secrète
+// Le fichier chiffré reste un JSON: une enveloppe dont seule la révision est en clair,
+// pour que la détection des sauvegardes concurrentes (stockage::revision) marche sans la clé
+
+const ALGORITHME: &str = "xchacha20poly1305-argon2id";
+
+// Clé dérivée de la phrase secrète et du sel du fichier; la phrase n'est pas conservée
+#[derive(Clone, PartialEq, Eq)]
+pub struct Cle {
+    sel: [u8; 16],
+    cle: [u8; 32],
+}
+
+// Jamais la clé dans les traces
+impl fmt::Debug for Cle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Cle(..)")
+    }
+}
+
+impl Cle {
+    // Nouvelle phrase: nouveau sel aléatoire
+    pub fn nouvelle(phrase: &str) -> Cle {
+        let mut sel = [0u8; 16];
+        OsRng.fill_bytes(&mut sel);
+        Cle::deriver(phrase, sel)
+    }
+
+    // Argon2id aux paramètres par défaut: lent exprès, contre les essais de phrases
+    fn deriver(phrase: &str, sel: [u8; 16]) -> Cle {
+        let mut cle = [0u8; 32];
+        Argon2::default()
+            .hash_password_into(phrase.as_bytes(), &sel, &mut cle)
+            .expect("sel et clé de tailles acceptées par Argon2");
+        Cle { sel, cle }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Enveloppe {
+    chiffrement: String,
+    // Authentifiée avec les données: la modifier rend le fichier illisible
+    revision: u64,
+    sel: String,
+    nonce: String,
+    donnees: String,
+}
+
+// Enveloppe du fichier, None pour un fichier en clair
+fn enveloppe(contenu: &str) -> Result<Option<Enveloppe>, ErreurChargement> {
+    #[derive(Deserialize)]
+    struct Entete {
+        chiffrement: Option<String>,
+    }
+    match serde_json::from_str::<Entete>(contenu)?.chiffrement {
+        Some(algorithme) if algorithme == ALGORITHME => Ok(Some(serde_json::from_str(contenu)?)),
+        Some(_) => Err(ErreurChargement::Illisible),
+        None => Ok(None),
+    }
+}
+
+fn decoder<const N: usize>(texte: &str) -> Result<[u8; N], ErreurChargement> {
+    BASE64
+        .decode(texte)
+        .ok()
+        .and_then(|octets| octets.try_into().ok())
+        .ok_or(ErreurChargement::Illisible)
+}
+
+pub fn chiffrer(cle: &Cle, revision: u64, clair: &str) -> String {
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let donnees = XChaCha20Poly1305::new(&cle.cle.into())
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: clair.as_bytes(),
+                aad: &revision.to_le_bytes(),
+            },
+        )
+        .expect("XChaCha20-Poly1305 ne refuse que des messages de plus de 256 Gio");
+    let enveloppe = Enveloppe {
+        chiffrement: ALGORITHME.to_string(),
+        revision,
+        sel: BASE64.encode(cle.sel),
+        nonce: BASE64.encode(nonce),
+        donnees: BASE64.encode(donnees),
+    };
+    serde_json::to_string_pretty(&enveloppe).expect("enveloppe sérialisable")
+}
+
+// Contenu en clair d'un fichier chiffré, None si le fichier n'est pas chiffré
+// Une clé dérivée avec un autre sel (phrase changée par un autre poste) ne suffit pas
+pub fn dechiffrer(contenu: &str, cle: Option<&Cle>) -> Result<Option<String>, ErreurChargement> {
+    let Some(enveloppe) = enveloppe(contenu)? else {
+        return Ok(None);
+    };
+    let sel = decoder::<16>(&enveloppe.sel)?;
+    let cle = cle.filter(|c| c.sel == sel).ok_or(ErreurChargement::PhraseRequise)?;
+    let nonce = decoder::<24>(&enveloppe.nonce)?;
+    let donnees = BASE64.decode(&enveloppe.donnees).map_err(|_| ErreurChargement::Illisible)?;
+    let clair = XChaCha20Poly1305::new(&cle.cle.into())
+        .decrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: &donnees,
+                aad: &enveloppe.revision.to_le_bytes(),
+            },
+        )
+        .map_err(|_| ErreurChargement::PhraseIncorrecte)?;
+    String::from_utf8(clair).map(Some).map_err(|_| ErreurChargement::Illisible)
+}
+
+// Clé d'un fichier chiffré, dérivée de la phrase et du sel lu dans le fichier
+// None pour un fichier en clair; une phrase fausse n'est détectée qu'au déchiffrement
+pub fn cle_du_fichier(contenu: &str, phrase: &str) -> Result<Option<Cle>, ErreurChargement> {
+    match enveloppe(contenu)? {
+        Some(enveloppe) => Ok(Some(Cle::deriver(phrase, decoder(&enveloppe.sel)?))),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::stockage;
+
+    #[test]
+    fn encrypted_file_keeps_only_the_revision_in_clear() {
+        let cle = Cle::nouvelle("phrase secrète");
+        let contenu = chiffrer(&cle, 7, "{\"livres\": [\"Le Petit Prince\"]}");
+        assert!(!contenu.contains("Petit Prince"));
+        assert_eq!(stockage::revision(&contenu), Ok(7));
+
+        let relue = cle_du_fichier(&contenu, "phrase secrète").unwrap();
+        assert_eq!(relue.as_ref(), Some(&cle));
+        assert_eq!(
+            dechiffrer(&contenu, relue.as_ref()).unwrap().as_deref(),
+            Some("{\"livres\": [\"Le Petit Prince\"]}")
+        );
+        assert_eq!(dechiffrer(&contenu, None), Err(ErreurChargement::PhraseRequise));
+        let fausse = cle_du_fichier(&contenu, "autre phrase").unwrap();
+        assert_eq!(dechiffrer(&contenu, fausse.as_ref()), Err(ErreurChargement::PhraseIncorrecte));
+    }
+
+    #[test]
+    fn tampered_revision_is_rejected_and_clear_files_pass_through() {
+        let cle = Cle::nouvelle("phrase");
+        let contenu = chiffrer(&cle, 3, "{}").replace("\"revision\": 3", "\"revision\": 4");
+        assert_eq!(dechiffrer(&contenu, Some(&cle)), Err(ErreurChargement::PhraseIncorrecte));
+
+        assert_eq!(dechiffrer("{\"revision\": 3}", Some(&cle)), Ok(None));
+        assert_eq!(cle_du_fichier("{\"revision\": 3}", "phrase"), Ok(None));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::i18n::{t, tf};
+use crate::models::Role;
+use std::fmt;
+use std::io;
+
+// Erreurs des opérations sur la bibliothèque
+// Le texte affiché dépend de la langue de l'interface
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErreurBibliotheque {
+    LivreNonTrouve,
+    AuteurNonTrouve,
+    SerieNonTrouvee,
+    RayonNonTrouve,
+    DejaEmprunte,
+    PasEmprunte,
+    AucunInventaire,
+    // Opération refusée au rôle de l'opérateur connecté
+    NonAutorise(Role),
+}
+
+impl fmt::Display for ErreurBibliotheque {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cle = match self {
+            ErreurBibliotheque::LivreNonTrouve => "error.book_not_found",
+            ErreurBibliotheque::AuteurNonTrouve => "error.author_not_found",
+            ErreurBibliotheque::SerieNonTrouvee => "error.series_not_found",
+            ErreurBibliotheque::RayonNonTrouve => "error.shelf_not_found",
+            ErreurBibliotheque::DejaEmprunte => "error.already_borrowed",
+            ErreurBibliotheque::PasEmprunte => "error.not_borrowed",
+            ErreurBibliotheque::AucunInventaire => "error.no_inventory",
+            ErreurBibliotheque::NonAutorise(role) => return f.write_str(&non_autorise(*role)),
+        };
+        f.write_str(t(cle))
+    }
+}
+
+impl std::error::Error for ErreurBibliotheque {}
+
+fn non_autorise(role: Role) -> String {
+    tf("error.not_allowed", &[("role", &t(role.cle()))])
+}
+
+// Échec d'une sauvegarde
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErreurSauvegarde {
+    // Un autre processus a enregistré le fichier depuis notre dernier chargement
+    Conflit { revision: u64 },
+    // Le fichier existe mais n'a jamais été chargé: l'enregistrer le remplacerait en entier
+    NonCharge,
+    NonAutorise(Role),
+    // Fichier illisible ou impossible à écrire
+    Fichier(String),
+}
+
+impl fmt::Display for ErreurSauvegarde {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErreurSauvegarde::Conflit { revision } => f.write_str(&tf("error.save_conflict", &[("revision", revision)])),
+            ErreurSauvegarde::NonCharge => f.write_str(t("error.save_not_loaded")),
+            ErreurSauvegarde::NonAutorise(role) => f.write_str(&non_autorise(*role)),
+            ErreurSauvegarde::Fichier(detail) => f.write_str(detail),
+        }
+    }
+}
+
+impl std::error::Error for ErreurSauvegarde {}
+
+impl From<io::Error> for ErreurSauvegarde {
+    fn from(e: io::Error) -> Self {
+        ErreurSauvegarde::Fichier(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for ErreurSauvegarde {
+    fn from(e: serde_json::Error) -> Self {
+        ErreurSauvegarde::Fichier(e.to_string())
+    }
+}
+
+// Refus de Bibliotheque::autoriser avant une écriture
+impl From<ErreurBibliotheque> for ErreurSauvegarde {
+    fn from(e: ErreurBibliotheque) -> Self {
+        match e {
+            ErreurBibliotheque::NonAutorise(role) => ErreurSauvegarde::NonAutorise(role),
+            e => ErreurSauvegarde::Fichier(e.to_string()),
+        }
+    }
+}
+
+// Échec de la lecture du fichier de la bibliothèque
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErreurChargement {
+    // Fichier chiffré: la phrase secrète est à demander
+    PhraseRequise,
+    PhraseIncorrecte,
+    // Enveloppe chiffrée endommagée ou d'un format inconnu
+    Illisible,
+    NonAutorise(Role),
+    Fichier(String),
+}
+
+impl fmt::Display for ErreurChargement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErreurChargement::PhraseRequise => f.write_str(t("error.passphrase_required")),
+            ErreurChargement::PhraseIncorrecte => f.write_str(t("error.passphrase_wrong")),
+            ErreurChargement::Illisible => f.write_str(t("error.encrypted_unreadable")),
+            ErreurChargement::NonAutorise(role) => f.write_str(&non_autorise(*role)),
+            ErreurChargement::Fichier(detail) => f.write_str(detail),
+        }
+    }
+}
+
+impl std::error::Error for ErreurChargement {}
+
+impl From<io::Error> for ErreurChargement {
+    fn from(e: io::Error) -> Self {
+        ErreurChargement::Fichier(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for ErreurChargement {
+    fn from(e: serde_json::Error) -> Self {
+        ErreurChargement::Fichier(e.to_string())
+    }
+}
+
+// Refus de Bibliotheque::autoriser avant un chargement
+impl From<ErreurBibliotheque> for ErreurChargement {
+    fn from(e: ErreurBibliotheque) -> Self {
+        match e {
+            ErreurBibliotheque::NonAutorise(role) => ErreurChargement::NonAutorise(role),
+            e => ErreurChargement::Fichier(e.to_string()),
+        }
+    }
+}
+
+// Relecture du fichier pendant une fusion
+impl From<ErreurChargement> for ErreurSauvegarde {
+    fn from(e: ErreurChargement) -> Self {
+        ErreurSauvegarde::Fichier(e.to_string())
+    }
+}
+
+// Échec de la gestion des comptes d'opérateurs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErreurOperateur {
+    // Seul un administrateur crée des comptes (n'importe qui crée le premier)
+    NonAutorise,
+    PremierCompteAdmin,
+    NomPris(String),
+    // Fichier des opérateurs illisible ou impossible à écrire
+    Fichier(String),
+    Hachage(String),
+}
+
+impl fmt::Display for ErreurOperateur {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErreurOperateur::NonAutorise => f.write_str(t("error.operators_admin_only")),
+            ErreurOperateur::PremierCompteAdmin => f.write_str(t("error.first_operator_admin")),
+            ErreurOperateur::NomPris(nom) => f.write_str(&tf("error.operator_exists", &[("nom", nom)])),
+            ErreurOperateur::Fichier(detail) => f.write_str(&tf("error.operators_file", &[("detail", detail)])),
+            ErreurOperateur::Hachage(detail) => f.write_str(&tf("error.password_hash", &[("detail", detail)])),
+        }
+    }
+}
+
+impl std::error::Error for ErreurOperateur {}
+
+impl From<io::Error> for ErreurOperateur {
+    fn from(e: io::Error) -> Self {
+        ErreurOperateur::Fichier(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for ErreurOperateur {
+    fn from(e: serde_json::Error) -> Self {
+        ErreurOperateur::Fichier(e.to_string())
+    }
+}
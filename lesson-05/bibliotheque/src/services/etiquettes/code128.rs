@@ -0,0 +1,90 @@
+use super::ErreurEtiquette;
+use crate::i18n::tf;
+
+// Largeurs (en modules) des barres et espaces de chaque symbole, en commençant par une barre
+// Les valeurs 103 à 105 sont les départs A, B et C, 106 l'arrêt
+const MOTIFS: [&str; 107] = [
+    "212222", "222122", "222221", "121223", "121322", "131222", "122213", "122312", "132212", "221213",
+    "221312", "231212", "112232", "122132", "122231", "113222", "123122", "123221", "223211", "221132",
+    "221231", "213212", "223112", "312131", "311222", "321122", "321221", "312212", "322112", "322211",
+    "212123", "212321", "232121", "111323", "131123", "131321", "112313", "132113", "132311", "211313",
+    "231113", "231311", "112133", "112331", "132131", "113123", "113321", "133121", "313121", "211331",
+    "231131", "213113", "213311", "213131", "311123", "311321", "331121", "312113", "312311", "332111",
+    "314111", "221411", "431111", "111224", "111422", "121124", "121421", "141122", "141221", "112214",
+    "112412", "122114", "122411", "142112", "142211", "241211", "221114", "413111", "241112", "134111",
+    "111242", "121142", "121241", "114212", "124112", "124211", "411212", "421112", "421211", "212141",
+    "214121", "412121", "111143", "111341", "131141", "114113", "114311", "411113", "411311", "113141",
+    "114131", "311141", "411131", "211412", "211214", "211232", "2331112",
+];
+
+const DEPART_B: usize = 104;
+const DEPART_C: usize = 105;
+const ARRET: usize = 106;
+
+// Marge blanche obligatoire de part et d'autre du code, en modules
+pub const ZONE_CALME: u32 = 10;
+
+// Valeurs des symboles, clé de contrôle et arrêt compris
+// Un nombre pair de chiffres passe en jeu C (deux chiffres par symbole), le reste en jeu B
+fn symboles(texte: &str) -> Result<Vec<usize>, ErreurEtiquette> {
+    let mut valeurs = Vec::new();
+    if !texte.is_empty() && texte.len().is_multiple_of(2) && texte.bytes().all(|b| b.is_ascii_digit()) {
+        valeurs.push(DEPART_C);
+        for paire in texte.as_bytes().chunks(2) {
+            valeurs.push(usize::from((paire[0] - b'0') * 10 + (paire[1] - b'0')));
+        }
+    } else {
+        valeurs.push(DEPART_B);
+        for c in texte.chars() {
+            if !(' '..='~').contains(&c) {
+                return Err(ErreurEtiquette::Code(tf("labels.invalid_code", &[("code", &texte)])));
+            }
+            valeurs.push(c as usize - 32);
+        }
+    }
+
+    let controle = valeurs.iter().enumerate().map(|(i, v)| i.max(1) * v).sum::<usize>() % 103;
+    valeurs.push(controle);
+    valeurs.push(ARRET);
+    Ok(valeurs)
+}
+
+// Largeurs alternées barre / espace du code complet, sans les zones calmes
+pub fn encoder(texte: &str) -> Result<Vec<u32>, ErreurEtiquette> {
+    Ok(symboles(texte)?
+        .into_iter()
+        .flat_map(|valeur| MOTIFS[valeur].chars())
+        .map(|c| c.to_digit(10).unwrap_or(0))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_symbol_is_eleven_modules_and_unique() {
+        for (valeur, motif) in MOTIFS.iter().enumerate() {
+            let largeur: u32 = motif.chars().filter_map(|c| c.to_digit(10)).sum();
+            assert_eq!(largeur, if valeur == ARRET { 13 } else { 11 }, "symbole {}", valeur);
+        }
+        let mut uniques = MOTIFS.to_vec();
+        uniques.sort();
+        uniques.dedup();
+        assert_eq!(uniques.len(), MOTIFS.len());
+    }
+
+    #[test]
+    fn digits_use_set_c_and_text_set_b() {
+        // 105 + 0×1 + 0×2 + 0×3 + 2×4 = 113, modulo 103 = 10
+        assert_eq!(symboles("00000002").unwrap(), vec![DEPART_C, 0, 0, 0, 2, 10, ARRET]);
+        // 104 + 33×1 + 34×2 = 205, modulo 103 = 102 ("A" = 33, "B" = 34)
+        assert_eq!(symboles("AB").unwrap(), vec![DEPART_B, 33, 34, 102, ARRET]);
+        assert_eq!(symboles("123").unwrap()[0], DEPART_B);
+        assert!(matches!(symboles("é"), Err(ErreurEtiquette::Code(_))));
+
+        let barres = encoder("00000002").unwrap();
+        assert_eq!(barres.iter().sum::<u32>(), 11 * 6 + 13);
+        assert_eq!(&barres[..6], &[2, 1, 1, 2, 3, 2]);
+    }
+}
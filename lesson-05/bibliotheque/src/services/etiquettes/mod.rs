@@ -0,0 +1,538 @@
+pub mod code128;
+mod pdf;
+mod svg;
+
+use crate::i18n::{t, tf};
+use crate::models::Livre;
+use crate::models::emplacement::comparer_cotes;
+use crate::services::Bibliotheque;
+use chrono::NaiveDate;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Dossier où les planches sont écrites par défaut
+pub const DOSSIER_ETIQUETTES: &str = "etiquettes";
+
+// Marge intérieure d'une étiquette, en millimètres
+const MARGE: f32 = 2.0;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErreurEtiquette {
+    // Texte impossible à coder en Code128
+    Code(String),
+    // Aucun livre à étiqueter
+    Aucune,
+    // Dossier ou fichier impossible à écrire
+    Fichier(String),
+}
+
+impl fmt::Display for ErreurEtiquette {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let texte = match self {
+            ErreurEtiquette::Code(detail) => detail.clone(),
+            ErreurEtiquette::Aucune => t("labels.none").to_string(),
+            ErreurEtiquette::Fichier(detail) => tf("error.labels_file", &[("detail", detail)]),
+        };
+        f.write_str(&texte)
+    }
+}
+
+impl std::error::Error for ErreurEtiquette {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeEtiquette {
+    // Étiquette de dos: cote et début du titre
+    Dos,
+    // Code-barres d'exemplaire, lu par l'écran de scan
+    CodeBarres,
+}
+
+impl TypeEtiquette {
+    pub const TOUS: [TypeEtiquette; 2] = [TypeEtiquette::Dos, TypeEtiquette::CodeBarres];
+
+    // Préfixe du nom des fichiers
+    pub fn nom(self) -> &'static str {
+        match self {
+            TypeEtiquette::Dos => "dos",
+            TypeEtiquette::CodeBarres => "codes-barres",
+        }
+    }
+
+    pub fn libelle(self) -> &'static str {
+        match self {
+            TypeEtiquette::Dos => t("labels.kind.spine"),
+            TypeEtiquette::CodeBarres => t("labels.kind.barcode"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatEtiquette {
+    // Un document, une page par planche
+    Pdf,
+    // Un fichier par planche
+    Svg,
+}
+
+impl FormatEtiquette {
+    pub const TOUS: [FormatEtiquette; 2] = [FormatEtiquette::Pdf, FormatEtiquette::Svg];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            FormatEtiquette::Pdf => "pdf",
+            FormatEtiquette::Svg => "svg",
+        }
+    }
+
+    pub fn libelle(self) -> &'static str {
+        match self {
+            FormatEtiquette::Pdf => "PDF",
+            FormatEtiquette::Svg => "SVG",
+        }
+    }
+}
+
+// Planche d'étiquettes adhésives du commerce, dimensions en millimètres
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Papier {
+    pub nom: &'static str,
+    // Largeur et hauteur de la feuille
+    pub page: (f32, f32),
+    pub colonnes: u32,
+    pub lignes: u32,
+    // Coin de la première étiquette
+    pub marge: (f32, f32),
+    // Distance entre les coins de deux étiquettes voisines
+    pub pas: (f32, f32),
+    pub etiquette: (f32, f32),
+}
+
+impl Papier {
+    pub fn par_planche(&self) -> u32 {
+        self.colonnes * self.lignes
+    }
+
+    // Coin haut gauche de l'étiquette `index` de la planche, ligne par ligne
+    fn coin(&self, index: u32) -> (f32, f32) {
+        let (colonne, ligne) = (index % self.colonnes, index / self.colonnes);
+        (
+            self.marge.0 + colonne as f32 * self.pas.0,
+            self.marge.1 + ligne as f32 * self.pas.1,
+        )
+    }
+
+    pub fn libelle(&self) -> String {
+        tf(
+            "labels.paper",
+            &[
+                ("nom", &self.nom),
+                ("n", &self.par_planche()),
+                ("largeur", &format!("{:.1}", self.etiquette.0)),
+                ("hauteur", &format!("{:.1}", self.etiquette.1)),
+            ],
+        )
+    }
+}
+
+const A4: (f32, f32) = (210.0, 297.0);
+const LETTER: (f32, f32) = (215.9, 279.4);
+
+pub const PAPIERS: [Papier; 3] = [
+    Papier {
+        nom: "Avery L7651",
+        page: A4,
+        colonnes: 5,
+        lignes: 13,
+        marge: (4.75, 10.7),
+        pas: (40.6, 21.2),
+        etiquette: (38.1, 21.2),
+    },
+    Papier {
+        nom: "Avery L7160",
+        page: A4,
+        colonnes: 3,
+        lignes: 7,
+        marge: (7.2, 15.15),
+        pas: (66.0, 38.1),
+        etiquette: (63.5, 38.1),
+    },
+    Papier {
+        nom: "Avery 5160",
+        page: LETTER,
+        colonnes: 3,
+        lignes: 10,
+        marge: (4.8, 12.7),
+        pas: (69.85, 25.4),
+        etiquette: (66.675, 25.4),
+    },
+];
+
+// Élément d'une planche, en millimètres depuis le coin haut gauche de la feuille
+#[derive(Debug, Clone, PartialEq)]
+enum Trace {
+    Barre { x: f32, y: f32, largeur: f32, hauteur: f32 },
+    // Texte centré sur `x`, `y` est la ligne de base; taille en points
+    Texte { x: f32, y: f32, taille: f32, gras: bool, texte: String },
+}
+
+const MM_PAR_POINT: f32 = 25.4 / 72.0;
+
+// Ce qu'on imprime sur une étiquette
+#[derive(Debug, Clone, PartialEq)]
+enum Contenu {
+    Dos { cote: String, titre: String },
+    CodeBarres { code: String, titre: String },
+}
+
+// Résultat d'une impression
+#[derive(Debug, Clone, PartialEq)]
+pub struct Impression {
+    pub fichiers: Vec<PathBuf>,
+    pub planches: usize,
+    pub etiquettes: usize,
+    // Livres sans cote, sans étiquette de dos
+    pub sans_cote: usize,
+}
+
+// Code-barres d'un exemplaire: son ID sur 8 chiffres, que `chercher_code` relit comme un ID
+pub fn code_exemplaire(livre_id: u32) -> String {
+    format!("{:08}", livre_id)
+}
+
+// Trois premières lettres du titre, articles de tête ignorés: "Le Petit Prince" -> "PET"
+fn abreger_titre(titre: &str) -> String {
+    const ARTICLES: [&str; 10] = ["le", "la", "les", "un", "une", "des", "du", "the", "a", "an"];
+    let mut mots = titre.split_whitespace().peekable();
+    while mots.peek().is_some_and(|mot| ARTICLES.contains(&mot.to_lowercase().as_str())) {
+        mots.next();
+    }
+    let reste: Vec<&str> = mots.collect();
+    let reste = reste.join(" ");
+    let reste = ["l'", "L'", "l’", "L’", "d'", "D'"]
+        .iter()
+        .find_map(|elision| reste.strip_prefix(elision))
+        .unwrap_or(&reste);
+    reste.chars().filter(|c| c.is_alphanumeric()).take(3).flat_map(char::to_uppercase).collect()
+}
+
+// Livres dans l'ordre de rangement: par cote, ceux sans cote à la fin
+fn livres_ordonnes(biblio: &Bibliotheque) -> Vec<&Livre> {
+    let mut livres: Vec<&Livre> = biblio.get_livres().iter().collect();
+    livres.sort_by(|a, b| match (&a.cote, &b.cote) {
+        (Some(x), Some(y)) => comparer_cotes(x, y).then(a.id.cmp(&b.id)),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a.id.cmp(&b.id),
+    });
+    livres
+}
+
+fn contenus(type_etiquette: TypeEtiquette, biblio: &Bibliotheque) -> (Vec<Contenu>, usize) {
+    let livres = livres_ordonnes(biblio);
+    match type_etiquette {
+        TypeEtiquette::Dos => {
+            let contenus: Vec<Contenu> = livres
+                .iter()
+                .filter_map(|l| {
+                    let cote = l.cote.clone()?;
+                    Some(Contenu::Dos {
+                        cote,
+                        titre: abreger_titre(&l.titre),
+                    })
+                })
+                .collect();
+            let sans_cote = livres.len() - contenus.len();
+            (contenus, sans_cote)
+        }
+        TypeEtiquette::CodeBarres => (
+            livres
+                .iter()
+                .map(|l| Contenu::CodeBarres {
+                    code: code_exemplaire(l.id),
+                    titre: l.titre.clone(),
+                })
+                .collect(),
+            0,
+        ),
+    }
+}
+
+// Largeur approchée d'un texte en Helvetica, en millimètres
+fn largeur_texte(texte: &str, taille: f32, gras: bool) -> f32 {
+    let em: f32 = texte
+        .chars()
+        .map(|c| match c {
+            ' ' | 'i' | 'j' | 'l' | '\'' | '.' | ',' | ':' | ';' | '!' | '|' => 0.28,
+            'f' | 't' | 'r' | 'I' | '-' | '(' | ')' => 0.35,
+            'm' | 'w' | 'M' | 'W' => 0.85,
+            '0'..='9' => 0.556,
+            c if c.is_uppercase() => 0.68,
+            _ => 0.53,
+        })
+        .sum();
+    em * taille * MM_PAR_POINT * if gras { 1.06 } else { 1.0 }
+}
+
+// Texte raccourci avec "…" pour tenir dans `largeur`
+fn tronquer(texte: &str, taille: f32, largeur: f32) -> String {
+    if largeur_texte(texte, taille, false) <= largeur {
+        return texte.to_string();
+    }
+    let mut court: String = texte.to_string();
+    while !court.is_empty() && largeur_texte(&format!("{}…", court), taille, false) > largeur {
+        court.pop();
+    }
+    format!("{}…", court.trim_end())
+}
+
+fn dessiner(contenu: &Contenu, (x, y): (f32, f32), (largeur, hauteur): (f32, f32)) -> Result<Vec<Trace>, ErreurEtiquette> {
+    let centre = x + largeur / 2.0;
+    let mut traces = Vec::new();
+    match contenu {
+        // Une ligne par partie de la cote ("843.9" / "SAI"), puis l'abréviation du titre
+        Contenu::Dos { cote, titre } => {
+            let mut lignes: Vec<(&str, bool)> = cote.split_whitespace().map(|partie| (partie, true)).collect();
+            lignes.push((titre, false));
+            let interligne = ((hauteur - 2.0 * MARGE) / lignes.len() as f32).min(7.0);
+            let taille = interligne * 0.75 / MM_PAR_POINT;
+            let haut = y + (hauteur - interligne * lignes.len() as f32) / 2.0;
+            for (i, (texte, gras)) in lignes.into_iter().enumerate() {
+                traces.push(Trace::Texte {
+                    x: centre,
+                    y: haut + (i as f32 + 0.8) * interligne,
+                    taille,
+                    gras,
+                    texte: texte.to_string(),
+                });
+            }
+        }
+        // Titre en haut, barres au milieu, code en clair dessous
+        Contenu::CodeBarres { code, titre } => {
+            let taille = 7.0;
+            let ligne = taille * MM_PAR_POINT * 1.2;
+            traces.push(Trace::Texte {
+                x: centre,
+                y: y + MARGE + ligne * 0.8,
+                taille,
+                gras: false,
+                texte: tronquer(titre, taille, largeur - 2.0 * MARGE),
+            });
+
+            let largeurs = code128::encoder(code)?;
+            let modules = largeurs.iter().sum::<u32>() + 2 * code128::ZONE_CALME;
+            let module = (largeur - 2.0 * MARGE) / modules as f32;
+            let (haut, bas) = (y + MARGE + ligne * 1.2, y + hauteur - MARGE - ligne * 1.2);
+            let mut position = x + MARGE + code128::ZONE_CALME as f32 * module;
+            for (i, &n) in largeurs.iter().enumerate() {
+                let epaisseur = n as f32 * module;
+                if i % 2 == 0 {
+                    traces.push(Trace::Barre {
+                        x: position,
+                        y: haut,
+                        largeur: epaisseur,
+                        hauteur: bas - haut,
+                    });
+                }
+                position += epaisseur;
+            }
+
+            traces.push(Trace::Texte {
+                x: centre,
+                y: y + hauteur - MARGE,
+                taille,
+                gras: false,
+                texte: code.clone(),
+            });
+        }
+    }
+    Ok(traces)
+}
+
+// Planches à imprimer; `depart` (à partir de 1) saute les étiquettes déjà utilisées de la première
+fn planches(contenus: &[Contenu], papier: &Papier, depart: u32) -> Result<Vec<Vec<Trace>>, ErreurEtiquette> {
+    let par_planche = papier.par_planche() as usize;
+    let decalage = depart.saturating_sub(1) as usize % par_planche;
+    let mut planches: Vec<Vec<Trace>> = Vec::new();
+    for (i, contenu) in contenus.iter().enumerate() {
+        let index = decalage + i;
+        if index / par_planche == planches.len() {
+            planches.push(Vec::new());
+        }
+        let coin = papier.coin((index % par_planche) as u32);
+        planches.last_mut().unwrap().extend(dessiner(contenu, coin, papier.etiquette)?);
+    }
+    Ok(planches)
+}
+
+// Génère les planches et les écrit dans `dossier`: `dos-2024-03-01.pdf`, ou en SVG
+// `dos-2024-03-01-1.svg`, `dos-2024-03-01-2.svg`... une planche par fichier
+pub fn ecrire(
+    type_etiquette: TypeEtiquette,
+    format: FormatEtiquette,
+    papier: &Papier,
+    depart: u32,
+    biblio: &Bibliotheque,
+    aujourdhui: NaiveDate,
+    dossier: &Path,
+) -> Result<Impression, ErreurEtiquette> {
+    let (contenus, sans_cote) = contenus(type_etiquette, biblio);
+    if contenus.is_empty() {
+        return Err(ErreurEtiquette::Aucune);
+    }
+    let planches = planches(&contenus, papier, depart)?;
+
+    let base = format!("{}-{}", type_etiquette.nom(), aujourdhui.format("%Y-%m-%d"));
+    let fichiers: Vec<(PathBuf, Vec<u8>)> = match format {
+        FormatEtiquette::Pdf => {
+            let chemin = dossier.join(format!("{}.{}", base, format.extension()));
+            vec![(chemin, pdf::document(papier, &planches))]
+        }
+        FormatEtiquette::Svg => planches
+            .iter()
+            .enumerate()
+            .map(|(i, traces)| {
+                let chemin = dossier.join(format!("{}-{}.{}", base, i + 1, format.extension()));
+                (chemin, svg::planche(papier, traces).into_bytes())
+            })
+            .collect(),
+    };
+
+    let erreur = |chemin: &Path, e: std::io::Error| ErreurEtiquette::Fichier(format!("{}: {}", chemin.display(), e));
+    fs::create_dir_all(dossier).map_err(|e| erreur(dossier, e))?;
+    for (chemin, contenu) in &fichiers {
+        fs::write(chemin, contenu).map_err(|e| erreur(chemin, e))?;
+    }
+
+    Ok(Impression {
+        fichiers: fichiers.into_iter().map(|(chemin, _)| chemin).collect(),
+        planches: planches.len(),
+        etiquettes: contenus.len(),
+        sans_cote,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn biblio() -> Bibliotheque {
+        let mut b = Bibliotheque::new();
+        b.ajouter_livre(Livre::new(1, "Le Petit Prince".to_string(), 1, 1943)).unwrap();
+        b.ajouter_livre(Livre::new(2, "L'Étranger".to_string(), 2, 1942)).unwrap();
+        b.ajouter_livre(Livre::new(3, "Dune".to_string(), 3, 1965)).unwrap();
+        b.placer_livre(1, Some("843.9 SAI".to_string()), None).unwrap();
+        b.placer_livre(3, Some("813.54 HER".to_string()), None).unwrap();
+        b
+    }
+
+    #[test]
+    fn spine_labels_follow_call_numbers_and_skip_books_without_one() {
+        assert_eq!(abreger_titre("Le Petit Prince"), "PET");
+        assert_eq!(abreger_titre("L'Étranger"), "ÉTR");
+        assert_eq!(abreger_titre("A la recherche du temps perdu"), "REC");
+        assert_eq!(abreger_titre("Dune"), "DUN");
+
+        let (dos, sans_cote) = contenus(TypeEtiquette::Dos, &biblio());
+        assert_eq!(sans_cote, 1);
+        assert_eq!(
+            dos,
+            vec![
+                Contenu::Dos {
+                    cote: "813.54 HER".to_string(),
+                    titre: "DUN".to_string(),
+                },
+                Contenu::Dos {
+                    cote: "843.9 SAI".to_string(),
+                    titre: "PET".to_string(),
+                },
+            ]
+        );
+
+        let (codes, _) = contenus(TypeEtiquette::CodeBarres, &biblio());
+        let codes: Vec<_> = codes
+            .iter()
+            .map(|c| match c {
+                Contenu::CodeBarres { code, .. } => code.as_str(),
+                Contenu::Dos { .. } => "",
+            })
+            .collect();
+        assert_eq!(codes, vec!["00000003", "00000001", "00000002"]);
+        assert_eq!(biblio().chercher_code("00000002")[0].id, 2);
+    }
+
+    #[test]
+    fn labels_fill_the_sheet_from_the_first_free_one() {
+        let papier = &PAPIERS[1];
+        let contenus = vec![
+            Contenu::Dos {
+                cote: "843".to_string(),
+                titre: "PET".to_string(),
+            };
+            3
+        ];
+        // 21 étiquettes par planche, on commence à la dernière: deux planches
+        let planches = planches(&contenus, papier, 21).unwrap();
+        assert_eq!(planches.len(), 2);
+        let premier_texte = |traces: &[Trace]| match &traces[0] {
+            Trace::Texte { x, y, .. } => (*x, *y),
+            Trace::Barre { .. } => panic!("texte attendu"),
+        };
+        let (x, y) = premier_texte(&planches[0]);
+        assert!((x - (7.2 + 2.0 * 66.0 + 63.5 / 2.0)).abs() < 0.01);
+        assert!(y > 15.15 + 6.0 * 38.1);
+        let (x, y) = premier_texte(&planches[1]);
+        assert!((x - (7.2 + 63.5 / 2.0)).abs() < 0.01);
+        assert!(y < 15.15 + 38.1);
+    }
+
+    #[test]
+    fn barcodes_stay_inside_their_label() {
+        let papier = &PAPIERS[0];
+        let contenu = Contenu::CodeBarres {
+            code: code_exemplaire(42),
+            titre: "Un titre bien trop long pour une si petite étiquette".to_string(),
+        };
+        let traces = dessiner(&contenu, (10.0, 20.0), papier.etiquette).unwrap();
+        let mut barres = 0;
+        for trace in &traces {
+            match trace {
+                Trace::Barre { x, largeur, .. } => {
+                    barres += 1;
+                    assert!(*x >= 10.0 + MARGE && x + largeur <= 10.0 + papier.etiquette.0 - MARGE);
+                }
+                Trace::Texte { texte, taille, .. } => {
+                    assert!(largeur_texte(texte, *taille, false) <= papier.etiquette.0 - 2.0 * MARGE);
+                }
+            }
+        }
+        // Départ, 4 paires de chiffres, contrôle: 3 barres chacun; l'arrêt en a 4
+        assert_eq!(barres, 6 * 3 + 4);
+        assert!(matches!(&traces[0], Trace::Texte { texte, .. } if texte.ends_with('…')));
+    }
+
+    #[test]
+    fn both_formats_are_written() {
+        let dossier = std::env::temp_dir().join(format!("etiquettes-{}", std::process::id()));
+        let aujourdhui = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let b = biblio();
+
+        let svg = ecrire(TypeEtiquette::CodeBarres, FormatEtiquette::Svg, &PAPIERS[0], 64, &b, aujourdhui, &dossier).unwrap();
+        assert_eq!((svg.planches, svg.etiquettes), (2, 3));
+        assert_eq!(
+            svg.fichiers,
+            vec![dossier.join("codes-barres-2024-03-01-1.svg"), dossier.join("codes-barres-2024-03-01-2.svg")]
+        );
+
+        let pdf = ecrire(TypeEtiquette::Dos, FormatEtiquette::Pdf, &PAPIERS[0], 1, &b, aujourdhui, &dossier).unwrap();
+        assert_eq!((pdf.etiquettes, pdf.sans_cote), (2, 1));
+        assert!(fs::read(&pdf.fichiers[0]).unwrap().starts_with(b"%PDF-"));
+
+        let vide = Bibliotheque::new();
+        assert_eq!(
+            ecrire(TypeEtiquette::Dos, FormatEtiquette::Pdf, &PAPIERS[0], 1, &vide, aujourdhui, &dossier),
+            Err(ErreurEtiquette::Aucune)
+        );
+        let _ = fs::remove_dir_all(&dossier);
+    }
+}
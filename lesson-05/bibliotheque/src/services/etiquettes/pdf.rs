@@ -0,0 +1,188 @@
+use super::{MM_PAR_POINT, Papier, Trace, largeur_texte};
+use std::fmt::Write;
+
+// PDF minimal écrit à la main: une page par planche, barres en rectangles pleins,
+// textes en Helvetica (police standard, rien à embarquer)
+
+// Objets fixes: 1 catalogue, 2 arbre des pages, 3 et 4 les polices; puis page et contenu
+const PREMIERE_PAGE: usize = 5;
+
+fn points(mm: f32) -> f32 {
+    mm / MM_PAR_POINT
+}
+
+// Caractère en WinAnsiEncoding (Windows-1252), '?' s'il n'y figure pas
+fn winansi(c: char) -> u8 {
+    match c {
+        ' '..='~' => c as u8,
+        '\u{a0}'..='\u{ff}' => c as u32 as u8,
+        '€' => 0x80,
+        '‚' => 0x82,
+        '„' => 0x84,
+        '…' => 0x85,
+        '‹' => 0x8b,
+        'Œ' => 0x8c,
+        '‘' => 0x91,
+        '’' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '–' => 0x96,
+        '—' => 0x97,
+        '›' => 0x9b,
+        'œ' => 0x9c,
+        'Ÿ' => 0x9f,
+        _ => b'?',
+    }
+}
+
+// Chaîne littérale PDF: (texte) avec parenthèses et barres obliques protégées
+fn chaine(texte: &str) -> Vec<u8> {
+    let mut octets = vec![b'('];
+    for c in texte.chars() {
+        let octet = winansi(c);
+        if matches!(octet, b'(' | b')' | b'\\') {
+            octets.push(b'\\');
+        }
+        octets.push(octet);
+    }
+    octets.push(b')');
+    octets
+}
+
+// Instructions de dessin d'une planche; y part du bas de la page en PDF
+fn contenu(papier: &Papier, traces: &[Trace]) -> Vec<u8> {
+    let hauteur_page = points(papier.page.1);
+    let mut flux = Vec::new();
+    let mut barres = String::new();
+    for trace in traces {
+        if let Trace::Barre { x, y, largeur, hauteur } = trace {
+            let _ = writeln!(
+                barres,
+                "{:.3} {:.3} {:.3} {:.3} re",
+                points(*x),
+                hauteur_page - points(y + hauteur),
+                points(*largeur),
+                points(*hauteur)
+            );
+        }
+    }
+    if !barres.is_empty() {
+        flux.extend_from_slice(barres.as_bytes());
+        flux.extend_from_slice(b"f\n");
+    }
+
+    for trace in traces {
+        if let Trace::Texte { x, y, taille, gras, texte } = trace {
+            let police = if *gras { "F2" } else { "F1" };
+            let debut = points(x - largeur_texte(texte, *taille, *gras) / 2.0);
+            flux.extend_from_slice(
+                format!("BT /{} {:.2} Tf {:.3} {:.3} Td ", police, taille, debut, hauteur_page - points(*y)).as_bytes(),
+            );
+            flux.extend_from_slice(&chaine(texte));
+            flux.extend_from_slice(b" Tj ET\n");
+        }
+    }
+    flux
+}
+
+pub fn document(papier: &Papier, planches: &[Vec<Trace>]) -> Vec<u8> {
+    let (largeur, hauteur) = (points(papier.page.0), points(papier.page.1));
+    let pages: Vec<String> = (0..planches.len())
+        .map(|i| format!("{} 0 R", PREMIERE_PAGE + 2 * i))
+        .collect();
+
+    let mut objets: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        format!("<< /Type /Pages /Kids [{}] /Count {} >>", pages.join(" "), planches.len()).into_bytes(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_vec(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>".to_vec(),
+    ];
+    for (i, traces) in planches.iter().enumerate() {
+        objets.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {:.2} {:.2}] \
+                 /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                largeur,
+                hauteur,
+                PREMIERE_PAGE + 2 * i + 1
+            )
+            .into_bytes(),
+        );
+        let flux = contenu(papier, traces);
+        let mut objet = format!("<< /Length {} >>\nstream\n", flux.len()).into_bytes();
+        objet.extend_from_slice(&flux);
+        objet.extend_from_slice(b"\nendstream");
+        objets.push(objet);
+    }
+
+    // Table des positions de chaque objet, exigée par les lecteurs
+    let mut pdf = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut positions = Vec::new();
+    for (i, objet) in objets.iter().enumerate() {
+        positions.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+        pdf.extend_from_slice(objet);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+    let table = pdf.len();
+    let mut fin = format!("xref\n0 {}\n0000000000 65535 f \n", objets.len() + 1);
+    for position in positions {
+        let _ = writeln!(fin, "{:010} 00000 n ", position);
+    }
+    let _ = writeln!(
+        fin,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF",
+        objets.len() + 1,
+        table
+    );
+    pdf.extend_from_slice(fin.as_bytes());
+    pdf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::etiquettes::PAPIERS;
+
+    fn texte(texte: &str) -> Trace {
+        Trace::Texte {
+            x: 105.0,
+            y: 10.0,
+            taille: 10.0,
+            gras: false,
+            texte: texte.to_string(),
+        }
+    }
+
+    #[test]
+    fn text_is_encoded_for_helvetica() {
+        assert_eq!(chaine("Été (1) \\ œuvre…"), b"(\xc9t\xe9 \\(1\\) \\\\ \x9cuvre\x85)".to_vec());
+        assert_eq!(chaine("日本"), b"(??)".to_vec());
+    }
+
+    #[test]
+    fn cross_reference_table_points_at_every_object() {
+        let barre = Trace::Barre {
+            x: 10.0,
+            y: 20.0,
+            largeur: 1.0,
+            hauteur: 5.0,
+        };
+        let pdf = document(&PAPIERS[0], &[vec![barre, texte("A")], vec![texte("B")]]);
+        let trouver = |motif: &str| pdf.windows(motif.len()).rposition(|w| w == motif.as_bytes());
+        assert!(trouver("/Count 2").is_some());
+        assert!(trouver("/Kids [5 0 R 7 0 R]").is_some());
+        // 297 mm de haut = 841.89 pt; la barre finit à 25 mm du haut
+        assert!(trouver("28.346 771.024 2.835 14.173 re\nf\n").is_some());
+
+        let debut_table = trouver("startxref\n").unwrap() + "startxref\n".len();
+        let fin = String::from_utf8(pdf[debut_table..].to_vec()).unwrap();
+        let table: usize = fin.lines().next().unwrap().parse().unwrap();
+        let table = String::from_utf8(pdf[table..].to_vec()).unwrap();
+        assert!(table.starts_with("xref\n0 9\n"));
+        for (numero, ligne) in table.lines().skip(3).take(8).enumerate() {
+            let position: usize = ligne[..10].parse().unwrap();
+            assert!(pdf[position..].starts_with(format!("{} 0 obj", numero + 1).as_bytes()));
+        }
+    }
+}
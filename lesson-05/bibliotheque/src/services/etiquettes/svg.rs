@@ -0,0 +1,62 @@
+use super::{MM_PAR_POINT, Papier, Trace};
+use crate::services::rapports::echapper_html;
+use std::fmt::Write;
+
+// Une planche en SVG, à la taille réelle de la feuille: les unités du dessin sont des millimètres
+pub fn planche(papier: &Papier, traces: &[Trace]) -> String {
+    let (largeur, hauteur) = papier.page;
+    let mut svg = String::new();
+    let _ = writeln!(svg, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{largeur}mm" height="{hauteur}mm" viewBox="0 0 {largeur} {hauteur}">"#
+    );
+    let _ = writeln!(svg, r#"<g font-family="Helvetica, Arial, sans-serif" text-anchor="middle" fill="black">"#);
+    for trace in traces {
+        match trace {
+            Trace::Barre { x, y, largeur, hauteur } => {
+                let _ = writeln!(svg, r#"<rect x="{x:.3}" y="{y:.3}" width="{largeur:.3}" height="{hauteur:.3}"/>"#);
+            }
+            Trace::Texte { x, y, taille, gras, texte } => {
+                let graisse = if *gras { r#" font-weight="bold""# } else { "" };
+                let _ = writeln!(
+                    svg,
+                    r#"<text x="{x:.3}" y="{y:.3}" font-size="{:.3}"{graisse}>{}</text>"#,
+                    taille * MM_PAR_POINT,
+                    echapper_html(texte)
+                );
+            }
+        }
+    }
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::etiquettes::PAPIERS;
+
+    #[test]
+    fn sheet_is_drawn_in_millimetres_with_escaped_text() {
+        let traces = vec![
+            Trace::Barre {
+                x: 10.0,
+                y: 20.0,
+                largeur: 0.5,
+                hauteur: 8.0,
+            },
+            Trace::Texte {
+                x: 30.0,
+                y: 40.0,
+                taille: 72.0,
+                gras: true,
+                texte: "Tom & <Jerry>".to_string(),
+            },
+        ];
+        let svg = planche(&PAPIERS[0], &traces);
+        assert!(svg.contains(r#"width="210mm" height="297mm" viewBox="0 0 210 297""#));
+        assert!(svg.contains(r#"<rect x="10.000" y="20.000" width="0.500" height="8.000"/>"#));
+        assert!(svg.contains(r#"font-size="25.400" font-weight="bold">Tom &amp; &lt;Jerry&gt;</text>"#));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::models::{Auteur, Livre, Rayon, Serie};
+use std::collections::HashMap;
+
+// Fusion à trois versions d'une collection identifiée par ID:
+//   base      le fichier tel qu'il était au dernier chargement ou à la dernière sauvegarde
+//   locale    la bibliothèque en mémoire, avec nos modifications
+//   distante  le fichier réécrit entre-temps par un autre processus
+// Une modification d'un seul côté est reprise; si les deux côtés ont changé
+// différemment le même élément, c'est un conflit et la version locale l'emporte
+
+pub trait Element: Clone {
+    fn id(&self) -> u32;
+
+    // Égalité utilisée pour détecter les modifications
+    fn meme_contenu(&self, autre: &Self) -> bool;
+}
+
+impl Element for Livre {
+    fn id(&self) -> u32 {
+        self.id
+    }
+
+    fn meme_contenu(&self, autre: &Self) -> bool {
+        self == autre
+    }
+}
+
+impl Element for Auteur {
+    fn id(&self) -> u32 {
+        self.id
+    }
+
+    // La liste des livres se déduit des livres fusionnés, elle est reconstruite ensuite
+    fn meme_contenu(&self, autre: &Self) -> bool {
+        (self.id, &self.prenom, &self.nom) == (autre.id, &autre.prenom, &autre.nom)
+    }
+}
+
+impl Element for Serie {
+    fn id(&self) -> u32 {
+        self.id
+    }
+
+    fn meme_contenu(&self, autre: &Self) -> bool {
+        (self.id, &self.nom) == (autre.id, &autre.nom)
+    }
+}
+
+impl Element for Rayon {
+    fn id(&self) -> u32 {
+        self.id
+    }
+
+    fn meme_contenu(&self, autre: &Self) -> bool {
+        (self.id, &self.salle, &self.nom) == (autre.id, &autre.salle, &autre.nom)
+    }
+}
+
+fn par_id<T: Element>(elements: &[T]) -> HashMap<u32, &T> {
+    elements.iter().map(|e| (e.id(), e)).collect()
+}
+
+// IDs ajoutés des deux côtés à des éléments différents (chacun a pris le prochain ID libre):
+// les ajouts locaux devront changer d'ID pour ne pas écraser ceux de l'autre processus
+pub fn ids_en_collision<T: Element>(base: &[T], locale: &[T], distante: &[T]) -> Vec<u32> {
+    let (base, distante) = (par_id(base), par_id(distante));
+    locale
+        .iter()
+        .filter(|l| !base.contains_key(&l.id()))
+        .filter(|l| distante.get(&l.id()).is_some_and(|d| !d.meme_contenu(l)))
+        .map(|l| l.id())
+        .collect()
+}
+
+// Éléments fusionnés dans l'ordre de la version distante, suivis des ajouts locaux,
+// et nombre de conflits
+pub fn fusionner<T: Element>(base: &[T], locale: &[T], distante: &[T]) -> (Vec<T>, usize) {
+    let (index_base, index_locale, index_distante) = (par_id(base), par_id(locale), par_id(distante));
+    let mut resultat = Vec::new();
+    let mut conflits = 0;
+
+    for d in distante {
+        match (index_base.get(&d.id()), index_locale.get(&d.id())) {
+            // Ajouté par l'autre processus (ou à l'identique des deux côtés)
+            (None, _) => resultat.push(d.clone()),
+            // Supprimé chez nous: la suppression tient si l'autre n'y a pas touché
+            (Some(b), None) => {
+                if !d.meme_contenu(b) {
+                    conflits += 1;
+                    resultat.push(d.clone());
+                }
+            }
+            (Some(b), Some(l)) => {
+                if l.meme_contenu(b) || l.meme_contenu(d) {
+                    resultat.push(d.clone());
+                } else {
+                    if !d.meme_contenu(b) {
+                        conflits += 1;
+                    }
+                    resultat.push((*l).clone());
+                }
+            }
+        }
+    }
+
+    for l in locale.iter().filter(|l| !index_distante.contains_key(&l.id())) {
+        match index_base.get(&l.id()) {
+            // Ajouté chez nous
+            None => resultat.push(l.clone()),
+            // Supprimé par l'autre processus: gardé seulement si on l'a modifié
+            Some(b) => {
+                if !l.meme_contenu(b) {
+                    conflits += 1;
+                    resultat.push(l.clone());
+                }
+            }
+        }
+    }
+
+    (resultat, conflits)
+}
+
+// Même règle pour une valeur unique (ex: la session d'inventaire)
+pub fn fusionner_valeur<T: Clone + PartialEq>(base: &T, locale: &T, distante: &T) -> (T, usize) {
+    if locale == base || locale == distante {
+        (distante.clone(), 0)
+    } else if distante == base {
+        (locale.clone(), 0)
+    } else {
+        (locale.clone(), 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn serie(id: u32, nom: &str) -> Serie {
+        Serie::new(id, nom.to_string())
+    }
+
+    fn noms(series: &[Serie]) -> Vec<(u32, &str)> {
+        series.iter().map(|s| (s.id, s.nom.as_str())).collect()
+    }
+
+    #[test]
+    fn changes_from_either_side_are_kept() {
+        let base = vec![serie(1, "Dune"), serie(2, "Fondation"), serie(3, "Tintin")];
+        // Chez nous: Dune renommée, Tintin supprimée, Astérix ajoutée
+        let locale = vec![serie(1, "Dune (cycle)"), serie(2, "Fondation"), serie(4, "Astérix")];
+        // Ailleurs: Fondation renommée
+        let distante = vec![serie(1, "Dune"), serie(2, "Le cycle de Fondation"), serie(3, "Tintin")];
+
+        let (fusion, conflits) = fusionner(&base, &locale, &distante);
+        assert_eq!(conflits, 0);
+        assert_eq!(noms(&fusion), vec![(1, "Dune (cycle)"), (2, "Le cycle de Fondation"), (4, "Astérix")]);
+    }
+
+    #[test]
+    fn conflicting_edits_keep_the_local_version() {
+        let base = vec![serie(1, "Dune"), serie(2, "Fondation")];
+        let locale = vec![serie(1, "Dune (Herbert)")];
+        // Ailleurs: Dune renommée autrement, Fondation modifiée alors qu'on l'a supprimée
+        let distante = vec![serie(1, "Dune, le cycle"), serie(2, "Fondation (Asimov)")];
+
+        let (fusion, conflits) = fusionner(&base, &locale, &distante);
+        assert_eq!(conflits, 2);
+        assert_eq!(noms(&fusion), vec![(1, "Dune (Herbert)"), (2, "Fondation (Asimov)")]);
+    }
+
+    #[test]
+    fn same_new_id_on_both_sides_is_a_collision() {
+        let base = vec![serie(1, "Dune")];
+        let locale = vec![serie(1, "Dune"), serie(2, "Astérix"), serie(3, "Spirou")];
+        let distante = vec![serie(1, "Dune"), serie(2, "Tintin"), serie(3, "Spirou")];
+        // Spirou ajoutée à l'identique des deux côtés: pas de collision
+        assert_eq!(ids_en_collision(&base, &locale, &distante), vec![2]);
+        assert_eq!(fusionner_valeur(&1, &2, &3), (2, 1));
+        assert_eq!(fusionner_valeur(&1, &1, &3), (3, 0));
+    }
+}
@@ -0,0 +1,233 @@
+use crate::i18n::tf;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+pub const OPEN_LIBRARY_URL: &str = "https://openlibrary.org";
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+// Ce qu'une notice bibliographique permet de préremplir
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Metadonnees {
+    pub titre: String,
+    // "Prénom Nom", dans l'ordre de la notice
+    pub auteurs: Vec<String>,
+    pub annee: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErreurMetadonnees {
+    // Serveur injoignable, délai dépassé, fichier illisible...
+    Acces(String),
+    // Réponse reçue mais pas au format attendu
+    Format(String),
+}
+
+impl fmt::Display for ErreurMetadonnees {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let texte = match self {
+            ErreurMetadonnees::Acces(detail) => tf("error.metadata_access", &[("detail", detail)]),
+            ErreurMetadonnees::Format(detail) => tf("error.metadata_format", &[("detail", detail)]),
+        };
+        f.write_str(&texte)
+    }
+}
+
+impl std::error::Error for ErreurMetadonnees {}
+
+// Source de notices, interrogée par ISBN (sans séparateurs)
+// Ok(None): la source répond mais ne connaît pas cet ISBN
+// CONCURRENCE: Send + Sync car la recherche tourne dans un thread de fond
+pub trait FournisseurMetadonnees: Send + Sync {
+    fn chercher(&self, isbn: &str) -> Result<Option<Metadonnees>, ErreurMetadonnees>;
+}
+
+// API "books" d'Open Library, ou d'un serveur qui expose la même interface
+pub struct OpenLibrary {
+    base_url: String,
+    agent: ureq::Agent,
+}
+
+impl OpenLibrary {
+    pub fn new(base_url: &str) -> Self {
+        OpenLibrary {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            agent: ureq::AgentBuilder::new().timeout(TIMEOUT).build(),
+        }
+    }
+}
+
+impl FournisseurMetadonnees for OpenLibrary {
+    fn chercher(&self, isbn: &str) -> Result<Option<Metadonnees>, ErreurMetadonnees> {
+        let corps = self
+            .agent
+            .get(&format!("{}/api/books", self.base_url))
+            .query("bibkeys", &format!("ISBN:{}", isbn))
+            .query("format", "json")
+            .query("jscmd", "data")
+            .call()
+            .map_err(|e| ErreurMetadonnees::Acces(e.to_string()))?
+            .into_string()
+            .map_err(|e| ErreurMetadonnees::Acces(e.to_string()))?;
+        analyser_reponse(&corps, isbn)
+    }
+}
+
+// Réponses enregistrées au format d'Open Library, pour les tests et le travail hors ligne
+pub struct FichierNotices {
+    chemin: PathBuf,
+}
+
+impl FichierNotices {
+    pub fn new(chemin: impl Into<PathBuf>) -> Self {
+        FichierNotices { chemin: chemin.into() }
+    }
+}
+
+impl FournisseurMetadonnees for FichierNotices {
+    fn chercher(&self, isbn: &str) -> Result<Option<Metadonnees>, ErreurMetadonnees> {
+        let corps = fs::read_to_string(&self.chemin).map_err(|e| ErreurMetadonnees::Acces(e.to_string()))?;
+        analyser_reponse(&corps, isbn)
+    }
+}
+
+// Garde sur disque les réponses d'un autre fournisseur, y compris "ISBN inconnu";
+// les erreurs ne sont pas gardées pour que la recherche soit retentée
+pub struct CacheMetadonnees<F> {
+    fournisseur: F,
+    fichier: PathBuf,
+    entrees: Mutex<HashMap<String, Option<Metadonnees>>>,
+}
+
+impl<F: FournisseurMetadonnees> CacheMetadonnees<F> {
+    // Un cache absent ou illisible repart de zéro
+    pub fn new(fournisseur: F, fichier: impl Into<PathBuf>) -> Self {
+        let fichier = fichier.into();
+        let entrees = fs::read_to_string(&fichier)
+            .ok()
+            .and_then(|contenu| serde_json::from_str(&contenu).ok())
+            .unwrap_or_default();
+        CacheMetadonnees {
+            fournisseur,
+            fichier,
+            entrees: Mutex::new(entrees),
+        }
+    }
+}
+
+impl<F: FournisseurMetadonnees> FournisseurMetadonnees for CacheMetadonnees<F> {
+    fn chercher(&self, isbn: &str) -> Result<Option<Metadonnees>, ErreurMetadonnees> {
+        if let Some(notice) = self.entrees.lock().unwrap().get(isbn) {
+            return Ok(notice.clone());
+        }
+
+        // Le verrou n'est pas gardé pendant l'appel réseau
+        let notice = self.fournisseur.chercher(isbn)?;
+        let mut entrees = self.entrees.lock().unwrap();
+        entrees.insert(isbn.to_string(), notice.clone());
+        // Un cache qui ne s'écrit pas ne fait pas échouer la recherche
+        if let Ok(json) = serde_json::to_string_pretty(&*entrees) {
+            let _ = fs::write(&self.fichier, json);
+        }
+        Ok(notice)
+    }
+}
+
+// Format de réponse d'Open Library: {"ISBN:978...": {"title": ..., "authors": [{"name": ...}], ...}}
+#[derive(Deserialize)]
+struct NoticeOpenLibrary {
+    title: String,
+    #[serde(default)]
+    authors: Vec<AuteurOpenLibrary>,
+    publish_date: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AuteurOpenLibrary {
+    name: String,
+}
+
+fn analyser_reponse(corps: &str, isbn: &str) -> Result<Option<Metadonnees>, ErreurMetadonnees> {
+    let mut notices: HashMap<String, NoticeOpenLibrary> =
+        serde_json::from_str(corps).map_err(|e| ErreurMetadonnees::Format(e.to_string()))?;
+    Ok(notices.remove(&format!("ISBN:{}", isbn)).map(|notice| Metadonnees {
+        titre: notice.title,
+        auteurs: notice.authors.into_iter().map(|a| a.name).collect(),
+        annee: notice.publish_date.as_deref().and_then(annee_publication),
+    }))
+}
+
+// Les dates sont libres: "1943", "April 1943", "6 avril 1943"...
+// On garde la première suite de quatre chiffres
+fn annee_publication(date: &str) -> Option<u32> {
+    date.split(|c: char| !c.is_ascii_digit())
+        .find(|nombre| nombre.len() == 4)
+        .and_then(|nombre| nombre.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/openlibrary.json");
+
+    // Compte les appels pour vérifier que le cache évite de redemander
+    struct Compteur<'a>(&'a AtomicUsize);
+
+    impl FournisseurMetadonnees for Compteur<'_> {
+        fn chercher(&self, isbn: &str) -> Result<Option<Metadonnees>, ErreurMetadonnees> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            FichierNotices::new(FIXTURES).chercher(isbn)
+        }
+    }
+
+    #[test]
+    fn fixture_file_answers_like_open_library() {
+        let fichiers = FichierNotices::new(FIXTURES);
+        let notice = fichiers.chercher("9782070612758").unwrap().unwrap();
+        assert_eq!(notice.titre, "Le Petit Prince");
+        assert_eq!(notice.auteurs, vec!["Antoine de Saint-Exupery".to_string()]);
+        assert_eq!(notice.annee, Some(1943));
+        assert_eq!(fichiers.chercher("9780000000002").unwrap(), None);
+
+        assert!(matches!(
+            FichierNotices::new("absent.json").chercher("9782070612758"),
+            Err(ErreurMetadonnees::Acces(_))
+        ));
+        assert!(matches!(analyser_reponse("<html>", "1"), Err(ErreurMetadonnees::Format(_))));
+    }
+
+    #[test]
+    fn year_is_read_from_free_form_dates() {
+        assert_eq!(annee_publication("1943"), Some(1943));
+        assert_eq!(annee_publication("April 6, 1943"), Some(1943));
+        assert_eq!(annee_publication("vers 1930-1931"), Some(1930));
+        assert_eq!(annee_publication("n.d."), None);
+    }
+
+    #[test]
+    fn cache_keeps_answers_on_disk() {
+        let fichier = std::env::temp_dir().join(format!("isbn-cache-{}.json", std::process::id()));
+        let appels = AtomicUsize::new(0);
+
+        let cache = CacheMetadonnees::new(Compteur(&appels), &fichier);
+        let notice = cache.chercher("9782070612758").unwrap();
+        assert!(notice.is_some());
+        assert_eq!(cache.chercher("9782070612758").unwrap(), notice);
+        assert_eq!(cache.chercher("9780000000002").unwrap(), None);
+        assert_eq!(cache.chercher("9780000000002").unwrap(), None);
+        assert_eq!(appels.load(Ordering::SeqCst), 2);
+
+        // Un nouveau cache relit le fichier
+        let cache = CacheMetadonnees::new(Compteur(&appels), &fichier);
+        assert_eq!(cache.chercher("9782070612758").unwrap(), notice);
+        assert_eq!(appels.load(Ordering::SeqCst), 2);
+
+        let _ = fs::remove_file(&fichier);
+    }
+}
@@ -1,2 +1,11 @@
 pub mod bibliotheque;
-pub use bibliotheque::Bibliotheque;
\ No newline at end of file
+pub mod chiffrement;
+pub mod erreur;
+pub mod etiquettes;
+pub mod fusion;
+pub mod metadonnees;
+pub mod operateurs;
+pub mod rapports;
+pub mod stockage;
+pub use bibliotheque::Bibliotheque;
+pub use erreur::{ErreurBibliotheque, ErreurChargement, ErreurOperateur, ErreurSauvegarde};
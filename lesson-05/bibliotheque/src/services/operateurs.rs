@@ -0,0 +1,144 @@
+use super::ErreurOperateur;
+use super::stockage;
+use crate::models::{Operateur, Permission, Role, Session};
+use argon2::Argon2;
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+// Comptes des opérateurs, dans un fichier à côté de celui de la bibliothèque
+// Aucun compte: l'interface s'ouvre sans connexion et tout est permis
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Operateurs {
+    operateurs: Vec<Operateur>,
+}
+
+impl Operateurs {
+    // Fichier absent: aucun compte
+    pub fn charger(fichier: &str) -> Result<Self, ErreurOperateur> {
+        match stockage::lire_si_existe(Path::new(fichier))? {
+            Some(contenu) => Ok(serde_json::from_str(&contenu)?),
+            None => Ok(Operateurs::default()),
+        }
+    }
+
+    pub fn sauvegarder(&self, fichier: &str) -> Result<(), ErreurOperateur> {
+        let json = serde_json::to_string_pretty(self)?;
+        stockage::ecrire_atomique(Path::new(fichier), &json)?;
+        Ok(())
+    }
+
+    pub fn est_vide(&self) -> bool {
+        self.operateurs.is_empty()
+    }
+
+    // Le premier compte est forcément un administrateur, les suivants sont créés par un administrateur
+    pub fn ajouter(
+        &mut self,
+        session: Option<&Session>,
+        nom: &str,
+        role: Role,
+        mot_de_passe: &str,
+    ) -> Result<(), ErreurOperateur> {
+        if self.operateurs.is_empty() {
+            if role != Role::Admin {
+                return Err(ErreurOperateur::PremierCompteAdmin);
+            }
+        } else if !session.is_some_and(|s| s.role.peut(Permission::Administrer)) {
+            return Err(ErreurOperateur::NonAutorise);
+        }
+        if self.trouver(nom).is_some() {
+            return Err(ErreurOperateur::NomPris(nom.to_string()));
+        }
+
+        // Sel aléatoire propre à chaque compte, stocké dans le hash
+        let sel = SaltString::generate(&mut OsRng);
+        let hash = Argon2::default()
+            .hash_password(mot_de_passe.as_bytes(), &sel)
+            .map_err(|e| ErreurOperateur::Hachage(e.to_string()))?;
+        self.operateurs.push(Operateur {
+            nom: nom.to_string(),
+            role,
+            mot_de_passe: hash.to_string(),
+        });
+        Ok(())
+    }
+
+    // Session ouverte si le nom (sans tenir compte de la casse) et le mot de passe correspondent
+    pub fn authentifier(&self, nom: &str, mot_de_passe: &str) -> Option<Session> {
+        let operateur = self.trouver(nom)?;
+        let hash = PasswordHash::new(&operateur.mot_de_passe).ok()?;
+        Argon2::default()
+            .verify_password(mot_de_passe.as_bytes(), &hash)
+            .ok()?;
+        Some(Session {
+            nom: operateur.nom.clone(),
+            role: operateur.role,
+        })
+    }
+
+    fn trouver(&self, nom: &str) -> Option<&Operateur> {
+        self.operateurs.iter().find(|o| o.nom.to_lowercase() == nom.trim().to_lowercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn admin() -> Session {
+        Session {
+            nom: "Claire".to_string(),
+            role: Role::Admin,
+        }
+    }
+
+    #[test]
+    fn first_account_is_an_admin_created_without_session() {
+        let mut operateurs = Operateurs::default();
+        assert_eq!(
+            operateurs.ajouter(None, "Paul", Role::Benevole, "secret"),
+            Err(ErreurOperateur::PremierCompteAdmin)
+        );
+        operateurs.ajouter(None, "Claire", Role::Admin, "secret").unwrap();
+
+        assert_eq!(
+            operateurs.ajouter(None, "Paul", Role::Benevole, "secret"),
+            Err(ErreurOperateur::NonAutorise)
+        );
+        let benevole = Session {
+            nom: "Paul".to_string(),
+            role: Role::Benevole,
+        };
+        assert_eq!(
+            operateurs.ajouter(Some(&benevole), "Zoé", Role::Admin, "secret"),
+            Err(ErreurOperateur::NonAutorise)
+        );
+        assert_eq!(
+            operateurs.ajouter(Some(&admin()), "claire", Role::Benevole, "autre"),
+            Err(ErreurOperateur::NomPris("claire".to_string()))
+        );
+    }
+
+    #[test]
+    fn passwords_are_hashed_and_checked() {
+        let mut operateurs = Operateurs::default();
+        operateurs.ajouter(None, "Claire", Role::Admin, "secret").unwrap();
+        operateurs.ajouter(Some(&admin()), "Paul", Role::Benevole, "bénévole").unwrap();
+
+        let fichier = std::env::temp_dir().join(format!("operateurs-{}.json", std::process::id()));
+        let fichier = fichier.to_str().unwrap();
+        operateurs.sauvegarder(fichier).unwrap();
+        let contenu = std::fs::read_to_string(fichier).unwrap();
+        assert!(!contenu.contains("bénévole\""));
+        assert!(contenu.contains("$argon2id$"));
+
+        let relus = Operateurs::charger(fichier).unwrap();
+        assert_eq!(relus.authentifier("paul", "bénévole").map(|s| s.role), Some(Role::Benevole));
+        assert_eq!(relus.authentifier("Paul", "secret"), None);
+        assert_eq!(relus.authentifier("Inconnu", "secret"), None);
+        let _ = std::fs::remove_file(fichier);
+        assert!(Operateurs::charger(fichier).unwrap().est_vide());
+    }
+}
@@ -0,0 +1,604 @@
+pub mod modele;
+
+use crate::i18n::{self, Locale, t, tf, tn};
+use crate::models::Livre;
+use crate::services::Bibliotheque;
+use chrono::{Datelike, NaiveDate};
+use modele::{Contexte, Modele, Valeur};
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+// Dossier où les rapports sont écrits par défaut
+pub const DOSSIER_RAPPORTS: &str = "rapports";
+// Modèles personnalisés: `modeles/rapport.html` et `modeles/rapport.md` remplacent
+// ceux livrés avec l'application
+pub const DOSSIER_MODELES: &str = "modeles";
+
+const MODELE_HTML: &str = include_str!("../../../templates/rapport.html");
+const MODELE_MARKDOWN: &str = include_str!("../../../templates/rapport.md");
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErreurRapport {
+    // Modèle mal formé (section non fermée...)
+    Modele(String),
+    // Modèle illisible, dossier ou fichier impossible à écrire
+    Fichier(String),
+}
+
+impl fmt::Display for ErreurRapport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let texte = match self {
+            ErreurRapport::Modele(detail) => tf("error.report_template", &[("detail", detail)]),
+            ErreurRapport::Fichier(detail) => tf("error.report_file", &[("detail", detail)]),
+        };
+        f.write_str(&texte)
+    }
+}
+
+impl std::error::Error for ErreurRapport {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeRapport {
+    Catalogue,
+    EmpruntsDuMois,
+    Retards,
+    Bibliographies,
+}
+
+impl TypeRapport {
+    pub const TOUS: [TypeRapport; 4] = [
+        TypeRapport::Catalogue,
+        TypeRapport::EmpruntsDuMois,
+        TypeRapport::Retards,
+        TypeRapport::Bibliographies,
+    ];
+
+    // Nom en ligne de commande et dans le nom du fichier
+    pub fn nom(self) -> &'static str {
+        match self {
+            TypeRapport::Catalogue => "catalogue",
+            TypeRapport::EmpruntsDuMois => "emprunts",
+            TypeRapport::Retards => "retards",
+            TypeRapport::Bibliographies => "bibliographies",
+        }
+    }
+
+    pub fn depuis_nom(nom: &str) -> Option<TypeRapport> {
+        TypeRapport::TOUS.into_iter().find(|r| r.nom() == nom)
+    }
+
+    pub fn libelle(self) -> &'static str {
+        match self {
+            TypeRapport::Catalogue => t("report.kind.catalogue"),
+            TypeRapport::EmpruntsDuMois => t("report.kind.loans"),
+            TypeRapport::Retards => t("report.kind.overdue"),
+            TypeRapport::Bibliographies => t("report.kind.bibliographies"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatRapport {
+    Html,
+    Markdown,
+}
+
+impl FormatRapport {
+    pub const TOUS: [FormatRapport; 2] = [FormatRapport::Html, FormatRapport::Markdown];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            FormatRapport::Html => "html",
+            FormatRapport::Markdown => "md",
+        }
+    }
+
+    // "html", "md" ou "markdown"
+    pub fn depuis_nom(nom: &str) -> Option<FormatRapport> {
+        match nom.to_lowercase().as_str() {
+            "html" | "htm" => Some(FormatRapport::Html),
+            "md" | "markdown" => Some(FormatRapport::Markdown),
+            _ => None,
+        }
+    }
+
+    pub fn libelle(self) -> &'static str {
+        match self {
+            FormatRapport::Html => "HTML",
+            FormatRapport::Markdown => "Markdown",
+        }
+    }
+
+    fn echapper(self) -> fn(&str) -> String {
+        match self {
+            FormatRapport::Html => echapper_html,
+            FormatRapport::Markdown => echapper_markdown,
+        }
+    }
+
+    // Modèle personnalisé du dossier s'il existe, sinon celui livré avec l'application
+    pub fn modele(self, dossier: &Path) -> Result<Modele, ErreurRapport> {
+        let chemin = dossier.join(format!("rapport.{}", self.extension()));
+        match fs::read_to_string(&chemin) {
+            Ok(source) => Modele::analyser(&source),
+            Err(e) if e.kind() == ErrorKind::NotFound => Modele::analyser(match self {
+                FormatRapport::Html => MODELE_HTML,
+                FormatRapport::Markdown => MODELE_MARKDOWN,
+            }),
+            Err(e) => Err(ErreurRapport::Fichier(format!("{}: {}", chemin.display(), e))),
+        }
+    }
+}
+
+// Aussi pour le XML des planches d'étiquettes SVG
+pub fn echapper_html(texte: &str) -> String {
+    let mut sortie = String::with_capacity(texte.len());
+    for c in texte.chars() {
+        match c {
+            '&' => sortie.push_str("&amp;"),
+            '<' => sortie.push_str("&lt;"),
+            '>' => sortie.push_str("&gt;"),
+            '"' => sortie.push_str("&quot;"),
+            '\'' => sortie.push_str("&#39;"),
+            _ => sortie.push(c),
+        }
+    }
+    sortie
+}
+
+// Les valeurs finissent surtout dans des cellules de tableau: pas de retour à la ligne,
+// et les caractères de mise en forme sont neutralisés
+fn echapper_markdown(texte: &str) -> String {
+    let mut sortie = String::with_capacity(texte.len());
+    for c in texte.chars() {
+        match c {
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' => {
+                sortie.push('\\');
+                sortie.push(c);
+            }
+            '\n' | '\r' => sortie.push(' '),
+            _ => sortie.push(c),
+        }
+    }
+    sortie
+}
+
+// Mois couvert par le rapport des emprunts, saisi "2024-06" ou "06/2024"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mois {
+    pub annee: i32,
+    pub mois: u32,
+}
+
+impl Mois {
+    pub fn de(date: NaiveDate) -> Mois {
+        Mois {
+            annee: date.year(),
+            mois: date.month(),
+        }
+    }
+
+    pub fn analyser(texte: &str) -> Option<Mois> {
+        let texte = texte.trim();
+        let (annee, mois) = match (texte.split_once('-'), texte.split_once('/')) {
+            (Some((annee, mois)), _) => (annee, mois),
+            (None, Some((mois, annee))) => (annee, mois),
+            (None, None) => return None,
+        };
+        let mois = Mois {
+            annee: annee.parse().ok()?,
+            mois: mois.parse().ok()?,
+        };
+        NaiveDate::from_ymd_opt(mois.annee, mois.mois, 1).map(|_| mois)
+    }
+
+    fn contient(self, date: NaiveDate) -> bool {
+        Mois::de(date) == self
+    }
+
+    // Au format de la langue: 06/2024 en français, 2024-06 en anglais
+    fn libelle(self) -> String {
+        NaiveDate::from_ymd_opt(self.annee, self.mois, 1)
+            .map_or_else(|| self.to_string(), |date| date.format(t("report.month_format")).to_string())
+    }
+}
+
+impl fmt::Display for Mois {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{:02}", self.annee, self.mois)
+    }
+}
+
+// Contenu d'un rapport, indépendant du format de sortie
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rapport {
+    pub titre: String,
+    pub sous_titre: String,
+    // Chiffres clés, une phrase chacun
+    pub resume: Vec<String>,
+    pub sections: Vec<Section>,
+}
+
+// Un tableau titré
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    pub titre: String,
+    pub colonnes: Vec<String>,
+    pub lignes: Vec<Vec<String>>,
+}
+
+impl Section {
+    fn new(titre: String, colonnes: &[&'static str]) -> Self {
+        Section {
+            titre,
+            colonnes: colonnes.iter().map(|cle| t(cle).to_string()).collect(),
+            lignes: Vec::new(),
+        }
+    }
+}
+
+impl Rapport {
+    // `mois` ne sert qu'aux emprunts, `aujourdhui` date le rapport et les retards
+    pub fn generer(type_rapport: TypeRapport, biblio: &Bibliotheque, mois: Mois, aujourdhui: NaiveDate) -> Rapport {
+        let genere_le = tf("report.generated_on", &[("date", &i18n::date(aujourdhui))]);
+        match type_rapport {
+            TypeRapport::Catalogue => catalogue(biblio, genere_le),
+            TypeRapport::EmpruntsDuMois => emprunts_du_mois(biblio, mois, genere_le),
+            TypeRapport::Retards => retards(biblio, aujourdhui, genere_le),
+            TypeRapport::Bibliographies => bibliographies(biblio, genere_le),
+        }
+    }
+
+    pub fn rendre(&self, format: FormatRapport, modele: &Modele) -> String {
+        let texte = |valeur: &str| Valeur::Texte(valeur.to_string());
+        let liste = |cle: &'static str, valeurs: &[String]| {
+            Valeur::Liste(valeurs.iter().map(|v| Contexte::from([(cle, texte(v))])).collect())
+        };
+
+        let sections = self
+            .sections
+            .iter()
+            .map(|section| {
+                let lignes = section
+                    .lignes
+                    .iter()
+                    .map(|cellules| Contexte::from([("cellules", liste("texte", cellules))]))
+                    .collect();
+                // Liste d'un élément vide, ou vide: {{#si_lignes}} sert de condition
+                let si_lignes = if section.lignes.is_empty() { vec![] } else { vec![Contexte::new()] };
+                Contexte::from([
+                    ("titre", texte(&section.titre)),
+                    ("colonnes", liste("nom", &section.colonnes)),
+                    ("lignes", Valeur::Liste(lignes)),
+                    ("si_lignes", Valeur::Liste(si_lignes)),
+                    ("vide", texte(t("report.empty"))),
+                ])
+            })
+            .collect();
+
+        let langue = match i18n::locale() {
+            Locale::Fr => "fr",
+            Locale::En => "en",
+        };
+        let contexte = Contexte::from([
+            ("langue", texte(langue)),
+            ("titre", texte(&self.titre)),
+            ("sous_titre", texte(&self.sous_titre)),
+            ("resume", liste("texte", &self.resume)),
+            ("sections", Valeur::Liste(sections)),
+        ]);
+        modele.rendre(&contexte, format.echapper())
+    }
+}
+
+// Nom du fichier: le mois pour les emprunts, le jour pour les autres
+pub fn nom_fichier(type_rapport: TypeRapport, format: FormatRapport, mois: Mois, aujourdhui: NaiveDate) -> String {
+    let periode = match type_rapport {
+        TypeRapport::EmpruntsDuMois => mois.to_string(),
+        _ => aujourdhui.format("%Y-%m-%d").to_string(),
+    };
+    format!("{}-{}.{}", type_rapport.nom(), periode, format.extension())
+}
+
+// Génère le rapport avec le modèle du format et l'écrit dans `chemin`
+pub fn ecrire(
+    type_rapport: TypeRapport,
+    format: FormatRapport,
+    biblio: &Bibliotheque,
+    mois: Mois,
+    aujourdhui: NaiveDate,
+    chemin: &Path,
+) -> Result<PathBuf, ErreurRapport> {
+    let modele = format.modele(Path::new(DOSSIER_MODELES))?;
+    let contenu = Rapport::generer(type_rapport, biblio, mois, aujourdhui).rendre(format, &modele);
+    let erreur = |e: std::io::Error| ErreurRapport::Fichier(format!("{}: {}", chemin.display(), e));
+    if let Some(dossier) = chemin.parent().filter(|d| !d.as_os_str().is_empty()) {
+        fs::create_dir_all(dossier).map_err(erreur)?;
+    }
+    fs::write(chemin, contenu).map_err(erreur)?;
+    Ok(chemin.to_path_buf())
+}
+
+fn nom_auteur(biblio: &Bibliotheque, auteur_id: u32) -> String {
+    match biblio.get_auteurs().iter().find(|a| a.id == auteur_id) {
+        Some(a) => format!("{} {}", a.prenom, a.nom).trim().to_string(),
+        None => format!("#{}", auteur_id),
+    }
+}
+
+fn serie(biblio: &Bibliotheque, livre: &Livre) -> String {
+    livre
+        .tome
+        .and_then(|tome| {
+            let serie = biblio.trouver_serie(tome.serie_id)?;
+            Some(tf("book.details.volume", &[("serie", &serie.nom), ("numero", &tome.numero)]))
+        })
+        .unwrap_or_default()
+}
+
+fn statut(livre: &Livre) -> String {
+    let cle = if livre.emprunte { "book.status.borrowed" } else { "book.status.available" };
+    t(cle).to_string()
+}
+
+fn catalogue(biblio: &Bibliotheque, genere_le: String) -> Rapport {
+    let livres = biblio.livres_groupes();
+    let empruntes = livres.iter().filter(|l| l.emprunte).count();
+
+    let mut section = Section::new(
+        t("report.catalogue.section").to_string(),
+        &[
+            "report.column.id",
+            "report.column.title",
+            "report.column.author",
+            "report.column.year",
+            "report.column.isbn",
+            "report.column.series",
+            "report.column.call_number",
+            "report.column.status",
+        ],
+    );
+    for livre in &livres {
+        section.lignes.push(vec![
+            livre.id.to_string(),
+            livre.titre.clone(),
+            nom_auteur(biblio, livre.auteur_id),
+            livre.annee.to_string(),
+            livre.isbn.clone().unwrap_or_default(),
+            serie(biblio, livre),
+            livre.cote.clone().unwrap_or_default(),
+            statut(livre),
+        ]);
+    }
+
+    Rapport {
+        titre: t("report.catalogue.title").to_string(),
+        sous_titre: genere_le,
+        resume: vec![tn("report.catalogue.books", livres.len()), tn("report.catalogue.borrowed", empruntes)],
+        sections: vec![section],
+    }
+}
+
+fn emprunts_du_mois(biblio: &Bibliotheque, mois: Mois, genere_le: String) -> Rapport {
+    // (date, livre, emprunt) des emprunts commencés et des retours du mois
+    let mut debuts = Vec::new();
+    let mut retours = Vec::new();
+    for livre in biblio.get_livres() {
+        for emprunt in &livre.historique {
+            if mois.contient(emprunt.debut) {
+                debuts.push((emprunt.debut, livre, emprunt));
+            }
+            if let Some(retour) = emprunt.retour.filter(|r| mois.contient(*r)) {
+                retours.push((retour, livre, emprunt));
+            }
+        }
+    }
+    debuts.sort_by_key(|(date, livre, _)| (*date, livre.id));
+    retours.sort_by_key(|(date, livre, _)| (*date, livre.id));
+    let en_cours = debuts.iter().filter(|(_, _, e)| e.retour.is_none()).count();
+
+    let mut section_debuts = Section::new(
+        t("report.loans.started").to_string(),
+        &[
+            "report.column.borrowed_on",
+            "report.column.id",
+            "report.column.title",
+            "report.column.due",
+            "report.column.returned_on",
+        ],
+    );
+    for (debut, livre, emprunt) in &debuts {
+        let retour = emprunt.retour.map_or_else(|| t("report.loans.ongoing").to_string(), i18n::date);
+        section_debuts.lignes.push(vec![
+            i18n::date(*debut),
+            livre.id.to_string(),
+            livre.titre.clone(),
+            i18n::date(emprunt.echeance),
+            retour,
+        ]);
+    }
+
+    let mut section_retours = Section::new(
+        t("report.loans.returned").to_string(),
+        &["report.column.returned_on", "report.column.id", "report.column.title", "report.column.borrowed_on"],
+    );
+    for (retour, livre, emprunt) in &retours {
+        section_retours.lignes.push(vec![
+            i18n::date(*retour),
+            livre.id.to_string(),
+            livre.titre.clone(),
+            i18n::date(emprunt.debut),
+        ]);
+    }
+
+    Rapport {
+        titre: tf("report.loans.title", &[("mois", &mois.libelle())]),
+        sous_titre: genere_le,
+        resume: vec![
+            tn("report.loans.count", debuts.len()),
+            tn("report.loans.returns", retours.len()),
+            tn("report.loans.still_out", en_cours),
+        ],
+        sections: vec![section_debuts, section_retours],
+    }
+}
+
+fn retards(biblio: &Bibliotheque, aujourdhui: NaiveDate, genere_le: String) -> Rapport {
+    let mut retards: Vec<(&Livre, NaiveDate, NaiveDate)> = biblio
+        .get_livres()
+        .iter()
+        .filter_map(|livre| {
+            let emprunt = livre.emprunt_en_cours()?;
+            emprunt
+                .en_retard(aujourdhui)
+                .then_some((livre, emprunt.debut, emprunt.echeance))
+        })
+        .collect();
+    // Les plus anciens retards en premier
+    retards.sort_by_key(|(livre, _, echeance)| (*echeance, livre.id));
+
+    let mut section = Section::new(
+        t("report.overdue.section").to_string(),
+        &[
+            "report.column.id",
+            "report.column.title",
+            "report.column.author",
+            "report.column.borrowed_on",
+            "report.column.due",
+            "report.column.days_late",
+        ],
+    );
+    for (livre, debut, echeance) in &retards {
+        section.lignes.push(vec![
+            livre.id.to_string(),
+            livre.titre.clone(),
+            nom_auteur(biblio, livre.auteur_id),
+            i18n::date(*debut),
+            i18n::date(*echeance),
+            (aujourdhui - *echeance).num_days().to_string(),
+        ]);
+    }
+
+    Rapport {
+        titre: tf("report.overdue.title", &[("date", &i18n::date(aujourdhui))]),
+        sous_titre: genere_le,
+        resume: vec![tn("report.overdue.count", retards.len())],
+        sections: vec![section],
+    }
+}
+
+fn bibliographies(biblio: &Bibliotheque, genere_le: String) -> Rapport {
+    let mut auteurs: Vec<_> = biblio.get_auteurs().iter().collect();
+    // "de Saint-Exupery" se range à la lettre D, comme dans un fichier auteurs
+    auteurs.sort_by_key(|a| (a.nom.to_lowercase(), a.prenom.to_lowercase(), a.id));
+
+    let sections = auteurs
+        .iter()
+        .map(|auteur| {
+            let mut livres: Vec<&Livre> = biblio.get_livres().iter().filter(|l| l.auteur_id == auteur.id).collect();
+            livres.sort_by(|a, b| (a.annee, &a.titre).cmp(&(b.annee, &b.titre)));
+
+            let mut section = Section::new(
+                nom_auteur(biblio, auteur.id),
+                &["report.column.year", "report.column.title", "report.column.series", "report.column.isbn"],
+            );
+            for livre in livres {
+                section.lignes.push(vec![
+                    livre.annee.to_string(),
+                    livre.titre.clone(),
+                    serie(biblio, livre),
+                    livre.isbn.clone().unwrap_or_default(),
+                ]);
+            }
+            section
+        })
+        .collect();
+
+    Rapport {
+        titre: t("report.bibliographies.title").to_string(),
+        sous_titre: genere_le,
+        resume: vec![tn("report.bibliographies.authors", auteurs.len())],
+        sections,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::LangueDeTest;
+    use crate::models::{Auteur, Emprunt};
+
+    fn date(mois: u32, jour: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, mois, jour).unwrap()
+    }
+
+    // Vol de nuit emprunté et rendu en janvier, emprunté en mars et jamais rendu
+    fn biblio() -> Bibliotheque {
+        let mut b = Bibliotheque::new();
+        b.ajouter_auteur(Auteur::new(1, "Antoine".to_string(), "de Saint-Exupery".to_string())).unwrap();
+        b.ajouter_auteur(Auteur::new(2, "Frank".to_string(), "Herbert".to_string())).unwrap();
+        b.ajouter_livre(Livre::new(1, "Le <Petit> Prince".to_string(), 1, 1943)).unwrap();
+        let mut vol_de_nuit = Livre::new(2, "Vol de nuit".to_string(), 1, 1931);
+        vol_de_nuit.emprunte = true;
+        vol_de_nuit.historique = vec![
+            Emprunt {
+                retour: Some(date(1, 20)),
+                ..Emprunt::new(date(1, 5))
+            },
+            Emprunt::new(date(3, 1)),
+        ];
+        b.ajouter_livre(vol_de_nuit).unwrap();
+        b
+    }
+
+    #[test]
+    fn month_is_read_both_ways() {
+        let juin = Mois { annee: 2024, mois: 6 };
+        assert_eq!(Mois::analyser("2024-06"), Some(juin));
+        assert_eq!(Mois::analyser(" 6/2024 "), Some(juin));
+        assert_eq!(Mois::analyser("2024-13"), None);
+        assert_eq!(Mois::analyser("juin"), None);
+        assert_eq!(juin.to_string(), "2024-06");
+    }
+
+    #[test]
+    fn loans_of_the_month_and_overdue_list() {
+        let _langue = LangueDeTest::francais();
+        let b = biblio();
+        let janvier = Rapport::generer(TypeRapport::EmpruntsDuMois, &b, Mois::analyser("2024-01").unwrap(), date(6, 3));
+        assert_eq!(janvier.sections[0].lignes, vec![vec!["05/01/2024", "2", "Vol de nuit", "26/01/2024", "20/01/2024"]]);
+        assert_eq!(janvier.sections[1].lignes.len(), 1);
+
+        let retards = Rapport::generer(TypeRapport::Retards, &b, Mois::de(date(6, 3)), date(3, 25));
+        assert_eq!(retards.sections[0].lignes[0][5], "3");
+        let a_temps = Rapport::generer(TypeRapport::Retards, &b, Mois::de(date(6, 3)), date(3, 22));
+        assert!(a_temps.sections[0].lignes.is_empty());
+    }
+
+    #[test]
+    fn bibliographies_list_every_author_by_year() {
+        let rapport = Rapport::generer(TypeRapport::Bibliographies, &biblio(), Mois::de(date(6, 3)), date(6, 3));
+        let titres: Vec<&str> = rapport.sections.iter().map(|s| s.titre.as_str()).collect();
+        assert_eq!(titres, vec!["Antoine de Saint-Exupery", "Frank Herbert"]);
+        assert_eq!(rapport.sections[0].lignes[0][1], "Vol de nuit");
+        assert!(rapport.sections[1].lignes.is_empty());
+    }
+
+    #[test]
+    fn builtin_templates_escape_for_their_format() {
+        let _langue = LangueDeTest::francais();
+        let rapport = Rapport::generer(TypeRapport::Catalogue, &biblio(), Mois::de(date(6, 3)), date(6, 3));
+
+        let html = rapport.rendre(FormatRapport::Html, &FormatRapport::Html.modele(Path::new("absent")).unwrap());
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<td>Le &lt;Petit&gt; Prince</td>"));
+
+        let markdown = FormatRapport::Markdown.modele(Path::new("absent")).unwrap();
+        let markdown = rapport.rendre(FormatRapport::Markdown, &markdown);
+        assert!(markdown.starts_with("# Catalogue\n\n_Généré le 03/06/2024_\n"));
+        assert!(markdown.contains("\n| 1 | Le \\<Petit\\> Prince | Antoine de Saint-Exupery | 1943 |"));
+        assert!(!markdown.contains("\n\n\n"));
+    }
+}
@@ -0,0 +1,249 @@
+use super::ErreurRapport;
+use crate::i18n::tf;
+use std::collections::BTreeMap;
+
+// Modèles de rapports, un sous-ensemble de Mustache:
+//   {{nom}}                 valeur, échappée pour le format du document
+//   {{#liste}} ... {{/liste}}  répété pour chaque élément, rien si la liste est vide
+//   {{^liste}} ... {{/liste}}  affiché seulement si la liste est vide
+// Dans une section, un nom est cherché dans l'élément courant puis dans les englobants
+// Une ligne qui ne contient qu'une balise de section disparaît du résultat
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Valeur {
+    Texte(String),
+    Liste(Vec<Contexte>),
+}
+
+pub type Contexte = BTreeMap<&'static str, Valeur>;
+
+#[derive(Debug, PartialEq)]
+enum Noeud {
+    Texte(String),
+    Variable(String),
+    Section { nom: String, inverse: bool, enfants: Vec<Noeud> },
+}
+
+#[derive(Debug, PartialEq)]
+enum Jeton {
+    Texte(String),
+    Variable(String),
+    Ouvre(String, bool),
+    Ferme(String),
+}
+
+#[derive(Debug)]
+pub struct Modele {
+    noeuds: Vec<Noeud>,
+}
+
+impl Modele {
+    pub fn analyser(source: &str) -> Result<Modele, ErreurRapport> {
+        let mut jetons = decouper(source)?;
+        retirer_lignes_de_section(&mut jetons);
+
+        // Pile des sections ouvertes; la première est le document
+        let mut pile: Vec<(String, bool, Vec<Noeud>)> = vec![(String::new(), false, Vec::new())];
+        for jeton in jetons {
+            match jeton {
+                Jeton::Texte(texte) if texte.is_empty() => {}
+                Jeton::Texte(texte) => pile.last_mut().unwrap().2.push(Noeud::Texte(texte)),
+                Jeton::Variable(nom) => pile.last_mut().unwrap().2.push(Noeud::Variable(nom)),
+                Jeton::Ouvre(nom, inverse) => pile.push((nom, inverse, Vec::new())),
+                Jeton::Ferme(nom) => {
+                    if pile.len() == 1 || pile.last().unwrap().0 != nom {
+                        return Err(ErreurRapport::Modele(tf("report.template.unexpected", &[("nom", &nom)])));
+                    }
+                    let (nom, inverse, enfants) = pile.pop().unwrap();
+                    pile.last_mut().unwrap().2.push(Noeud::Section { nom, inverse, enfants });
+                }
+            }
+        }
+        if pile.len() > 1 {
+            let nom = &pile.last().unwrap().0;
+            return Err(ErreurRapport::Modele(tf("report.template.unclosed", &[("nom", nom)])));
+        }
+        Ok(Modele {
+            noeuds: pile.pop().unwrap().2,
+        })
+    }
+
+    pub fn rendre(&self, contexte: &Contexte, echapper: fn(&str) -> String) -> String {
+        let mut sortie = String::new();
+        rendre_noeuds(&self.noeuds, &mut vec![contexte], echapper, &mut sortie);
+        sortie
+    }
+}
+
+// Texte et balises en alternance: le premier et le dernier jeton sont des textes, éventuellement vides
+fn decouper(source: &str) -> Result<Vec<Jeton>, ErreurRapport> {
+    let mut jetons = Vec::new();
+    let mut reste = source;
+    while let Some(debut) = reste.find("{{") {
+        jetons.push(Jeton::Texte(reste[..debut].to_string()));
+        let apres = &reste[debut + 2..];
+        let fin = apres
+            .find("}}")
+            .ok_or_else(|| ErreurRapport::Modele(tf("report.template.unterminated", &[("debut", &preview(apres))])))?;
+        let balise = apres[..fin].trim();
+        let nom = |prefixe: usize| balise[prefixe..].trim().to_string();
+        jetons.push(match balise.chars().next() {
+            Some('#') => Jeton::Ouvre(nom(1), false),
+            Some('^') => Jeton::Ouvre(nom(1), true),
+            Some('/') => Jeton::Ferme(nom(1)),
+            _ => Jeton::Variable(balise.to_string()),
+        });
+        reste = &apres[fin + 2..];
+    }
+    jetons.push(Jeton::Texte(reste.to_string()));
+    Ok(jetons)
+}
+
+// Début d'une balise non fermée, pour le message d'erreur
+fn preview(texte: &str) -> String {
+    texte.chars().take(20).collect()
+}
+
+// Une balise de section seule sur sa ligne emporte la ligne entière (espaces et retour)
+// Les lignes sont repérées sur les textes d'origine: deux balises qui se suivent sur
+// des lignes distinctes se partagent le texte qui les sépare
+fn retirer_lignes_de_section(jetons: &mut [Jeton]) {
+    // Partie conservée de chaque texte (début, fin)
+    let mut bornes: Vec<(usize, usize)> = jetons
+        .iter()
+        .map(|jeton| match jeton {
+            Jeton::Texte(texte) => (0, texte.len()),
+            _ => (0, 0),
+        })
+        .collect();
+
+    for i in (1..jetons.len()).step_by(2) {
+        if !matches!(jetons[i], Jeton::Ouvre(..) | Jeton::Ferme(_)) {
+            continue;
+        }
+        let (Jeton::Texte(avant), Jeton::Texte(apres)) = (&jetons[i - 1], &jetons[i + 1]) else {
+            continue;
+        };
+
+        // Rien avant la balise sur sa ligne: début du modèle ou retour à la ligne
+        let debut_ligne = match avant.rfind('\n') {
+            Some(position) => position + 1,
+            None if i == 1 => 0,
+            None => continue,
+        };
+        if !avant[debut_ligne..].trim().is_empty() {
+            continue;
+        }
+        // Rien après jusqu'à la fin de la ligne (ou du modèle)
+        let fin_ligne = match apres.find('\n') {
+            Some(position) => position + 1,
+            None if i + 2 == jetons.len() => apres.len(),
+            None => continue,
+        };
+        if !apres[..fin_ligne].trim().is_empty() {
+            continue;
+        }
+
+        bornes[i - 1].1 = debut_ligne;
+        bornes[i + 1].0 = fin_ligne;
+    }
+
+    for (jeton, (debut, fin)) in jetons.iter_mut().zip(bornes) {
+        if let Jeton::Texte(texte) = jeton {
+            *texte = texte[debut..fin.max(debut)].to_string();
+        }
+    }
+}
+
+fn chercher<'a>(pile: &[&'a Contexte], nom: &str) -> Option<&'a Valeur> {
+    pile.iter().rev().find_map(|contexte| contexte.get(nom))
+}
+
+fn rendre_noeuds<'a>(
+    noeuds: &[Noeud],
+    pile: &mut Vec<&'a Contexte>,
+    echapper: fn(&str) -> String,
+    sortie: &mut String,
+) {
+    for noeud in noeuds {
+        match noeud {
+            Noeud::Texte(texte) => sortie.push_str(texte),
+            Noeud::Variable(nom) => {
+                if let Some(Valeur::Texte(texte)) = chercher(pile, nom) {
+                    sortie.push_str(&echapper(texte));
+                }
+            }
+            Noeud::Section { nom, inverse, enfants } => {
+                let elements: &'a [Contexte] = match chercher(pile, nom) {
+                    Some(Valeur::Liste(elements)) => elements,
+                    _ => &[],
+                };
+                if *inverse {
+                    if elements.is_empty() {
+                        rendre_noeuds(enfants, pile, echapper, sortie);
+                    }
+                    continue;
+                }
+                for element in elements {
+                    pile.push(element);
+                    rendre_noeuds(enfants, pile, echapper, sortie);
+                    pile.pop();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texte(valeur: &str) -> Valeur {
+        Valeur::Texte(valeur.to_string())
+    }
+
+    fn sans_echappement(texte: &str) -> String {
+        texte.to_string()
+    }
+
+    fn contexte(auteurs: &[(&str, &[&str])]) -> Contexte {
+        let auteurs = auteurs
+            .iter()
+            .map(|(nom, titres)| {
+                let livres = titres.iter().map(|titre| Contexte::from([("titre", texte(titre))])).collect();
+                Contexte::from([("nom", texte(nom)), ("livres", Valeur::Liste(livres))])
+            })
+            .collect();
+        Contexte::from([("titre", texte("Bibliographies")), ("auteurs", Valeur::Liste(auteurs))])
+    }
+
+    const MODELE: &str = "# {{titre}}\n{{#auteurs}}\n## {{nom}}\n{{#livres}}\n- {{titre}}\n{{/livres}}\n{{^livres}}\n(aucun livre)\n{{/livres}}\n{{/auteurs}}\n";
+
+    #[test]
+    fn sections_repeat_and_standalone_tags_leave_no_blank_lines() {
+        let modele = Modele::analyser(MODELE).unwrap();
+        let sortie = modele.rendre(
+            &contexte(&[("Herbert", &["Dune", "Le Messie de Dune"]), ("Zola", &[])]),
+            sans_echappement,
+        );
+        assert_eq!(sortie, "# Bibliographies\n## Herbert\n- Dune\n- Le Messie de Dune\n## Zola\n(aucun livre)\n");
+    }
+
+    #[test]
+    fn inline_sections_and_escaping() {
+        let modele = Modele::analyser("|{{#livres}} {{titre}} |{{/livres}}\n{{absent}}.").unwrap();
+        let contexte = Contexte::from([(
+            "livres",
+            Valeur::Liste(vec![Contexte::from([("titre", texte("A|B"))])]),
+        )]);
+        assert_eq!(modele.rendre(&contexte, |t| t.replace('|', "\\|")), "| A\\|B |\n.");
+    }
+
+    #[test]
+    fn malformed_templates_are_rejected() {
+        assert!(matches!(Modele::analyser("{{#livres}}x"), Err(ErreurRapport::Modele(_))));
+        assert!(matches!(Modele::analyser("x{{/livres}}"), Err(ErreurRapport::Modele(_))));
+        assert!(matches!(Modele::analyser("{{#a}}{{/b}}"), Err(ErreurRapport::Modele(_))));
+        assert!(matches!(Modele::analyser("{{titre"), Err(ErreurRapport::Modele(_))));
+    }
+}
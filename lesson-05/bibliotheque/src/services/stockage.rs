@@ -0,0 +1,115 @@
+use super::ErreurSauvegarde;
+use serde::Deserialize;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+// Accès au fichier de la bibliothèque partagé par plusieurs processus (deux postes
+// ouverts sur le même dossier)
+
+// Verrou exclusif sur `<fichier>.lock`, relâché quand la valeur est détruite
+// CONCURRENCE: verrou consultatif, seuls les processus qui le demandent s'attendent;
+// il couvre la relecture de la révision et l'écriture, qui ne doivent pas se croiser
+pub struct Verrou {
+    _fichier: File,
+}
+
+impl Verrou {
+    // Attend que l'autre processus ait fini sa sauvegarde
+    pub fn prendre(fichier: &Path) -> io::Result<Verrou> {
+        let verrou = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(suffixe(fichier, "lock"))?;
+        verrou.lock()?;
+        Ok(Verrou { _fichier: verrou })
+    }
+}
+
+fn suffixe(fichier: &Path, extension: &str) -> PathBuf {
+    let mut nom = fichier.as_os_str().to_owned();
+    nom.push(".");
+    nom.push(extension);
+    PathBuf::from(nom)
+}
+
+// Contenu du fichier, None s'il n'existe pas encore
+pub fn lire_si_existe(fichier: &Path) -> io::Result<Option<String>> {
+    match fs::read_to_string(fichier) {
+        Ok(contenu) => Ok(Some(contenu)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+// Révision d'un fichier sauvegardé, sans construire toute la bibliothèque
+pub fn revision(contenu: &str) -> Result<u64, ErreurSauvegarde> {
+    #[derive(Deserialize)]
+    struct Entete {
+        // Absente des fichiers sauvegardés avant l'ajout des révisions
+        #[serde(default)]
+        revision: u64,
+    }
+    Ok(serde_json::from_str::<Entete>(contenu)?.revision)
+}
+
+// Écrit dans un fichier temporaire puis le renomme: un lecteur voit l'ancienne
+// version ou la nouvelle, jamais un fichier à moitié écrit
+pub fn ecrire_atomique(fichier: &Path, contenu: &str) -> io::Result<()> {
+    let temporaire = suffixe(fichier, "tmp");
+    fs::write(&temporaire, contenu)?;
+    fs::rename(&temporaire, fichier)
+}
+
+// Date de modification et taille: change à chaque écriture, lu sans ouvrir le fichier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Empreinte {
+    modifie: SystemTime,
+    taille: u64,
+}
+
+pub fn empreinte(fichier: &Path) -> Option<Empreinte> {
+    let meta = fs::metadata(fichier).ok()?;
+    Some(Empreinte {
+        modifie: meta.modified().ok()?,
+        taille: meta.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::TryLockError;
+
+    fn chemin(nom: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("stockage-{}-{}.json", nom, std::process::id()))
+    }
+
+    #[test]
+    fn lock_is_exclusive_until_dropped() {
+        let fichier = chemin("verrou");
+        let verrou = Verrou::prendre(&fichier).unwrap();
+        let autre = File::open(suffixe(&fichier, "lock")).unwrap();
+        assert!(matches!(autre.try_lock(), Err(TryLockError::WouldBlock)));
+
+        drop(verrou);
+        assert!(autre.try_lock().is_ok());
+        let _ = fs::remove_file(suffixe(&fichier, "lock"));
+    }
+
+    #[test]
+    fn atomic_write_replaces_the_file() {
+        let fichier = chemin("ecriture");
+        assert_eq!(lire_si_existe(&fichier).unwrap(), None);
+        assert_eq!(empreinte(&fichier), None);
+
+        ecrire_atomique(&fichier, "{}").unwrap();
+        ecrire_atomique(&fichier, "{\"revision\": 2}").unwrap();
+        assert_eq!(lire_si_existe(&fichier).unwrap().as_deref(), Some("{\"revision\": 2}"));
+        assert!(!suffixe(&fichier, "tmp").exists());
+        assert!(empreinte(&fichier).is_some());
+        let _ = fs::remove_file(&fichier);
+    }
+}
@@ -0,0 +1,102 @@
+// Recherche approximative pour la palette de commandes
+//
+// Chaque mot de la recherche doit retrouver ses lettres, dans l'ordre, dans le
+// libellé (pas forcément côte à côte): "emp petit" trouve
+// "Emprunter « Le Petit Prince »". Les majuscules et les accents sont ignorés.
+
+// Points gagnés par lettre retrouvée
+const DEBUT_DE_MOT: u32 = 10;
+const CONSECUTIVE: u32 = 5;
+const AUTRE: u32 = 1;
+
+// Score du libellé pour cette recherche (plus haut = meilleur), None s'il ne correspond pas
+// Une recherche vide correspond à tout
+pub fn score(recherche: &str, libelle: &str) -> Option<u32> {
+    let cible = replier(libelle);
+    recherche
+        .split_whitespace()
+        .map(|mot| score_mot(&replier(mot), &cible))
+        .sum()
+}
+
+// Meilleur placement du mot dans le libellé, en essayant chaque position de départ
+fn score_mot(mot: &[char], cible: &[char]) -> Option<u32> {
+    let (premiere, suite) = mot.split_first()?;
+
+    (0..cible.len())
+        .filter(|&debut| cible[debut] == *premiere)
+        .filter_map(|debut| {
+            let mut total = points(cible, debut, None);
+            let mut precedent = debut;
+            for lettre in suite {
+                let position = precedent + 1 + cible[precedent + 1..].iter().position(|c| c == lettre)?;
+                total += points(cible, position, Some(precedent));
+                precedent = position;
+            }
+            Some(total)
+        })
+        .max()
+}
+
+fn points(cible: &[char], position: usize, precedent: Option<usize>) -> u32 {
+    if precedent == Some(position.wrapping_sub(1)) {
+        CONSECUTIVE
+    } else if position == 0 || !cible[position - 1].is_alphanumeric() {
+        DEBUT_DE_MOT
+    } else {
+        AUTRE
+    }
+}
+
+// Minuscules sans accents, pour comparer "Éte" et "ete"
+fn replier(texte: &str) -> Vec<char> {
+    texte
+        .chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            'à' | 'â' | 'ä' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'î' | 'ï' => 'i',
+            'ô' | 'ö' => 'o',
+            'ù' | 'û' | 'ü' => 'u',
+            'ÿ' => 'y',
+            'ç' => 'c',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_word_must_match_in_order() {
+        assert!(score("emp petit", "Emprunter « Le Petit Prince »").is_some());
+        assert!(score("petit emp", "Emprunter « Le Petit Prince »").is_some());
+        assert!(score("emp petit", "Emprunter un livre").is_none());
+        assert!(score("tipet", "Le Petit Prince").is_none());
+    }
+
+    #[test]
+    fn empty_search_matches_everything() {
+        assert_eq!(score("", "Sauvegarder"), Some(0));
+        assert_eq!(score("   ", ""), Some(0));
+    }
+
+    #[test]
+    fn case_and_accents_are_ignored() {
+        assert!(score("ETE", "Un été").is_some());
+        assert!(score("éte", "UN ETE").is_some());
+    }
+
+    #[test]
+    fn word_starts_and_consecutive_letters_rank_first() {
+        let prefixe = score("ret", "Retourner un livre").unwrap();
+        let disperse = score("ret", "Lister les auteurs").unwrap();
+        assert!(prefixe > disperse);
+
+        // Le meilleur départ est retenu, pas le premier trouvé
+        assert_eq!(score("pri", "Le Petit Prince"), Some(DEBUT_DE_MOT + 2 * CONSECUTIVE));
+    }
+}
@@ -0,0 +1,50 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+
+// Largeur maximale des formulaires: au-delà, le formulaire est centré
+const FORM_MAX_WIDTH: u16 = 80;
+
+// À partir de cette largeur, les listes affichent un panneau de détails
+pub const WIDE_LAYOUT_MIN_WIDTH: u16 = 100;
+
+// Découpe l'écran d'un formulaire: un bloc ajusté au contenu (+ bordures)
+// et la barre d'aide sur la dernière ligne
+pub fn form_layout(area: Rect, content_height: u16) -> (Rect, Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(content_height + 2),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(area);
+
+    let width = chunks[0].width.min(FORM_MAX_WIDTH);
+    let form_area = Rect {
+        x: chunks[0].x + (chunks[0].width - width) / 2,
+        width,
+        ..chunks[0]
+    };
+
+    (form_area, chunks[2])
+}
+
+// Zone de taille fixe centrée dans `r` (dialogues)
+pub fn centered_rect(width: u16, height: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length((r.height.saturating_sub(height)) / 2),
+            Constraint::Length(height),
+            Constraint::Length((r.height.saturating_sub(height)) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Length((r.width.saturating_sub(width)) / 2),
+            Constraint::Length(width),
+            Constraint::Length((r.width.saturating_sub(width)) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -1,3 +1,5 @@
+pub mod fuzzy;
+pub mod layout;
 pub mod screens;
 pub mod theme;
 pub mod widgets;
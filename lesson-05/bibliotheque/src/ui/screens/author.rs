@@ -1,12 +1,18 @@
-use crate::app::state::{FormState, ListState, SharedBibliotheque};
-use crate::ui::widgets::Form;
-use crossterm::event::{KeyCode, KeyEvent};
+use super::{MessageScreen, libelle_tome};
+use crate::app::keymap::{Action, KeyContext};
+use crate::app::screen::{Context, Navigation, Screen};
+use crate::app::state::ListState;
+use crate::i18n::{t, tf, tn};
+use crate::models::Auteur;
+use crate::services::Bibliotheque;
+use crate::ui::theme::symbols;
+use crate::ui::widgets::{Field, Form, FormValues, HelpBar};
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     buffer::Buffer,
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Constraint, Direction, Layout, Rect},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Widget},
+    widgets::{Block, Borders, List, ListItem, Widget},
 };
 
 // Formulaire d'ajout d'auteur
@@ -15,163 +21,231 @@ pub struct AddAuthorScreen {
 }
 
 impl AddAuthorScreen {
-    pub fn new(state: &FormState) -> Self {
-        let labels = vec!["ID".to_string(), "Prénom".to_string(), "Nom".to_string()];
-        let form = Form::with_values(labels, state.fields.clone());
-        AddAuthorScreen { form }
+    pub fn new() -> Self {
+        AddAuthorScreen {
+            form: Form::new(vec![
+                Field::integer("id", t("author.field.id"), 1, u32::MAX.into()),
+                Field::text("prenom", t("author.field.first_name")),
+                Field::text("nom", t("author.field.last_name")),
+            ]),
+        }
     }
 
-    pub fn handle_key_event(&mut self, key: KeyEvent) -> Option<AuthorAction> {
-        if key.code == KeyCode::Enter
-            && !key
-                .modifiers
-                .contains(crossterm::event::KeyModifiers::SHIFT)
-        {
-            let values = self.form.values();
-            if !values.iter().any(|v| v.trim().is_empty()) {
-                return Some(AuthorAction::SubmitAdd(values));
-            }
+    fn submit(&mut self, values: FormValues, ctx: &mut Context) -> Navigation {
+        let id = values.integer("id") as u32;
+        let mut b = ctx.biblio.lock().unwrap();
+        if b.get_auteurs().iter().any(|a| a.id == id) {
+            self.form.set_error("id", t("form.id_taken").to_string());
+            return Navigation::None;
         }
 
-        self.form.handle_key_event(key);
-        None
+        let auteur = Auteur::new(id, values.text("prenom").to_string(), values.text("nom").to_string());
+        match b.ajouter_auteur(auteur) {
+            Ok(()) => Navigation::replace(MessageScreen::new(t("common.success"), t("author.added"), false)),
+            Err(e) => Navigation::error(tf("common.error_detail", &[("erreur", &e)])),
+        }
     }
+}
 
-    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Min(12), Constraint::Length(1)])
-            .split(area);
+impl Screen for AddAuthorScreen {
+    fn title(&self) -> String {
+        format!("✍️  {}", t("author.add.title"))
+    }
 
-        let block = Block::default()
-            .borders(Borders::ALL)
-            .title("✍️  Ajouter un auteur")
-            .border_style(Style::default().fg(Color::Cyan));
-
-        let inner = block.inner(chunks[0]);
-        block.render(chunks[0], buf);
-
-        self.form.render(inner, buf);
-
-        // Aide
-        let help = Paragraph::new(Line::from(vec![
-            Span::styled("Tab", Style::default().fg(Color::Yellow)),
-            Span::raw(":Champ suivant "),
-            Span::styled("Enter", Style::default().fg(Color::Yellow)),
-            Span::raw(":Ajouter "),
-            Span::styled("Esc", Style::default().fg(Color::Yellow)),
-            Span::raw(":Annuler"),
-        ]))
-        .alignment(Alignment::Center);
-
-        help.render(chunks[1], buf);
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        vec![
+            (&[Action::NextField], t("help.next_field")),
+            (&[Action::Submit], t("help.add")),
+            (&[Action::Back], t("help.cancel")),
+            (&[Action::Help], t("help.help")),
+        ]
+    }
+
+    fn key_context(&self) -> KeyContext {
+        KeyContext::Form
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, action: Option<Action>, ctx: &mut Context) -> Navigation {
+        match action {
+            Some(Action::Back) => Navigation::Pop,
+            _ => match self.form.handle_key_event(key, action) {
+                Some(values) => self.submit(values, ctx),
+                None => Navigation::None,
+            },
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, _ctx: &mut Context) -> Navigation {
+        self.form.handle_mouse_event(mouse);
+        Navigation::None
+    }
+
+    fn handle_paste(&mut self, text: &str, _ctx: &mut Context) -> Navigation {
+        self.form.paste(text);
+        Navigation::None
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        self.form.render_framed(&self.title(), &hints, area, buf, ctx.theme);
     }
 }
 
 // Liste des auteurs
-pub struct ListAuthorsScreen<'a> {
-    biblio: &'a SharedBibliotheque,
-    state: &'a mut ListState,
+#[derive(Default)]
+pub struct ListAuthorsScreen {
+    state: ListState,
+    // Zone des lignes lors du dernier rendu (souris et nombre de lignes visibles)
+    list_area: Rect,
 }
 
-impl<'a> ListAuthorsScreen<'a> {
-    pub fn new(biblio: &'a SharedBibliotheque, state: &'a mut ListState) -> Self {
-        ListAuthorsScreen { biblio, state }
+impl ListAuthorsScreen {
+    pub fn new() -> Self {
+        ListAuthorsScreen::default()
+    }
+
+    // Liste ouverte sur l'en-tête d'un auteur (ex: depuis la palette)
+    pub fn on_author(biblio: &Bibliotheque, auteur_id: u32) -> Self {
+        let mut screen = ListAuthorsScreen::new();
+        screen.state.selected = biblio
+            .get_auteurs()
+            .iter()
+            .take_while(|auteur| auteur.id != auteur_id)
+            .map(|auteur| auteur.livres.len() + 2)
+            .sum();
+        screen
     }
 
-    pub fn handle_key_event(&mut self, key: KeyEvent) {
-        let b = self.biblio.lock().unwrap();
+    fn visible_lines(&self) -> usize {
+        self.list_area.height as usize
+    }
 
-        // Compter le nombre total d'items affichés (auteurs + livres + lignes vides)
-        let mut total_items = 0;
-        for auteur in b.get_auteurs() {
-            total_items += 1; // En-tête auteur
-            total_items += auteur.livres.len(); // Livres
-            total_items += 1; // Ligne vide
+    // Nombre total de lignes affichées (auteurs + livres + lignes vides)
+    fn total_items(ctx: &Context) -> usize {
+        let b = ctx.biblio.lock().unwrap();
+        b.get_auteurs()
+            .iter()
+            .map(|auteur| auteur.livres.len() + 2)
+            .sum()
+    }
+}
+
+impl Screen for ListAuthorsScreen {
+    fn title(&self) -> String {
+        format!("👥 {}", t("author.list.title"))
+    }
+
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        vec![
+            (&[Action::Up, Action::Down], t("help.navigate")),
+            (&[Action::PageUp, Action::PageDown], t("help.scroll")),
+            (&[Action::Back], t("help.back")),
+            (&[Action::Help], t("help.help")),
+        ]
+    }
+
+    fn handle_key(&mut self, _key: KeyEvent, action: Option<Action>, ctx: &mut Context) -> Navigation {
+        if action == Some(Action::Back) {
+            return Navigation::Pop;
         }
-        drop(b);
+
+        let total_items = Self::total_items(ctx);
 
         if total_items == 0 {
-            return;
+            return Navigation::None;
         }
 
-        let old_selected = self.state.selected;
+        // Une page = le nombre de lignes réellement visibles
+        let page = self.visible_lines().max(1) as isize;
 
-        match key.code {
-            KeyCode::Down | KeyCode::Char('j') => {
+        match action {
+            Some(Action::Down) => {
                 self.state.selected = (self.state.selected + 1) % total_items;
             }
-            KeyCode::Up | KeyCode::Char('k') => {
+            Some(Action::Up) => {
                 if self.state.selected == 0 {
                     self.state.selected = total_items - 1;
                 } else {
                     self.state.selected -= 1;
                 }
             }
-            KeyCode::PageDown => {
-                self.state.selected = (self.state.selected + 10).min(total_items - 1);
-            }
-            KeyCode::PageUp => {
-                self.state.selected = self.state.selected.saturating_sub(10);
-            }
+            Some(Action::PageDown) => self.state.move_by(page, total_items),
+            Some(Action::PageUp) => self.state.move_by(-page, total_items),
             _ => {}
         }
 
-        // Ajuster le scroll_offset pour garder l'élément sélectionné visible
-        // On affiche environ 15 lignes à la fois (estimation)
-        const VISIBLE_LINES: usize = 15;
+        self.state.scroll_into_view(self.visible_lines());
+        Navigation::None
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, ctx: &mut Context) -> Navigation {
+        let total_items = Self::total_items(ctx);
 
-        if self.state.selected >= self.state.scroll_offset + VISIBLE_LINES {
-            // Scroll down
-            self.state.scroll_offset = self.state.selected.saturating_sub(VISIBLE_LINES - 1);
-        } else if self.state.selected < self.state.scroll_offset {
-            // Scroll up
-            self.state.scroll_offset = self.state.selected;
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let clicked = self.state.item_at(self.list_area, mouse.column, mouse.row);
+                if let Some(index) = clicked.filter(|&index| index < total_items) {
+                    self.state.selected = index;
+                }
+            }
+            MouseEventKind::ScrollDown => self.state.move_by(1, total_items),
+            MouseEventKind::ScrollUp => self.state.move_by(-1, total_items),
+            _ => {}
         }
+
+        self.state.scroll_into_view(self.visible_lines());
+        Navigation::None
     }
-}
 
-impl<'a> Widget for ListAuthorsScreen<'a> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Min(10), Constraint::Length(1)])
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
             .split(area);
 
-        let b = self.biblio.lock().unwrap();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(self.title())
+            .border_style(ctx.theme.border());
+        self.list_area = block.inner(chunks[0]);
+        self.state.fit(Self::total_items(ctx), self.visible_lines());
+
+        let b = ctx.biblio.lock().unwrap();
         let auteurs = b.get_auteurs();
+        // Ordre des livres dans les listes (séries regroupées)
+        let ordre: Vec<u32> = b.livres_groupes().iter().map(|l| l.id).collect();
 
         let mut items: Vec<ListItem> = Vec::new();
         let mut item_index = 0;
         let selected = self.state.selected;
+        let selector = format!("{} ", symbols::SELECTOR);
+        let branch = format!(" {} ", symbols::TREE_BRANCH);
 
         if auteurs.is_empty() {
             items.push(ListItem::new(Line::from(Span::styled(
-                "Aucun auteur dans la bibliothèque",
-                Style::default().fg(Color::DarkGray),
+                t("author.list.empty"),
+                ctx.theme.dim(),
             ))));
         } else {
             for auteur in auteurs {
                 // En-tête auteur avec highlight si sélectionné
                 let is_selected = item_index == selected;
                 let header_style = if is_selected {
-                    Style::default()
-                        .fg(Color::Cyan)
-                        .bg(Color::DarkGray)
-                        .add_modifier(Modifier::BOLD)
+                    ctx.theme.selected()
                 } else {
-                    Style::default()
-                        .fg(Color::Cyan)
-                        .add_modifier(Modifier::BOLD)
+                    ctx.theme.title()
                 };
 
-                let prefix = if is_selected { "> " } else { "  " };
+                let prefix = if is_selected { selector.as_str() } else { "  " };
                 let header = Line::from(vec![
                     Span::styled(prefix, header_style),
                     Span::styled(
                         format!(
-                            "#{} - {} {} ({} livre(s))",
-                            auteur.id, auteur.prenom, auteur.nom, auteur.livres.len()
+                            "#{} - {} {} ({})",
+                            auteur.id,
+                            auteur.prenom,
+                            auteur.nom,
+                            tn("author.list.books", auteur.livres.len())
                         ),
                         header_style,
                     ),
@@ -179,37 +253,40 @@ impl<'a> Widget for ListAuthorsScreen<'a> {
                 items.push(ListItem::new(header));
                 item_index += 1;
 
-                // Liste des livres de l'auteur
+                // Liste des livres de l'auteur, ceux d'une même série à la suite
                 if !auteur.livres.is_empty() {
-                    for &livre_id in &auteur.livres {
+                    let mut livres_auteur = auteur.livres.clone();
+                    livres_auteur.sort_by_key(|id| ordre.iter().position(|l| l == id).unwrap_or(usize::MAX));
+                    for livre_id in livres_auteur {
                         let is_selected = item_index == selected;
                         let livre_style = if is_selected {
-                            Style::default().fg(Color::White).bg(Color::DarkGray)
+                            ctx.theme.text().bg(ctx.theme.selection_bg)
                         } else {
-                            Style::default().fg(Color::White)
+                            ctx.theme.text()
                         };
 
-                        let prefix = if is_selected { "> " } else { "  " };
+                        let prefix = if is_selected { selector.as_str() } else { "  " };
 
                         match b.get_livres().iter().find(|l| l.id == livre_id) {
                             Some(livre) => {
+                                let mut texte = format!("{} ({})", livre.titre, livre.annee);
+                                if let Some(volume) = libelle_tome(livre, &b, "book.list.volume") {
+                                    texte = format!("{} {}", texte, volume);
+                                }
                                 let livre_line = Line::from(vec![
                                     Span::styled(prefix, livre_style),
-                                    Span::raw(" └─ "),
-                                    Span::styled(
-                                        format!("{} ({})", livre.titre, livre.annee),
-                                        livre_style,
-                                    ),
+                                    Span::raw(branch.as_str()),
+                                    Span::styled(texte, livre_style),
                                 ]);
                                 items.push(ListItem::new(livre_line));
                             }
                             None => {
                                 let livre_line = Line::from(vec![
                                     Span::styled(prefix, livre_style),
-                                    Span::raw(" └─ "),
+                                    Span::raw(branch.as_str()),
                                     Span::styled(
-                                        format!("Livre ID {} (non trouvé)", livre_id),
-                                        Style::default().fg(Color::Red),
+                                        tf("author.list.missing_book", &[("id", &livre_id)]),
+                                        ctx.theme.status(true),
                                     ),
                                 ]);
                                 items.push(ListItem::new(livre_line));
@@ -229,32 +306,12 @@ impl<'a> Widget for ListAuthorsScreen<'a> {
         let visible_items: Vec<ListItem> = items
             .into_iter()
             .skip(self.state.scroll_offset)
+            .take(self.visible_lines())
             .collect();
 
-        let list = List::new(visible_items).block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("👥 Liste des auteurs")
-                .border_style(Style::default().fg(Color::Blue)),
-        );
-
-        list.render(chunks[0], buf);
-
-        // Aide
-        let help = Paragraph::new(Line::from(vec![
-            Span::styled("↑/↓", Style::default().fg(Color::Yellow)),
-            Span::raw(":Navigate "),
-            Span::styled("PgUp/PgDn", Style::default().fg(Color::Yellow)),
-            Span::raw(":Scroll "),
-            Span::styled("Esc", Style::default().fg(Color::Yellow)),
-            Span::raw(":Retour"),
-        ]))
-        .alignment(Alignment::Center);
-
-        help.render(chunks[1], buf);
-    }
-}
+        List::new(visible_items).block(block).render(chunks[0], buf);
 
-pub enum AuthorAction {
-    SubmitAdd(Vec<String>),
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        HelpBar::new(&hints, ctx.theme).render(chunks[1], buf);
+    }
 }
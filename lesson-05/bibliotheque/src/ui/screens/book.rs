@@ -1,162 +1,553 @@
-use crate::app::state::{FormState, ListState, SharedBibliotheque};
-use crate::ui::widgets::Form;
-use crossterm::event::{KeyCode, KeyEvent};
+use super::{ConfirmScreen, FiltreEmplacement, LocationPickerScreen, MessageScreen, SummaryScreen, libelle_rayon};
+use crate::app::event::{AppEvent, DomainEvent, EventKind};
+use crate::app::keymap::{Action, KeyContext};
+use crate::app::screen::{Context, Navigation, Screen};
+use crate::app::state::ListState;
+use crate::i18n::{self, t, tf, tn};
+use crate::models::{Auteur, Emplacement, Livre, Rayon, Serie, Tome};
+use crate::services::metadonnees::Metadonnees;
+use crate::services::{Bibliotheque, ErreurBibliotheque};
+use crate::ui::layout::WIDE_LAYOUT_MIN_WIDTH;
+use crate::ui::theme::{Theme, symbols};
+use crate::ui::widgets::{Field, FieldValue, Form, FormValues, HelpBar, Lookup, Suggestion};
+use chrono::Local;
+use std::collections::BTreeSet;
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     buffer::Buffer,
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Constraint, Direction, Layout, Rect},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Widget},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Widget, Wrap},
 };
 
-// Formulaire d'ajout de livre
+// Formulaire d'ajout de livre, aussi utilisé pour modifier un livre existant
 pub struct AddBookScreen {
     form: Form,
+    // ID du livre modifié; None pour un ajout
+    edition: Option<u32>,
+    // ISBN dont la notice est attendue
+    recherche: Option<String>,
 }
 
 impl AddBookScreen {
-    pub fn new(state: &FormState) -> Self {
-        let labels = vec![
-            "ID".to_string(),
-            "Titre".to_string(),
-            "Auteur ID".to_string(),
-            "Année".to_string(),
-        ];
-        let form = Form::with_values(labels, state.fields.clone());
-        AddBookScreen { form }
+    // L'auteur se cherche par son nom parmi les auteurs existants
+    pub fn new(biblio: &Bibliotheque) -> Self {
+        AddBookScreen {
+            form: Form::new(vec![
+                Field::integer("id", t("book.field.id"), 1, u32::MAX.into()),
+                Field::text("titre", t("book.field.title")),
+                champ_auteur(biblio),
+                Field::year("annee", t("book.field.year")),
+                Field::isbn("isbn", t("book.field.isbn")).optional(),
+                champ_serie(biblio),
+                champ_tome(),
+                champ_cote(),
+                champ_rayon(biblio),
+                champ_position(),
+            ]),
+            edition: None,
+            recherche: None,
+        }
+    }
+
+    // Formulaire prérempli; l'ID d'un livre ne change pas
+    pub fn edit(biblio: &Bibliotheque, livre: &Livre) -> Self {
+        let auteur = match biblio.get_auteurs().iter().find(|a| a.id == livre.auteur_id) {
+            Some(a) => format!("{} {}", a.prenom, a.nom),
+            None => livre.auteur_id.to_string(),
+        };
+        let (serie, numero) = match livre.tome {
+            Some(tome) => (
+                biblio.trouver_serie(tome.serie_id).map_or(String::new(), |s| s.nom.clone()),
+                tome.numero.to_string(),
+            ),
+            None => (String::new(), String::new()),
+        };
+        let (rayon, position) = match livre.emplacement {
+            Some(emplacement) => (
+                biblio.trouver_rayon(emplacement.rayon_id).map_or(String::new(), libelle_rayon),
+                emplacement.position.to_string(),
+            ),
+            None => (String::new(), String::new()),
+        };
+
+        AddBookScreen {
+            form: Form::new(vec![
+                Field::text("titre", t("book.field.title")).with_value(&livre.titre),
+                champ_auteur(biblio).with_value(&auteur),
+                Field::year("annee", t("book.field.year")).with_value(&livre.annee.to_string()),
+                Field::isbn("isbn", t("book.field.isbn"))
+                    .optional()
+                    .with_value(livre.isbn.as_deref().unwrap_or("")),
+                champ_serie(biblio).with_value(&serie),
+                champ_tome().with_value(&numero),
+                champ_cote().with_value(livre.cote.as_deref().unwrap_or("")),
+                champ_rayon(biblio).with_value(&rayon),
+                champ_position().with_value(&position),
+            ]),
+            edition: Some(livre.id),
+            recherche: None,
+        }
     }
 
-    pub fn handle_key_event(&mut self, key: KeyEvent) -> Option<BookAction> {
-        if key.code == KeyCode::Enter && !key.modifiers.contains(crossterm::event::KeyModifiers::SHIFT) {
-            // Vérifier si on n'est pas en train d'éditer un champ
-            let values = self.form.values();
-            if !values.iter().any(|v| v.trim().is_empty()) {
-                return Some(BookAction::SubmitAdd(values));
+    // Cherche la notice de l'ISBN saisi; la réponse arrive par handle_event
+    fn chercher_notice(&mut self, ctx: &Context) -> Navigation {
+        let Some(recherche) = ctx.recherche_isbn else {
+            self.form.set_error("isbn", t("book.lookup.unavailable").to_string());
+            return Navigation::None;
+        };
+        let Some(champ) = self.form.field_mut("isbn") else {
+            return Navigation::None;
+        };
+        match champ.parse() {
+            Ok(FieldValue::Text(isbn)) => {
+                champ.error = None;
+                recherche.lancer(isbn.clone());
+                self.recherche = Some(isbn);
             }
+            Ok(_) => self.form.set_error("isbn", t("form.required").to_string()),
+            Err(erreur) => self.form.set_error("isbn", erreur),
         }
+        Navigation::None
+    }
 
-        self.form.handle_key_event(key);
-        None
+    // La notice remplace la saisie; l'auteur est cherché parmi les auteurs connus,
+    // sinon sa création est proposée comme pour une saisie au clavier
+    fn preremplir(&mut self, notice: &Metadonnees) {
+        let mut valeurs = vec![("titre", notice.titre.clone())];
+        if let Some(auteur) = notice.auteurs.first() {
+            valeurs.push(("auteur", auteur.clone()));
+        }
+        if let Some(annee) = notice.annee {
+            valeurs.push(("annee", annee.to_string()));
+        }
+        for (nom, valeur) in valeurs {
+            if let Some(champ) = self.form.field_mut(nom) {
+                champ.set_value(&valeur);
+            }
+        }
     }
 
-    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Min(15), Constraint::Length(1)])
-            .split(area);
+    fn submit(&mut self, values: FormValues, ctx: &mut Context) -> Navigation {
+        let annee = values.integer("annee") as u32;
+        let titre = values.text("titre").to_string();
+        let isbn = Some(values.text("isbn").to_string()).filter(|isbn| !isbn.is_empty());
+        let cote = Some(values.text("cote").trim().to_string()).filter(|cote| !cote.is_empty());
 
-        let block = Block::default()
-            .borders(Borders::ALL)
-            .title("📚 Ajouter un livre")
-            .border_style(Style::default().fg(Color::Cyan));
+        let mut b = ctx.biblio.lock().unwrap();
+        let id = match self.edition {
+            Some(id) => id,
+            None => {
+                let id = values.integer("id") as u32;
+                if b.get_livres().iter().any(|l| l.id == id) {
+                    self.form.set_error("id", t("form.id_taken").to_string());
+                    return Navigation::None;
+                }
+                id
+            }
+        };
+
+        // Un tome appartient à une série, et une série demande un numéro de tome
+        let tome = match (values.optional_lookup("serie"), values.optional_integer("tome")) {
+            (None, None) => None,
+            (Some(serie), Some(numero)) => Some((serie.clone(), numero as u32)),
+            (Some(_), None) => {
+                self.form.set_error("tome", t("form.required").to_string());
+                return Navigation::None;
+            }
+            (None, Some(_)) => {
+                self.form.set_error("serie", t("book.series_missing").to_string());
+                return Navigation::None;
+            }
+        };
+
+        // Une position se prend dans un rayon; un nouveau rayon se saisit "Salle / Rayon"
+        let rayon = match (values.optional_lookup("rayon"), values.optional_integer("position")) {
+            (None, None) => None,
+            (None, Some(_)) => {
+                self.form.set_error("rayon", t("book.shelf_missing").to_string());
+                return Navigation::None;
+            }
+            (Some(Lookup::New(saisie)), _) if !saisie.contains('/') => {
+                self.form.set_error("rayon", t("book.shelf_format").to_string());
+                return Navigation::None;
+            }
+            (Some(rayon), position) => Some((rayon.clone(), position.map(|p| p as u32))),
+        };
+
+        // Auteur, série et rayon saisis sont créés avec le livre, en une fois: si le livre
+        // est refusé, ils ne restent pas dans le catalogue
+        let edition = self.edition;
+        let resultat = b.en_une_fois(|b| {
+            // Auteur choisi dans la liste, ou créé à partir du nom saisi ("Prénom Nom")
+            let (auteur_id, auteur_cree) = match values.lookup("auteur") {
+                Lookup::Existing(auteur_id) => (*auteur_id, None),
+                Lookup::New(nom_complet) => {
+                    let auteur_id = b.prochain_id_auteur();
+                    let (prenom, nom) = nom_complet.split_once(' ').unwrap_or(("", nom_complet));
+                    b.ajouter_auteur(Auteur::new(auteur_id, prenom.to_string(), nom.trim().to_string()))?;
+                    (auteur_id, Some(nom_complet))
+                }
+            };
 
-        let inner = block.inner(chunks[0]);
-        block.render(chunks[0], buf);
+            // Même principe pour la série
+            let (tome, serie_creee) = match tome {
+                None => (None, None),
+                Some((Lookup::Existing(serie_id), numero)) => (Some(Tome { serie_id, numero }), None),
+                Some((Lookup::New(nom), numero)) => {
+                    let serie_id = b.prochain_id_serie();
+                    b.ajouter_serie(Serie::new(serie_id, nom.clone()))?;
+                    let creee = tf("book.series_created", &[("nom", &nom), ("id", &serie_id)]);
+                    (Some(Tome { serie_id, numero }), Some(creee))
+                }
+            };
 
-        self.form.render(inner, buf);
+            let (emplacement, rayon_cree) = match rayon {
+                None => (None, None),
+                Some((rayon, position)) => {
+                    let (rayon_id, cree) = match rayon {
+                        Lookup::Existing(rayon_id) => (rayon_id, None),
+                        Lookup::New(saisie) => {
+                            let (salle, nom) = saisie.split_once('/').unwrap_or(("", &saisie));
+                            let rayon_id = b.prochain_id_rayon();
+                            let rayon = Rayon::new(rayon_id, salle.trim().to_string(), nom.trim().to_string());
+                            let libelle = libelle_rayon(&rayon);
+                            let cree = tf("book.shelf_created", &[("rayon", &libelle), ("id", &rayon_id)]);
+                            b.ajouter_rayon(rayon)?;
+                            (rayon_id, Some(cree))
+                        }
+                    };
+                    // Sans position: au bout du rayon, ou à sa place actuelle s'il y est déjà
+                    let actuelle = b
+                        .get_livres()
+                        .iter()
+                        .find(|l| l.id == id)
+                        .and_then(|l| l.emplacement)
+                        .filter(|e| e.rayon_id == rayon_id)
+                        .map(|e| e.position);
+                    let position = position.or(actuelle).unwrap_or_else(|| b.position_libre(rayon_id));
+                    (Some(Emplacement { rayon_id, position }), cree)
+                }
+            };
 
-        // Aide
-        let help = Paragraph::new(Line::from(vec![
-            Span::styled("Tab", Style::default().fg(Color::Yellow)),
-            Span::raw(":Champ suivant "),
-            Span::styled("Enter", Style::default().fg(Color::Yellow)),
-            Span::raw(":Ajouter "),
-            Span::styled("Esc", Style::default().fg(Color::Yellow)),
-            Span::raw(":Annuler"),
-        ]))
-        .alignment(Alignment::Center);
+            if edition.is_some() {
+                b.modifier_livre(id, titre, auteur_id, annee, isbn)?;
+                b.ranger_livre(id, tome)?;
+                b.placer_livre(id, cote, emplacement)?;
+            } else {
+                b.ajouter_livre(Livre {
+                    isbn,
+                    tome,
+                    cote,
+                    emplacement,
+                    ..Livre::new(id, titre, auteur_id, annee)
+                })?;
+                b.associer_livre_auteur(id, auteur_id)?;
+            }
+            Ok((auteur_id, auteur_cree, [serie_creee, rayon_cree]))
+        });
+        let (auteur_id, auteur_cree, crees) = match resultat {
+            Ok(resultat) => resultat,
+            Err(e) => return Navigation::error(tf("common.error_detail", &[("erreur", &e)])),
+        };
 
-        help.render(chunks[1], buf);
+        let mut message = match (edition, auteur_cree) {
+            (None, None) => t("book.added").to_string(),
+            (None, Some(nom)) => tf("book.added_with_author", &[("nom", nom), ("id", &auteur_id)]),
+            (Some(_), None) => t("book.updated").to_string(),
+            (Some(_), Some(nom)) => tf("book.updated_with_author", &[("nom", nom), ("id", &auteur_id)]),
+        };
+        for cree in crees.into_iter().flatten() {
+            message = format!("{}\n{}", message, cree);
+        }
+        Navigation::replace(MessageScreen::new(t("common.success"), message, false))
     }
 }
 
-// Liste des livres
-pub struct ListBooksScreen<'a> {
-    biblio: &'a SharedBibliotheque,
-    state: &'a mut ListState,
+fn champ_auteur(biblio: &Bibliotheque) -> Field {
+    let auteurs = biblio
+        .get_auteurs()
+        .iter()
+        .map(|a| Suggestion {
+            id: a.id,
+            label: format!("{} {}", a.prenom, a.nom),
+        })
+        .collect();
+    Field::lookup("auteur", t("book.field.author"), auteurs).allow_create("book.create_author")
+}
+
+fn champ_serie(biblio: &Bibliotheque) -> Field {
+    let series = biblio
+        .get_series()
+        .iter()
+        .map(|s| Suggestion {
+            id: s.id,
+            label: s.nom.clone(),
+        })
+        .collect();
+    Field::lookup("serie", t("book.field.series"), series)
+        .allow_create("book.create_series")
+        .optional()
+}
+
+fn champ_tome() -> Field {
+    Field::integer("tome", t("book.field.volume"), 1, 9999).optional()
+}
+
+// Cote libre: Dewey ("843.914 SAI"), CDU ("821.133.1-31")...
+fn champ_cote() -> Field {
+    Field::text("cote", t("book.field.call_number")).optional()
+}
+
+fn champ_rayon(biblio: &Bibliotheque) -> Field {
+    let rayons = biblio
+        .get_rayons()
+        .iter()
+        .map(|r| Suggestion {
+            id: r.id,
+            label: libelle_rayon(r),
+        })
+        .collect();
+    Field::lookup("rayon", t("book.field.shelf"), rayons)
+        .allow_create("book.create_shelf")
+        .optional()
+}
+
+fn champ_position() -> Field {
+    Field::integer("position", t("book.field.position"), 1, 9999).optional()
 }
 
-impl<'a> ListBooksScreen<'a> {
-    pub fn new(biblio: &'a SharedBibliotheque, state: &'a mut ListState) -> Self {
-        ListBooksScreen { biblio, state }
+impl Screen for AddBookScreen {
+    fn title(&self) -> String {
+        let titre = match self.edition {
+            Some(_) => format!("📝 {}", t("book.edit.title")),
+            None => format!("📚 {}", t("book.add.title")),
+        };
+        match &self.recherche {
+            Some(isbn) => format!("{} · {}", titre, tf("book.lookup.pending", &[("isbn", isbn)])),
+            None => titre,
+        }
     }
 
-    pub fn handle_key_event(&mut self, key: KeyEvent) {
-        let b = self.biblio.lock().unwrap();
-        let count = b.get_livres().len();
-        drop(b);
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        let submit = if self.edition.is_some() { t("help.save") } else { t("help.add") };
+        vec![
+            (&[Action::NextField], t("help.next_field")),
+            (&[Action::Submit], submit),
+            (&[Action::Lookup], t("help.lookup_isbn")),
+            (&[Action::Back], t("help.cancel")),
+            (&[Action::Help], t("help.help")),
+        ]
+    }
 
-        if count == 0 {
+    fn key_context(&self) -> KeyContext {
+        KeyContext::Form
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, action: Option<Action>, ctx: &mut Context) -> Navigation {
+        match action {
+            // Esc ferme d'abord la liste des auteurs proposés
+            Some(Action::Back) if self.form.close_popup() => Navigation::None,
+            Some(Action::Back) => Navigation::Pop,
+            Some(Action::Lookup) => self.chercher_notice(ctx),
+            _ => match self.form.handle_key_event(key, action) {
+                Some(values) => self.submit(values, ctx),
+                None => Navigation::None,
+            },
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, _ctx: &mut Context) -> Navigation {
+        self.form.handle_mouse_event(mouse);
+        Navigation::None
+    }
+
+    fn handle_paste(&mut self, text: &str, _ctx: &mut Context) -> Navigation {
+        self.form.paste(text);
+        Navigation::None
+    }
+
+    fn subscriptions(&self) -> &'static [EventKind] {
+        &[EventKind::Domain]
+    }
+
+    // Réponse de la recherche de notice; celle d'un ISBN déjà remplacé par un autre est ignorée
+    // Les erreurs s'affichent sous l'ISBN sans lui donner le focus: l'utilisateur a pu continuer à saisir
+    fn handle_event(&mut self, event: &AppEvent, _ctx: &mut Context) {
+        let AppEvent::Domain(DomainEvent::Metadonnees { isbn, resultat }) = event else {
+            return;
+        };
+        if self.recherche.as_ref() != Some(isbn) {
             return;
         }
+        self.recherche = None;
 
-        match key.code {
-            KeyCode::Down | KeyCode::Char('j') => {
-                self.state.selected = (self.state.selected + 1) % count;
-            }
-            KeyCode::Up | KeyCode::Char('k') => {
-                if self.state.selected == 0 {
-                    self.state.selected = count - 1;
-                } else {
-                    self.state.selected -= 1;
-                }
-            }
-            KeyCode::PageDown => {
-                self.state.selected = (self.state.selected + 10).min(count - 1);
+        let erreur = match resultat {
+            Ok(Some(notice)) => {
+                self.preremplir(notice);
+                return;
             }
-            KeyCode::PageUp => {
-                self.state.selected = self.state.selected.saturating_sub(10);
-            }
-            _ => {}
+            Ok(None) => tf("book.lookup.not_found", &[("isbn", isbn)]),
+            Err(erreur) => erreur.clone(),
+        };
+        if let Some(champ) = self.form.field_mut("isbn") {
+            champ.error = Some(erreur);
+        }
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        self.form.render_framed(&self.title(), &hints, area, buf, ctx.theme);
+    }
+}
+
+// Liste des livres
+#[derive(Default)]
+pub struct ListBooksScreen {
+    state: ListState,
+    // Zone des lignes lors du dernier rendu (souris et nombre de lignes visibles)
+    list_area: Rect,
+    // IDs des livres marqués (Espace) pour un emprunt ou un retour groupé
+    marques: BTreeSet<u32>,
+    // Salle ou rayon choisi (f); None: tous les livres
+    filtre: Option<FiltreEmplacement>,
+}
+
+impl ListBooksScreen {
+    pub fn new() -> Self {
+        ListBooksScreen::default()
+    }
+
+    // Liste limitée aux livres d'une salle ou d'un rayon
+    pub fn filtree(filtre: Option<FiltreEmplacement>) -> Self {
+        ListBooksScreen {
+            filtre,
+            ..ListBooksScreen::default()
         }
+    }
 
-        // Ajuster le scroll_offset pour garder l'élément sélectionné visible
-        const VISIBLE_LINES: usize = 15;
+    // Liste ouverte sur un livre donné (ex: depuis la palette)
+    pub fn on_book(biblio: &Bibliotheque, livre_id: u32) -> Self {
+        let mut screen = ListBooksScreen::new();
+        screen.state.selected = screen.livres(biblio).iter().position(|l| l.id == livre_id).unwrap_or(0);
+        screen
+    }
 
-        if self.state.selected >= self.state.scroll_offset + VISIBLE_LINES {
-            // Scroll down
-            self.state.scroll_offset = self.state.selected.saturating_sub(VISIBLE_LINES - 1);
-        } else if self.state.selected < self.state.scroll_offset {
-            // Scroll up
-            self.state.scroll_offset = self.state.selected;
+    // Livres affichés, dans l'ordre d'affichage: les séries regroupées
+    fn livres<'a>(&self, biblio: &'a Bibliotheque) -> Vec<&'a Livre> {
+        let mut livres = biblio.livres_groupes();
+        if let Some(filtre) = &self.filtre {
+            livres.retain(|l| filtre.contient(l, biblio));
         }
+        livres
     }
-}
 
-impl<'a> Widget for ListBooksScreen<'a> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Min(10), Constraint::Length(1)])
-            .split(area);
+    fn visible_lines(&self) -> usize {
+        self.list_area.height as usize
+    }
 
-        let b = self.biblio.lock().unwrap();
-        let livres = b.get_livres();
+    // Emprunt ou retour des livres marqués, à défaut du livre sélectionné
+    // Un seul livre: écran de succès; plusieurs: bilan livre par livre
+    fn operation_groupee(
+        &mut self,
+        b: &mut Bibliotheque,
+        titre: &str,
+        operation: impl Fn(&mut Bibliotheque, u32) -> Result<(), ErreurBibliotheque>,
+        succes: fn(&Bibliotheque, u32) -> Box<dyn Screen>,
+    ) -> Navigation {
+        let livres: Vec<(u32, String)> = self
+            .livres(b)
+            .into_iter()
+            .enumerate()
+            .filter(|(i, l)| {
+                if self.marques.is_empty() {
+                    *i == self.state.selected
+                } else {
+                    self.marques.contains(&l.id)
+                }
+            })
+            .map(|(_, l)| (l.id, l.titre.clone()))
+            .collect();
+        self.marques.clear();
+
+        if let [(id, _)] = livres[..] {
+            return match operation(b, id) {
+                Ok(()) => Navigation::Push(succes(b, id)),
+                Err(e) => Navigation::error(tf("common.error_detail", &[("erreur", &e)])),
+            };
+        }
+
+        let mut bilan = Vec::new();
+        for (id, titre_livre) in livres {
+            bilan.push(match operation(b, id) {
+                Ok(()) => (true, tf("book.batch.line", &[("id", &id), ("titre", &titre_livre)])),
+                Err(e) => (
+                    false,
+                    tf("book.batch.line_error", &[("id", &id), ("titre", &titre_livre), ("erreur", &e)]),
+                ),
+            });
+        }
+        Navigation::push(SummaryScreen::new(titre, bilan))
+    }
+
+    fn render_list(&mut self, biblio: &Bibliotheque, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        let livres = self.livres(biblio);
+        let mut title = self.title();
+        if let Some(filtre) = &self.filtre {
+            title = format!("{} · {}", title, filtre.libelle(biblio));
+        }
+        if !self.marques.is_empty() {
+            title = format!("{} ({})", title, tn("book.list.marked", self.marques.len()));
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(theme.border());
+        self.list_area = block.inner(area);
+        self.state.fit(livres.len(), self.visible_lines());
 
         let items: Vec<ListItem> = if livres.is_empty() {
             vec![ListItem::new(Line::from(Span::styled(
-                "Aucun livre dans la bibliothèque",
-                Style::default().fg(Color::DarkGray),
+                t("book.list.empty"),
+                theme.dim(),
             )))]
         } else {
             livres
                 .iter()
                 .enumerate()
+                // Appliquer le scroll en skippant les premiers items
+                .skip(self.state.scroll_offset)
+                .take(self.visible_lines())
                 .map(|(i, livre)| {
-                    let status_icon = if livre.emprunte { "●" } else { "○" };
-                    let status_color = if livre.emprunte { Color::Red } else { Color::Green };
-                    let status_text = if livre.emprunte { "Emprunté" } else { "Disponible" };
-
-                    let line = Line::from(vec![
-                        Span::raw(format!("#{} - {} ({}) - Auteur ID: {} ", livre.id, livre.titre, livre.annee, livre.auteur_id)),
-                        Span::styled(status_icon, Style::default().fg(status_color)),
-                        Span::styled(format!(" {}", status_text), Style::default().fg(status_color)),
+                    let status_icon = if livre.emprunte { symbols::BULLET_FILLED } else { symbols::BULLET_EMPTY };
+                    let status_style = theme.livre(livre.emprunte);
+                    let ligne = tf(
+                        "book.list.line",
+                        &[("id", &livre.id), ("titre", &livre.titre), ("annee", &livre.annee), ("auteur", &livre.auteur_id)],
+                    );
+
+                    // Cases à cocher affichées dès qu'un livre est marqué
+                    let case = match (self.marques.is_empty(), self.marques.contains(&livre.id)) {
+                        (true, _) => String::new(),
+                        (false, true) => format!("{} ", symbols::CHECKBOX_CHECKED),
+                        (false, false) => format!("{} ", symbols::CHECKBOX_EMPTY),
+                    };
+
+                    // Les livres d'une série se suivent: leur tome est rappelé après le titre
+                    let mut spans = vec![Span::raw(format!("{}{} ", case, ligne))];
+                    if let Some(volume) = libelle_tome(livre, biblio, "book.list.volume") {
+                        spans.push(Span::styled(format!("{} ", volume), theme.dim()));
+                    }
+                    spans.extend([
+                        Span::styled(status_icon, status_style),
+                        Span::styled(format!(" {}", statut_livre(livre)), status_style),
                     ]);
+                    let line = Line::from(spans);
 
                     let style = if i == self.state.selected {
-                        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+                        theme.selected()
                     } else {
-                        Style::default()
+                        theme.text()
                     };
 
                     ListItem::new(line).style(style)
@@ -164,86 +555,492 @@ impl<'a> Widget for ListBooksScreen<'a> {
                 .collect()
         };
 
-        // Appliquer le scroll en skippant les premiers items
-        let visible_items: Vec<ListItem> = items
-            .into_iter()
-            .skip(self.state.scroll_offset)
-            .collect();
+        List::new(items).block(block).render(area, buf);
+    }
+}
 
-        let list = List::new(visible_items).block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("📖 Liste des livres")
-                .border_style(Style::default().fg(Color::Blue)),
-        );
+// Série et tome d'un livre, ex: "Fondation, tome 2" (`cle` avec {serie} et {numero})
+pub fn libelle_tome(livre: &Livre, biblio: &Bibliotheque, cle: &'static str) -> Option<String> {
+    let tome = livre.tome?;
+    let serie = biblio.trouver_serie(tome.serie_id)?;
+    Some(tf(cle, &[("serie", &serie.nom), ("numero", &tome.numero)]))
+}
+
+// Cote et emplacement, pour les livres qui en ont
+fn lignes_emplacement(livre: &Livre, biblio: &Bibliotheque, theme: &Theme) -> Vec<Line<'static>> {
+    let label = theme.key();
+    let mut lignes = Vec::new();
+    if let Some(cote) = &livre.cote {
+        lignes.push(Line::from(vec![Span::styled(t("book.details.call_number"), label), Span::raw(cote.clone())]));
+    }
+    if let Some(emplacement) = livre.emplacement {
+        let rayon = biblio
+            .trouver_rayon(emplacement.rayon_id)
+            .map_or(format!("#{}", emplacement.rayon_id), libelle_rayon);
+        let texte = tf("book.details.location_value", &[("rayon", &rayon), ("position", &emplacement.position)]);
+        lignes.push(Line::from(vec![Span::styled(t("book.details.location"), label), Span::raw(texte)]));
+    }
+    lignes
+}
+
+fn statut_livre(livre: &Livre) -> &'static str {
+    if livre.emprunte {
+        t("book.status.borrowed")
+    } else {
+        t("book.status.available")
+    }
+}
+
+// Panneau de détails du livre sélectionné (terminaux larges)
+fn render_details(livre: Option<&Livre>, biblio: &Bibliotheque, area: Rect, buf: &mut Buffer, theme: &Theme) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("📋 {}", t("book.details.title")))
+        .border_style(theme.border());
+
+    let label = theme.key();
+    let lines = match livre {
+        None => vec![Line::from(Span::styled(
+            t("book.details.none"),
+            theme.dim(),
+        ))],
+        Some(livre) => {
+            let auteur = match biblio.get_auteurs().iter().find(|a| a.id == livre.auteur_id) {
+                Some(a) => format!("{} {} (#{})", a.prenom, a.nom, a.id),
+                None => tf("book.details.unknown_author", &[("id", &livre.auteur_id)]),
+            };
+
+            let mut lignes = vec![
+                Line::from(Span::styled(livre.titre.clone(), theme.title())),
+                Line::from(""),
+                Line::from(vec![Span::styled(t("book.details.id"), label), Span::raw(livre.id.to_string())]),
+                Line::from(vec![Span::styled(t("book.details.author"), label), Span::raw(auteur)]),
+                Line::from(vec![Span::styled(t("book.details.year"), label), Span::raw(livre.annee.to_string())]),
+            ];
+            if let Some(volume) = libelle_tome(livre, biblio, "book.details.volume") {
+                lignes.push(Line::from(vec![Span::styled(t("book.details.series"), label), Span::raw(volume)]));
+            }
+            lignes.extend(lignes_emplacement(livre, biblio, theme));
+            lignes.push(Line::from(vec![
+                Span::styled(t("book.details.status"), label),
+                Span::styled(statut_livre(livre), theme.livre(livre.emprunte)),
+            ]));
+            lignes
+        }
+    };
 
-        list.render(chunks[0], buf);
+    Paragraph::new(lines)
+        .style(theme.text())
+        .wrap(Wrap { trim: true })
+        .block(block)
+        .render(area, buf);
+}
 
-        // Aide
-        let help = Paragraph::new(Line::from(vec![
-            Span::styled("↑/↓", Style::default().fg(Color::Yellow)),
-            Span::raw(":Navigate "),
-            Span::styled("PgUp/PgDn", Style::default().fg(Color::Yellow)),
-            Span::raw(":Scroll "),
-            Span::styled("Esc", Style::default().fg(Color::Yellow)),
-            Span::raw(":Retour"),
-        ]))
-        .alignment(Alignment::Center);
+impl Screen for ListBooksScreen {
+    fn title(&self) -> String {
+        format!("📖 {}", t("book.list.title"))
+    }
 
-        help.render(chunks[1], buf);
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        vec![
+            (&[Action::Up, Action::Down], t("help.navigate")),
+            (&[Action::Select], t("help.details")),
+            (&[Action::Mark], t("help.mark")),
+            (&[Action::Borrow, Action::Return], t("help.borrow_return")),
+            (&[Action::Filter], t("help.filter")),
+            (&[Action::Back], t("help.back")),
+            (&[Action::Help], t("help.help")),
+        ]
+    }
+
+    fn handle_key(&mut self, _key: KeyEvent, action: Option<Action>, ctx: &mut Context) -> Navigation {
+        if action == Some(Action::Back) {
+            return Navigation::Pop;
+        }
+
+        let mut b = ctx.biblio.lock().unwrap();
+        // Le filtre se choisit même quand il ne laisse aucun livre
+        if action == Some(Action::Filter) {
+            return Navigation::push(LocationPickerScreen::filtre_liste(&b));
+        }
+        let ids: Vec<u32> = self.livres(&b).iter().map(|l| l.id).collect();
+        let count = ids.len();
+
+        if count == 0 {
+            return Navigation::None;
+        }
+        self.state.selected = self.state.selected.min(count - 1);
+        let livre_id = ids[self.state.selected];
+
+        match action {
+            Some(Action::Select) => return Navigation::push(BookDetailScreen::new(livre_id)),
+            Some(Action::Borrow) => {
+                let titre = t("book.batch.borrow_title");
+                return self.operation_groupee(&mut b, titre, Bibliotheque::emprunter_livre, apres_emprunt);
+            }
+            Some(Action::Return) => {
+                let titre = t("book.batch.return_title");
+                return self.operation_groupee(&mut b, titre, Bibliotheque::retourner_livre, apres_retour);
+            }
+            // Marque ou démarque puis passe au suivant, pour enchaîner les Espace
+            Some(Action::Mark) => {
+                if !self.marques.remove(&livre_id) {
+                    self.marques.insert(livre_id);
+                }
+                self.state.move_by(1, count);
+            }
+            _ => {}
+        }
+
+        // Une page = le nombre de lignes réellement visibles
+        let page = self.visible_lines().max(1) as isize;
+
+        match action {
+            Some(Action::Down) => {
+                self.state.selected = (self.state.selected + 1) % count;
+            }
+            Some(Action::Up) => {
+                if self.state.selected == 0 {
+                    self.state.selected = count - 1;
+                } else {
+                    self.state.selected -= 1;
+                }
+            }
+            Some(Action::PageDown) => self.state.move_by(page, count),
+            Some(Action::PageUp) => self.state.move_by(-page, count),
+            _ => {}
+        }
+
+        self.state.scroll_into_view(self.visible_lines());
+        Navigation::None
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, ctx: &mut Context) -> Navigation {
+        let count = self.livres(&ctx.biblio.lock().unwrap()).len();
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let clicked = self.state.item_at(self.list_area, mouse.column, mouse.row);
+                if let Some(index) = clicked.filter(|&index| index < count) {
+                    self.state.selected = index;
+                }
+            }
+            MouseEventKind::ScrollDown => self.state.move_by(1, count),
+            MouseEventKind::ScrollUp => self.state.move_by(-1, count),
+            _ => {}
+        }
+
+        self.state.scroll_into_view(self.visible_lines());
+        Navigation::None
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(area);
+
+        let b = ctx.biblio.lock().unwrap();
+        // Terminal large: liste à gauche, détails du livre sélectionné à droite
+        if area.width >= WIDE_LAYOUT_MIN_WIDTH {
+            let panes = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .split(chunks[0]);
+
+            self.render_list(&b, panes[0], buf, ctx.theme);
+            let selection = self.livres(&b).get(self.state.selected).copied();
+            render_details(selection, &b, panes[1], buf, ctx.theme);
+        } else {
+            self.render_list(&b, chunks[0], buf, ctx.theme);
+        }
+
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        HelpBar::new(&hints, ctx.theme).render(chunks[1], buf);
     }
 }
 
-// Dialog d'emprunt de livre
-pub struct BorrowBookScreen {
-    form: Form,
+// Fiche d'un livre: métadonnées, emprunt en cours, historique et opérations
+// Relue dans la bibliothèque à chaque rendu, elle reflète les modifications faites depuis
+pub struct BookDetailScreen {
+    livre_id: u32,
+    // Défilement quand l'historique dépasse la hauteur de l'écran
+    scroll: u16,
 }
 
-impl BorrowBookScreen {
-    pub fn new(state: &FormState) -> Self {
-        let labels = vec!["ID du livre à emprunter".to_string()];
-        let form = Form::with_values(labels, state.fields.clone());
-        BorrowBookScreen { form }
+impl BookDetailScreen {
+    pub fn new(livre_id: u32) -> Self {
+        BookDetailScreen { livre_id, scroll: 0 }
+    }
+
+    fn lignes(livre: &Livre, biblio: &Bibliotheque, theme: &Theme) -> Vec<Line<'static>> {
+        let label = theme.key();
+        let auteur = match biblio.get_auteurs().iter().find(|a| a.id == livre.auteur_id) {
+            Some(a) => format!("{} {} (#{})", a.prenom, a.nom, a.id),
+            None => tf("book.details.unknown_author", &[("id", &livre.auteur_id)]),
+        };
+
+        let mut lignes = vec![
+            Line::from(Span::styled(livre.titre.clone(), theme.title())),
+            Line::from(""),
+            Line::from(vec![Span::styled(t("book.details.id"), label), Span::raw(livre.id.to_string())]),
+            Line::from(vec![Span::styled(t("book.details.author"), label), Span::raw(auteur)]),
+            Line::from(vec![Span::styled(t("book.details.year"), label), Span::raw(livre.annee.to_string())]),
+        ];
+        if let Some(isbn) = &livre.isbn {
+            lignes.push(Line::from(vec![Span::styled(t("book.details.isbn"), label), Span::raw(isbn.clone())]));
+        }
+        if let Some(volume) = libelle_tome(livre, biblio, "book.details.volume") {
+            lignes.push(Line::from(vec![Span::styled(t("book.details.series"), label), Span::raw(volume)]));
+        }
+        lignes.extend(lignes_emplacement(livre, biblio, theme));
+        lignes.push(Line::from(vec![
+            Span::styled(t("book.details.status"), label),
+            Span::styled(statut_livre(livre), theme.livre(livre.emprunte)),
+        ]));
+
+        if let Some(emprunt) = livre.emprunt_en_cours() {
+            let mut echeance = vec![
+                Span::styled(t("book.detail.due"), label),
+                Span::raw(i18n::date(emprunt.echeance)),
+            ];
+            if emprunt.en_retard(Local::now().date_naive()) {
+                echeance.push(Span::styled(t("book.detail.overdue"), theme.status(true)));
+            }
+            lignes.push(Line::from(vec![
+                Span::styled(t("book.detail.borrowed_on"), label),
+                Span::raw(i18n::date(emprunt.debut)),
+            ]));
+            lignes.push(Line::from(echeance));
+        }
+
+        // Les tomes de la série dans l'ordre, celui-ci repéré par le sélecteur
+        if let Some(tome) = livre.tome {
+            lignes.push(Line::from(""));
+            lignes.push(Line::from(Span::styled(t("book.detail.volumes"), theme.title())));
+            for volume in biblio.volumes(tome.serie_id) {
+                let repere = if volume.id == livre.id { symbols::SELECTOR } else { " " };
+                let numero = volume.tome.map_or(0, |tome| tome.numero);
+                let texte = tf(
+                    "book.detail.volume_line",
+                    &[("numero", &numero), ("id", &volume.id), ("titre", &volume.titre)],
+                );
+                lignes.push(Line::from(vec![
+                    Span::raw(format!("{} {} ", repere, texte)),
+                    Span::styled(statut_livre(volume), theme.livre(volume.emprunte)),
+                ]));
+            }
+        }
+
+        lignes.push(Line::from(""));
+        lignes.push(Line::from(Span::styled(t("book.detail.history"), theme.title())));
+        if livre.historique.is_empty() {
+            lignes.push(Line::from(Span::styled(t("book.detail.no_history"), theme.dim())));
+        }
+        // Le plus récent en haut
+        for emprunt in livre.historique.iter().rev() {
+            let texte = match emprunt.retour {
+                Some(retour) => tf(
+                    "book.detail.loan_returned",
+                    &[("debut", &i18n::date(emprunt.debut)), ("retour", &i18n::date(retour))],
+                ),
+                None => tf(
+                    "book.detail.loan_current",
+                    &[("debut", &i18n::date(emprunt.debut)), ("echeance", &i18n::date(emprunt.echeance))],
+                ),
+            };
+            let texte = match &emprunt.operateur {
+                Some(operateur) => tf("book.detail.loan_by", &[("emprunt", &texte), ("operateur", operateur)]),
+                None => texte,
+            };
+            lignes.push(Line::from(format!("  {}", texte)));
+        }
+        lignes
+    }
+
+    // Emprunt / retour depuis la fiche: le résultat s'affiche par-dessus, la fiche reste ouverte
+    fn operation(
+        &self,
+        ctx: &mut Context,
+        operation: fn(&mut Bibliotheque, u32) -> Result<(), ErreurBibliotheque>,
+        succes: fn(&Bibliotheque, u32) -> Box<dyn Screen>,
+    ) -> Navigation {
+        let mut b = ctx.biblio.lock().unwrap();
+        match operation(&mut b, self.livre_id) {
+            Ok(()) => Navigation::Push(succes(&b, self.livre_id)),
+            Err(e) => Navigation::error(tf("common.error_detail", &[("erreur", &e)])),
+        }
+    }
+
+    fn confirmer_suppression(&self, livre: &Livre) -> Navigation {
+        let id = self.livre_id;
+        let titre = livre.titre.clone();
+        let confirmation = ConfirmScreen::new(
+            t("book.delete.title"),
+            tf("book.delete.confirm", &[("titre", &titre)]),
+            move |ctx| {
+                let resultat = ctx.biblio.lock().unwrap().supprimer_livre(id);
+                // Le dialog se ferme; la fiche d'un livre supprimé laisse place au message
+                match resultat {
+                    Ok(_) => Navigation::Sequence(vec![
+                        Navigation::Pop,
+                        Navigation::replace(MessageScreen::new(
+                            t("common.success"),
+                            tf("book.deleted", &[("titre", &titre)]),
+                            false,
+                        )),
+                    ]),
+                    Err(e) => Navigation::Sequence(vec![
+                        Navigation::Pop,
+                        Navigation::error(tf("common.error_detail", &[("erreur", &e)])),
+                    ]),
+                }
+            },
+        );
+        Navigation::push(confirmation)
+    }
+}
+
+impl Screen for BookDetailScreen {
+    fn title(&self) -> String {
+        format!("📋 {}", t("book.detail.title"))
     }
 
-    pub fn handle_key_event(&mut self, key: KeyEvent) -> Option<BookAction> {
-        if key.code == KeyCode::Enter {
-            let values = self.form.values();
-            if !values[0].trim().is_empty() {
-                return Some(BookAction::SubmitBorrow(values[0].clone()));
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        vec![
+            (&[Action::Borrow], t("help.borrow")),
+            (&[Action::Return], t("help.return")),
+            (&[Action::Edit], t("help.edit")),
+            (&[Action::Delete], t("help.delete")),
+            (&[Action::Back], t("help.back")),
+            (&[Action::Help], t("help.help")),
+        ]
+    }
+
+    fn handle_key(&mut self, _key: KeyEvent, action: Option<Action>, ctx: &mut Context) -> Navigation {
+        match action {
+            Some(Action::Back) => Navigation::Pop,
+            Some(Action::Down) => {
+                self.scroll = self.scroll.saturating_add(1);
+                Navigation::None
+            }
+            Some(Action::Up) => {
+                self.scroll = self.scroll.saturating_sub(1);
+                Navigation::None
+            }
+            Some(Action::Borrow) => self.operation(ctx, Bibliotheque::emprunter_livre, apres_emprunt),
+            Some(Action::Return) => self.operation(ctx, Bibliotheque::retourner_livre, apres_retour),
+            Some(Action::Edit | Action::Delete) => {
+                let b = ctx.biblio.lock().unwrap();
+                match b.get_livres().iter().find(|l| l.id == self.livre_id) {
+                    Some(livre) if action == Some(Action::Edit) => Navigation::push(AddBookScreen::edit(&b, livre)),
+                    Some(livre) => self.confirmer_suppression(livre),
+                    None => Navigation::None,
+                }
             }
+            _ => Navigation::None,
         }
+    }
 
-        self.form.handle_key_event(key);
-        None
+    fn handle_mouse(&mut self, mouse: MouseEvent, _ctx: &mut Context) -> Navigation {
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.scroll = self.scroll.saturating_add(1),
+            MouseEventKind::ScrollUp => self.scroll = self.scroll.saturating_sub(1),
+            _ => {}
+        }
+        Navigation::None
     }
 
-    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let theme = ctx.theme;
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Min(8), Constraint::Length(1)])
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
             .split(area);
 
         let block = Block::default()
             .borders(Borders::ALL)
-            .title("✋ Emprunter un livre")
-            .border_style(Style::default().fg(Color::Cyan));
+            .title(self.title())
+            .border_style(theme.border());
 
-        let inner = block.inner(chunks[0]);
-        block.render(chunks[0], buf);
+        let b = ctx.biblio.lock().unwrap();
+        let lignes = match b.get_livres().iter().find(|l| l.id == self.livre_id) {
+            Some(livre) => Self::lignes(livre, &b, theme),
+            None => vec![Line::from(Span::styled(t("book.detail.not_found"), theme.dim()))],
+        };
 
-        self.form.render(inner, buf);
+        // Pas de défilement au-delà de la dernière ligne
+        let visibles = block.inner(chunks[0]).height;
+        let max_scroll = (lignes.len() as u16).saturating_sub(visibles);
+        self.scroll = self.scroll.min(max_scroll);
 
-        // Aide
-        let help = Paragraph::new(Line::from(vec![
-            Span::styled("Enter", Style::default().fg(Color::Yellow)),
-            Span::raw(":Emprunter "),
-            Span::styled("Esc", Style::default().fg(Color::Yellow)),
-            Span::raw(":Annuler"),
-        ]))
-        .alignment(Alignment::Center);
+        Paragraph::new(lignes)
+            .style(theme.text())
+            .scroll((self.scroll, 0))
+            .block(block)
+            .render(chunks[0], buf);
 
-        help.render(chunks[1], buf);
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        HelpBar::new(&hints, theme).render(chunks[1], buf);
+    }
+}
+
+// Dialog d'emprunt de livre
+pub struct BorrowBookScreen {
+    form: Form,
+}
+
+impl BorrowBookScreen {
+    pub fn new() -> Self {
+        let fields = vec![Field::integer("id", t("book.borrow.field"), 1, u32::MAX.into())];
+        BorrowBookScreen {
+            form: Form::new(fields),
+        }
+    }
+}
+
+impl Screen for BorrowBookScreen {
+    fn title(&self) -> String {
+        format!("✋ {}", t("book.borrow.title"))
+    }
+
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        vec![
+            (&[Action::Submit], t("help.borrow")),
+            (&[Action::Back], t("help.cancel")),
+            (&[Action::Help], t("help.help")),
+        ]
+    }
+
+    fn key_context(&self) -> KeyContext {
+        KeyContext::Form
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, action: Option<Action>, ctx: &mut Context) -> Navigation {
+        match action {
+            Some(Action::Back) => Navigation::Pop,
+            _ => match self.form.handle_key_event(key, action) {
+                Some(values) => {
+                    operation_livre(values.integer("id") as u32, ctx, Bibliotheque::emprunter_livre, apres_emprunt)
+                }
+                None => Navigation::None,
+            },
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, _ctx: &mut Context) -> Navigation {
+        self.form.handle_mouse_event(mouse);
+        Navigation::None
+    }
+
+    fn handle_paste(&mut self, text: &str, _ctx: &mut Context) -> Navigation {
+        self.form.paste(text);
+        Navigation::None
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        self.form.render_framed(&self.title(), &hints, area, buf, ctx.theme);
     }
 }
 
@@ -253,55 +1050,94 @@ pub struct ReturnBookScreen {
 }
 
 impl ReturnBookScreen {
-    pub fn new(state: &FormState) -> Self {
-        let labels = vec!["ID du livre à retourner".to_string()];
-        let form = Form::with_values(labels, state.fields.clone());
-        ReturnBookScreen { form }
+    pub fn new() -> Self {
+        let fields = vec![Field::integer("id", t("book.return.field"), 1, u32::MAX.into())];
+        ReturnBookScreen {
+            form: Form::new(fields),
+        }
     }
+}
 
-    pub fn handle_key_event(&mut self, key: KeyEvent) -> Option<BookAction> {
-        if key.code == KeyCode::Enter {
-            let values = self.form.values();
-            if !values[0].trim().is_empty() {
-                return Some(BookAction::SubmitReturn(values[0].clone()));
-            }
-        }
+impl Screen for ReturnBookScreen {
+    fn title(&self) -> String {
+        format!("📥 {}", t("book.return.title"))
+    }
 
-        self.form.handle_key_event(key);
-        None
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        vec![
+            (&[Action::Submit], t("help.return")),
+            (&[Action::Back], t("help.cancel")),
+            (&[Action::Help], t("help.help")),
+        ]
     }
 
-    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Min(8), Constraint::Length(1)])
-            .split(area);
+    fn key_context(&self) -> KeyContext {
+        KeyContext::Form
+    }
 
-        let block = Block::default()
-            .borders(Borders::ALL)
-            .title("📥 Retourner un livre")
-            .border_style(Style::default().fg(Color::Cyan));
+    fn handle_key(&mut self, key: KeyEvent, action: Option<Action>, ctx: &mut Context) -> Navigation {
+        match action {
+            Some(Action::Back) => Navigation::Pop,
+            _ => match self.form.handle_key_event(key, action) {
+                Some(values) => {
+                    operation_livre(values.integer("id") as u32, ctx, Bibliotheque::retourner_livre, apres_retour)
+                }
+                None => Navigation::None,
+            },
+        }
+    }
 
-        let inner = block.inner(chunks[0]);
-        block.render(chunks[0], buf);
+    fn handle_mouse(&mut self, mouse: MouseEvent, _ctx: &mut Context) -> Navigation {
+        self.form.handle_mouse_event(mouse);
+        Navigation::None
+    }
 
-        self.form.render(inner, buf);
+    fn handle_paste(&mut self, text: &str, _ctx: &mut Context) -> Navigation {
+        self.form.paste(text);
+        Navigation::None
+    }
 
-        // Aide
-        let help = Paragraph::new(Line::from(vec![
-            Span::styled("Enter", Style::default().fg(Color::Yellow)),
-            Span::raw(":Retourner "),
-            Span::styled("Esc", Style::default().fg(Color::Yellow)),
-            Span::raw(":Annuler"),
-        ]))
-        .alignment(Alignment::Center);
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        self.form.render_framed(&self.title(), &hints, area, buf, ctx.theme);
+    }
+}
 
-        help.render(chunks[1], buf);
+// Succès: l'écran courant est remplacé par l'écran de succès; erreur: il reste ouvert dessous
+pub fn operation_livre(
+    id: u32,
+    ctx: &mut Context,
+    operation: fn(&mut Bibliotheque, u32) -> Result<(), ErreurBibliotheque>,
+    succes: fn(&Bibliotheque, u32) -> Box<dyn Screen>,
+) -> Navigation {
+    let mut b = ctx.biblio.lock().unwrap();
+    match operation(&mut b, id) {
+        Ok(()) => Navigation::Replace(succes(&b, id)),
+        Err(e) => Navigation::error(tf("common.error_detail", &[("erreur", &e)])),
     }
 }
 
-pub enum BookAction {
-    SubmitAdd(Vec<String>),
-    SubmitBorrow(String),
-    SubmitReturn(String),
+// Écrans de succès des emprunts et retours (formulaires, liste, fiche, palette)
+pub fn apres_emprunt(_biblio: &Bibliotheque, _livre_id: u32) -> Box<dyn Screen> {
+    Box::new(MessageScreen::new(t("common.success"), t("book.borrowed"), false))
+}
+
+// Retour d'un tome: on propose d'emprunter le tome suivant de la série s'il est disponible
+pub fn apres_retour(biblio: &Bibliotheque, livre_id: u32) -> Box<dyn Screen> {
+    let Some(suivant) = biblio.tome_suivant(livre_id) else {
+        return Box::new(MessageScreen::new(t("common.success"), t("book.returned"), false));
+    };
+
+    let (id, titre) = (suivant.id, suivant.titre.clone());
+    let numero = suivant.tome.map_or(0, |tome| tome.numero);
+    let question = tf("book.next_volume.confirm", &[("numero", &numero), ("titre", &titre), ("id", &id)]);
+    Box::new(ConfirmScreen::new(t("book.next_volume.title"), question, move |ctx| {
+        let resultat = ctx.biblio.lock().unwrap().emprunter_livre(id);
+        let (message, erreur) = match resultat {
+            Ok(()) => (tf("book.next_volume.borrowed", &[("numero", &numero), ("titre", &titre)]), false),
+            Err(e) => (tf("common.error_detail", &[("erreur", &e)]), true),
+        };
+        let titre_message = if erreur { t("common.error") } else { t("common.success") };
+        Navigation::replace(MessageScreen::new(titre_message, message, erreur))
+    }))
 }
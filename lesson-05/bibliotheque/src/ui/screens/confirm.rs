@@ -0,0 +1,129 @@
+use super::render_dialog_frame;
+use crate::app::keymap::{Action, KeyContext};
+use crate::app::screen::{Context, Navigation, Screen};
+use crate::i18n::t;
+use crate::ui::widgets::HelpBar;
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Direction, Layout, Position, Rect},
+    style::Modifier,
+    widgets::{Paragraph, Widget, Wrap},
+};
+
+// Ce qui est fait si l'utilisateur confirme; le dialog est encore au sommet de la pile
+type OnConfirm = Box<dyn FnOnce(&mut Context) -> Navigation>;
+
+// Dialog Oui / Non avant une opération irréversible (ex: supprimer un livre)
+pub struct ConfirmScreen {
+    title: String,
+    message: String,
+    on_confirm: Option<OnConfirm>,
+    // Position des boutons lors du dernier rendu (pour la souris)
+    yes_button: Rect,
+    no_button: Rect,
+}
+
+impl ConfirmScreen {
+    pub fn new(
+        title: &str,
+        message: impl Into<String>,
+        on_confirm: impl FnOnce(&mut Context) -> Navigation + 'static,
+    ) -> Self {
+        ConfirmScreen {
+            title: title.to_string(),
+            message: message.into(),
+            on_confirm: Some(Box::new(on_confirm)),
+            yes_button: Rect::default(),
+            no_button: Rect::default(),
+        }
+    }
+
+    fn confirm(&mut self, ctx: &mut Context) -> Navigation {
+        match self.on_confirm.take() {
+            Some(on_confirm) => on_confirm(ctx),
+            None => Navigation::Pop,
+        }
+    }
+}
+
+impl Screen for ConfirmScreen {
+    fn title(&self) -> String {
+        self.title.clone()
+    }
+
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        vec![
+            (&[Action::Select], t("help.confirm")),
+            (&[Action::Back], t("help.cancel")),
+        ]
+    }
+
+    fn key_context(&self) -> KeyContext {
+        KeyContext::Dialog
+    }
+
+    fn handle_key(&mut self, _key: KeyEvent, action: Option<Action>, ctx: &mut Context) -> Navigation {
+        match action {
+            Some(Action::Select) => self.confirm(ctx),
+            Some(Action::Back) => Navigation::Pop,
+            _ => Navigation::None,
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, ctx: &mut Context) -> Navigation {
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+            return Navigation::None;
+        }
+        let position = Position::new(mouse.column, mouse.row);
+        if self.yes_button.contains(position) {
+            self.confirm(ctx)
+        } else if self.no_button.contains(position) {
+            Navigation::Pop
+        } else {
+            Navigation::None
+        }
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let theme = ctx.theme;
+
+        let status_style = theme.status(true);
+        let title = format!("❓ {}", self.title);
+        let inner = render_dialog_frame(area, buf, theme, (50, 8), &title, status_style);
+
+        // Layout interne: message + boutons + aide
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        Paragraph::new(self.message.as_str())
+            .wrap(Wrap { trim: true })
+            .alignment(Alignment::Center)
+            .style(theme.text())
+            .render(chunks[0], buf);
+
+        // Boutons côte à côte, centrés: "[ Oui ]   [ Non ]"
+        let yes = format!("[ {} ]", t("common.yes"));
+        let no = format!("[ {} ]", t("common.no"));
+        let yes_width = yes.chars().count() as u16;
+        let no_width = no.chars().count() as u16;
+        let gap = 3;
+        let x = chunks[1].x + chunks[1].width.saturating_sub(yes_width + gap + no_width) / 2;
+
+        self.yes_button = Rect::new(x, chunks[1].y, yes_width, 1).intersection(chunks[1]);
+        self.no_button = Rect::new(x + yes_width + gap, chunks[1].y, no_width, 1).intersection(chunks[1]);
+        Paragraph::new(yes)
+            .style(status_style.add_modifier(Modifier::BOLD))
+            .render(self.yes_button, buf);
+        Paragraph::new(no).style(theme.text()).render(self.no_button, buf);
+
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        HelpBar::new(&hints, theme).render(chunks[2], buf);
+    }
+}
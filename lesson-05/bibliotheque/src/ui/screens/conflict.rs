@@ -0,0 +1,177 @@
+use super::{MessageScreen, render_dialog_frame};
+use crate::app::keymap::{Action, KeyContext};
+use crate::app::screen::{Context, Navigation, Screen};
+use crate::app::state::ListState;
+use crate::i18n::{t, tf, tn};
+use crate::ui::widgets::HelpBar;
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::Line,
+    widgets::{List, ListItem, Paragraph, Widget, Wrap},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Choix {
+    Recharger,
+    Fusionner,
+    Ecraser,
+}
+
+impl Choix {
+    const TOUS: [Choix; 3] = [Choix::Recharger, Choix::Fusionner, Choix::Ecraser];
+
+    fn libelle(self) -> &'static str {
+        match self {
+            Choix::Recharger => t("conflict.reload"),
+            Choix::Fusionner => t("conflict.merge"),
+            Choix::Ecraser => t("conflict.overwrite"),
+        }
+    }
+}
+
+// Le fichier a été enregistré par un autre processus alors qu'on a des modifications:
+// recharger sa version, fusionner les deux ou écraser avec la nôtre (Échap: décider plus tard)
+pub struct ConflitScreen {
+    fichier: String,
+    revision: u64,
+    // Sans modifications locales: pourquoi le rechargement d'office a échoué
+    erreur_rechargement: Option<String>,
+    state: ListState,
+    // Zone des choix lors du dernier rendu (pour la souris)
+    list_area: Rect,
+}
+
+impl ConflitScreen {
+    pub fn new(fichier: &str, revision: u64) -> Self {
+        ConflitScreen {
+            fichier: fichier.to_string(),
+            revision,
+            erreur_rechargement: None,
+            state: ListState::default(),
+            list_area: Rect::default(),
+        }
+    }
+
+    pub fn with_erreur_rechargement(mut self, erreur: String) -> Self {
+        self.erreur_rechargement = Some(erreur);
+        self
+    }
+
+    fn executer(&self, choix: Choix, ctx: &mut Context) -> Navigation {
+        let mut b = ctx.biblio.lock().unwrap();
+        let resultat = match choix {
+            Choix::Recharger => b
+                .recharger(&self.fichier)
+                .map(|()| tf("conflict.reloaded", &[("fichier", &self.fichier)]))
+                .map_err(|e| e.to_string()),
+            Choix::Fusionner => b
+                .fusionner_fichier(&self.fichier)
+                .map(|conflits| {
+                    let mut texte = tf("conflict.merged", &[("fichier", &self.fichier)]);
+                    if conflits > 0 {
+                        texte.push('\n');
+                        texte.push_str(&tn("conflict.kept", conflits));
+                    }
+                    texte
+                })
+                .map_err(|e| e.to_string()),
+            Choix::Ecraser => b
+                .ecraser(&self.fichier)
+                .map(|()| tf("conflict.overwritten", &[("fichier", &self.fichier)]))
+                .map_err(|e| e.to_string()),
+        };
+        match resultat {
+            Ok(texte) => Navigation::replace(MessageScreen::new(t("common.success"), texte, false)),
+            Err(erreur) => Navigation::error(tf("common.error_detail", &[("erreur", &erreur)])),
+        }
+    }
+}
+
+impl Screen for ConflitScreen {
+    fn title(&self) -> String {
+        format!("⚠️  {}", t("conflict.title"))
+    }
+
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        vec![
+            (&[Action::Up, Action::Down], t("help.navigate")),
+            (&[Action::Select], t("help.select")),
+            (&[Action::Back], t("help.later")),
+        ]
+    }
+
+    fn key_context(&self) -> KeyContext {
+        KeyContext::List
+    }
+
+    fn handle_key(&mut self, _key: KeyEvent, action: Option<Action>, ctx: &mut Context) -> Navigation {
+        match action {
+            Some(Action::Up) => {
+                self.state.move_by(-1, Choix::TOUS.len());
+                Navigation::None
+            }
+            Some(Action::Down) => {
+                self.state.move_by(1, Choix::TOUS.len());
+                Navigation::None
+            }
+            Some(Action::Select) => self.executer(Choix::TOUS[self.state.selected], ctx),
+            Some(Action::Back) => Navigation::Pop,
+            _ => Navigation::None,
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, ctx: &mut Context) -> Navigation {
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+            return Navigation::None;
+        }
+        match self.state.item_at(self.list_area, mouse.column, mouse.row) {
+            Some(index) if index < Choix::TOUS.len() => self.executer(Choix::TOUS[index], ctx),
+            _ => Navigation::None,
+        }
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let theme = ctx.theme;
+        let inner = render_dialog_frame(area, buf, theme, (64, 10), &self.title(), theme.status(true));
+
+        // Message, choix, aide
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),
+                Constraint::Length(Choix::TOUS.len() as u16),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let message = match &self.erreur_rechargement {
+            Some(erreur) => tf(
+                "conflict.reload_failed",
+                &[("fichier", &self.fichier), ("revision", &self.revision), ("erreur", erreur)],
+            ),
+            None => tf("conflict.message", &[("fichier", &self.fichier), ("revision", &self.revision)]),
+        };
+        Paragraph::new(message)
+            .wrap(Wrap { trim: true })
+            .alignment(Alignment::Center)
+            .style(theme.text())
+            .render(chunks[0], buf);
+
+        self.list_area = chunks[1];
+        let items: Vec<ListItem> = Choix::TOUS
+            .iter()
+            .enumerate()
+            .map(|(i, choix)| {
+                let style = if i == self.state.selected { theme.selected() } else { theme.text() };
+                ListItem::new(Line::from(format!(" {} ", choix.libelle()))).style(style)
+            })
+            .collect();
+        List::new(items).render(chunks[1], buf);
+
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        HelpBar::new(&hints, theme).render(chunks[3], buf);
+    }
+}
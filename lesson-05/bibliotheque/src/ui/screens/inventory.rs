@@ -0,0 +1,421 @@
+use super::{ConfirmScreen, MessageScreen};
+use crate::app::keymap::{Action, KeyContext};
+use crate::app::screen::{Context, Navigation, Screen};
+use crate::i18n::{t, tf, tn};
+use crate::models::RapportInventaire;
+use crate::services::{Bibliotheque, ErreurBibliotheque};
+use crate::ui::fuzzy;
+use crate::ui::theme::{Theme, symbols};
+use crate::ui::widgets::HelpBar;
+use crate::ui::widgets::text_input::TextInput;
+use crossterm::event::{KeyEvent, MouseEvent, MouseEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, Borders, Gauge, List, ListItem, Paragraph, Widget},
+};
+
+// Livres proposés au maximum après une recherche par titre
+const MAX_CANDIDATS: usize = 8;
+
+// Pointage des livres en rayon: un scan, un ID ou un début de titre par saisie
+// La session vit dans la bibliothèque: quitter l'écran ne la ferme pas
+pub struct InventoryScreen {
+    input: TextInput,
+    // Résultat de chaque pointage, du plus ancien au plus récent (erreur, texte)
+    journal: Vec<(bool, String)>,
+    // Livres entre lesquels choisir (recherche par titre, exemplaires d'un même ISBN)
+    candidats: Vec<u32>,
+    choix: usize,
+}
+
+impl InventoryScreen {
+    // Démarre une session, ou reprend celle en cours
+    pub fn ouvrir(biblio: &mut Bibliotheque) -> Result<Self, ErreurBibliotheque> {
+        biblio.commencer_inventaire()?;
+        let mut input = TextInput::new(t("inventory.field").to_string());
+        input.focused = true;
+        Ok(InventoryScreen {
+            input,
+            journal: Vec::new(),
+            candidats: Vec::new(),
+            choix: 0,
+        })
+    }
+
+    fn saisir(&mut self, ctx: &mut Context) {
+        let saisie = self.input.value().trim().to_string();
+        self.input.clear();
+        if saisie.is_empty() {
+            return;
+        }
+
+        let mut b = ctx.biblio.lock().unwrap();
+        let trouves: Vec<u32> = b.chercher_code(&saisie).iter().map(|l| l.id).collect();
+        match trouves[..] {
+            [id] => self.pointer(&mut b, id),
+            // Exemplaires d'une même édition: celui qui est en main
+            [_, _, ..] => self.candidats = trouves,
+            // Un code-barres qui ne correspond à rien est noté; un texte est un titre cherché
+            [] if est_un_code(&saisie) => {
+                let noter = b.pointer_inconnu(&saisie);
+                self.noter(noter.map(|_| tf("inventory.unknown", &[("code", &saisie)])), true);
+            }
+            [] => {
+                self.candidats = chercher_titre(&b, &saisie);
+                if self.candidats.is_empty() {
+                    self.journal.push((true, tf("inventory.no_match", &[("recherche", &saisie)])));
+                }
+            }
+        }
+        self.choix = 0;
+    }
+
+    fn pointer(&mut self, b: &mut Bibliotheque, id: u32) {
+        self.candidats.clear();
+        let Some((titre, emprunte)) = b.get_livres().iter().find(|l| l.id == id).map(|l| (l.titre.clone(), l.emprunte))
+        else {
+            return;
+        };
+        let texte = b.pointer_livre(id).map(|nouveau| match (nouveau, emprunte) {
+            (false, _) => tf("inventory.already_seen", &[("titre", &titre)]),
+            (true, false) => tf("inventory.seen", &[("titre", &titre)]),
+            (true, true) => tf("inventory.seen_borrowed", &[("titre", &titre)]),
+        });
+        self.noter(texte, emprunte);
+    }
+
+    // Un avertissement (livre marqué emprunté, code inconnu) s'affiche comme une erreur
+    fn noter(&mut self, texte: Result<String, ErreurBibliotheque>, avertissement: bool) {
+        self.journal.push(match texte {
+            Ok(texte) => (avertissement, texte),
+            Err(e) => (true, tf("common.error_detail", &[("erreur", &e)])),
+        });
+    }
+
+    fn render_progression(&self, biblio: &Bibliotheque, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        let Some(rapport) = biblio.rapport_inventaire() else {
+            return;
+        };
+        let ratio = if rapport.total == 0 { 1.0 } else { rapport.vus as f64 / rapport.total as f64 };
+        Gauge::default()
+            .block(Block::default().borders(Borders::ALL).border_style(theme.border()))
+            .gauge_style(theme.status(false))
+            .ratio(ratio)
+            .label(tf("inventory.progress", &[("vus", &rapport.vus), ("total", &rapport.total)]))
+            .render(area, buf);
+    }
+
+    fn render_candidats(&self, biblio: &Bibliotheque, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        let items: Vec<ListItem> = self
+            .candidats
+            .iter()
+            .enumerate()
+            .filter_map(|(i, id)| {
+                let livre = biblio.get_livres().iter().find(|l| l.id == *id)?;
+                let style = if i == self.choix { theme.selected() } else { theme.text() };
+                Some(ListItem::new(Line::from(vec![
+                    Span::styled(format!(" {} ({})", livre.titre, livre.annee), style),
+                    Span::styled(format!("  #{}", livre.id), theme.dim()),
+                ])))
+            })
+            .collect();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(t("inventory.choose"))
+            .border_style(theme.border_focused());
+        List::new(items).block(block).render(area, buf);
+    }
+
+    fn render_journal(&self, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(t("inventory.log.title"))
+            .border_style(theme.border());
+
+        // Le plus récent en haut
+        let items: Vec<ListItem> = if self.journal.is_empty() {
+            vec![ListItem::new(Line::from(Span::styled(t("inventory.log.empty"), theme.dim())))]
+        } else {
+            self.journal
+                .iter()
+                .rev()
+                .map(|(erreur, texte)| {
+                    let symbole = if *erreur { symbols::FAILURE } else { symbols::SUCCESS };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!(" {} ", symbole), theme.status(*erreur)),
+                        Span::raw(texte.as_str()),
+                    ]))
+                })
+                .collect()
+        };
+        List::new(items).block(block).style(theme.text()).render(area, buf);
+    }
+}
+
+// ISBN ou numéro d'exemplaire: des chiffres, éventuellement des tirets et un X final
+fn est_un_code(saisie: &str) -> bool {
+    saisie.chars().any(|c| c.is_ascii_digit())
+        && saisie.chars().all(|c| c.is_ascii_digit() || c == '-' || c == 'X' || c == 'x')
+}
+
+fn chercher_titre(biblio: &Bibliotheque, recherche: &str) -> Vec<u32> {
+    let mut scores: Vec<(u32, u32)> = biblio
+        .livres_groupes()
+        .iter()
+        .filter_map(|l| fuzzy::score(recherche, &l.titre).map(|score| (l.id, score)))
+        .collect();
+    scores.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+    scores.into_iter().take(MAX_CANDIDATS).map(|(id, _)| id).collect()
+}
+
+impl Screen for InventoryScreen {
+    fn title(&self) -> String {
+        format!("📋 {}", t("inventory.title"))
+    }
+
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        if !self.candidats.is_empty() {
+            return vec![
+                (&[Action::NextField, Action::PrevField], t("help.navigate")),
+                (&[Action::Submit], t("help.select")),
+                (&[Action::Back], t("help.cancel")),
+            ];
+        }
+        vec![
+            (&[Action::Submit], t("help.mark_seen")),
+            (&[Action::Report], t("help.report")),
+            (&[Action::Back], t("common.close")),
+            (&[Action::Help], t("help.help")),
+        ]
+    }
+
+    fn key_context(&self) -> KeyContext {
+        KeyContext::Form
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, action: Option<Action>, ctx: &mut Context) -> Navigation {
+        let count = self.candidats.len();
+        match action {
+            Some(Action::Back) if count > 0 => self.candidats.clear(),
+            Some(Action::Back) => return Navigation::Pop,
+            Some(Action::Submit) if count > 0 => {
+                let id = self.candidats[self.choix];
+                self.pointer(&mut ctx.biblio.lock().unwrap(), id);
+            }
+            Some(Action::Submit) => self.saisir(ctx),
+            Some(Action::NextField) if count > 0 => self.choix = (self.choix + 1) % count,
+            Some(Action::PrevField) if count > 0 => self.choix = (self.choix + count - 1) % count,
+            Some(Action::Report) => return Navigation::push(InventoryReportScreen::new()),
+            _ => {
+                // Une nouvelle saisie abandonne le choix en cours
+                self.candidats.clear();
+                self.input.handle_key_event(key);
+            }
+        }
+        Navigation::None
+    }
+
+    fn handle_paste(&mut self, text: &str, _ctx: &mut Context) -> Navigation {
+        self.candidats.clear();
+        self.input.paste(text);
+        Navigation::None
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let theme = ctx.theme;
+        let biblio = ctx.biblio.lock().unwrap();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(area);
+
+        let titre = match biblio.inventaire() {
+            Some(inventaire) => format!(
+                "{} · {}",
+                self.title(),
+                tf("inventory.since", &[("date", &inventaire.debut.format(t("common.date_format")))])
+            ),
+            None => self.title(),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(titre)
+            .title_style(theme.title())
+            .border_style(theme.border_focused());
+        let inner = block.inner(chunks[0]);
+        block.render(chunks[0], buf);
+
+        // Saisie, progression, puis le choix en cours ou le journal de la session
+        let zones = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Length(3), Constraint::Min(3)])
+            .split(inner);
+
+        self.input.render(zones[0], buf, theme);
+        self.render_progression(&biblio, zones[1], buf, theme);
+        if self.candidats.is_empty() {
+            self.render_journal(zones[2], buf, theme);
+        } else {
+            self.render_candidats(&biblio, zones[2], buf, theme);
+        }
+
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        HelpBar::new(&hints, theme).render(chunks[1], buf);
+    }
+}
+
+// Rapport de la session en cours; Entrée propose de la clôturer
+#[derive(Default)]
+pub struct InventoryReportScreen {
+    scroll: u16,
+}
+
+impl InventoryReportScreen {
+    pub fn new() -> Self {
+        InventoryReportScreen::default()
+    }
+
+    fn lignes(rapport: &RapportInventaire, biblio: &Bibliotheque, theme: &Theme) -> Vec<Line<'static>> {
+        let date = rapport.debut.format(t("common.date_format")).to_string();
+        let mut lignes = vec![
+            Line::from(Span::styled(
+                tf(
+                    "inventory.report.summary",
+                    &[("date", &date), ("vus", &rapport.vus), ("total", &rapport.total)],
+                ),
+                theme.text(),
+            )),
+            Line::default(),
+        ];
+
+        let livre = |id: &u32| match biblio.get_livres().iter().find(|l| l.id == *id) {
+            Some(livre) => format!("#{} {} ({})", livre.id, livre.titre, livre.annee),
+            None => format!("#{}", id),
+        };
+        let sections = [
+            ("inventory.report.missing", rapport.manquants.iter().map(livre).collect::<Vec<_>>()),
+            (
+                "inventory.report.borrowed_on_shelf",
+                rapport.empruntes_en_rayon.iter().map(livre).collect(),
+            ),
+            ("inventory.report.unknown", rapport.inconnus.clone()),
+        ];
+        for (cle, elements) in sections {
+            lignes.push(Line::from(Span::styled(
+                tn(cle, elements.len()),
+                theme.title().add_modifier(Modifier::BOLD),
+            )));
+            if elements.is_empty() {
+                lignes.push(Line::from(Span::styled(format!("  {}", t("inventory.report.none")), theme.dim())));
+            }
+            for element in elements {
+                lignes.push(Line::from(vec![
+                    Span::styled(format!("  {} ", symbols::FAILURE), theme.status(true)),
+                    Span::styled(element, theme.text()),
+                ]));
+            }
+            lignes.push(Line::default());
+        }
+        lignes
+    }
+
+    fn confirmer_cloture(&self) -> Navigation {
+        Navigation::push(ConfirmScreen::new(
+            t("inventory.close.title"),
+            t("inventory.close.confirm"),
+            |ctx| {
+                let rapport = ctx.biblio.lock().unwrap().terminer_inventaire();
+                let message = match rapport {
+                    Ok(Some(r)) => tf(
+                        "inventory.closed",
+                        &[
+                            ("manquants", &r.manquants.len()),
+                            ("en_rayon", &r.empruntes_en_rayon.len()),
+                            ("inconnus", &r.inconnus.len()),
+                        ],
+                    ),
+                    Ok(None) => t("error.no_inventory").to_string(),
+                    Err(e) => {
+                        return Navigation::Sequence(vec![
+                            Navigation::Pop,
+                            Navigation::error(tf("common.error_detail", &[("erreur", &e)])),
+                        ]);
+                    }
+                };
+                // Ferme la confirmation et le rapport, le message remplace l'écran de pointage
+                Navigation::Sequence(vec![
+                    Navigation::Pop,
+                    Navigation::Pop,
+                    Navigation::replace(MessageScreen::new(t("common.success"), message, false)),
+                ])
+            },
+        ))
+    }
+}
+
+impl Screen for InventoryReportScreen {
+    fn title(&self) -> String {
+        format!("📋 {}", t("inventory.report.title"))
+    }
+
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        vec![
+            (&[Action::Up, Action::Down], t("help.scroll")),
+            (&[Action::Select], t("help.close_inventory")),
+            (&[Action::Back], t("help.back")),
+        ]
+    }
+
+    fn handle_key(&mut self, _key: KeyEvent, action: Option<Action>, _ctx: &mut Context) -> Navigation {
+        match action {
+            Some(Action::Select) => return self.confirmer_cloture(),
+            Some(Action::Back) => return Navigation::Pop,
+            Some(Action::Down) => self.scroll = self.scroll.saturating_add(1),
+            Some(Action::Up) => self.scroll = self.scroll.saturating_sub(1),
+            _ => {}
+        }
+        Navigation::None
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, _ctx: &mut Context) -> Navigation {
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.scroll = self.scroll.saturating_add(1),
+            MouseEventKind::ScrollUp => self.scroll = self.scroll.saturating_sub(1),
+            _ => {}
+        }
+        Navigation::None
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let theme = ctx.theme;
+        let biblio = ctx.biblio.lock().unwrap();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(self.title())
+            .title_style(theme.title())
+            .border_style(theme.border_focused());
+        let inner = block.inner(chunks[0]);
+        block.render(chunks[0], buf);
+
+        let lignes = match biblio.rapport_inventaire() {
+            Some(rapport) => Self::lignes(&rapport, &biblio, theme),
+            None => vec![Line::from(Span::styled(t("error.no_inventory"), theme.dim()))],
+        };
+
+        // Pas de défilement au-delà de la dernière ligne
+        let max_scroll = (lignes.len() as u16).saturating_sub(inner.height);
+        self.scroll = self.scroll.min(max_scroll);
+        Paragraph::new(lignes).scroll((self.scroll, 0)).render(inner, buf);
+
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        HelpBar::new(&hints, theme).render(chunks[1], buf);
+    }
+}
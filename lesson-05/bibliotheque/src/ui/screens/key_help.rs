@@ -0,0 +1,88 @@
+use crate::app::keymap::{Action, KeyContext};
+use crate::app::screen::{Context, Navigation, Screen};
+use crate::i18n::t;
+use crate::ui::layout::centered_rect;
+use crate::ui::widgets::HelpBar;
+use crossterm::event::KeyEvent;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+
+// Liste des raccourcis actifs pour l'écran courant, générée depuis le keymap
+pub struct KeyHelpScreen {
+    // (touches, description)
+    entries: Vec<(String, &'static str)>,
+}
+
+impl KeyHelpScreen {
+    pub fn new(entries: Vec<(String, &'static str)>) -> Self {
+        KeyHelpScreen { entries }
+    }
+}
+
+impl Screen for KeyHelpScreen {
+    fn title(&self) -> String {
+        format!("⌨️  {}", t("keyhelp.title"))
+    }
+
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        vec![(&[Action::Back, Action::Help], t("common.close"))]
+    }
+
+    fn key_context(&self) -> KeyContext {
+        KeyContext::Dialog
+    }
+
+    fn handle_key(&mut self, _key: KeyEvent, action: Option<Action>, _ctx: &mut Context) -> Navigation {
+        match action {
+            Some(Action::Back | Action::Select | Action::Help) => Navigation::Pop,
+            _ => Navigation::None,
+        }
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let theme = ctx.theme;
+        let keys_width = self
+            .entries
+            .iter()
+            .map(|(keys, _)| keys.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        // Bordures + ligne d'aide
+        let height = (self.entries.len() as u16 + 3).min(area.height);
+        let width = 60.min(area.width);
+        let dialog_area = centered_rect(width, height, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(self.title())
+            .title_style(theme.title())
+            .border_style(theme.border_focused());
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        let lines: Vec<Line> = self
+            .entries
+            .iter()
+            .map(|(keys, description)| {
+                Line::from(vec![
+                    Span::styled(format!(" {:<width$}  ", keys, width = keys_width), theme.key()),
+                    Span::styled(*description, theme.text()),
+                ])
+            })
+            .collect();
+        Paragraph::new(lines).render(chunks[0], buf);
+
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        HelpBar::new(&hints, theme).render(chunks[1], buf);
+    }
+}
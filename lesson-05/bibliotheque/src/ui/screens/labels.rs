@@ -0,0 +1,113 @@
+use super::MessageScreen;
+use crate::app::keymap::{Action, KeyContext};
+use crate::app::screen::{Context, Navigation, Screen};
+use crate::i18n::{t, tf, tn};
+use crate::services::etiquettes::{self, DOSSIER_ETIQUETTES, FormatEtiquette, PAPIERS, TypeEtiquette};
+use crate::ui::widgets::{Field, Form, FormValues};
+use chrono::Local;
+use crossterm::event::{KeyEvent, MouseEvent};
+use ratatui::{buffer::Buffer, layout::Rect};
+use std::path::Path;
+
+// Impression d'étiquettes: dos ou codes-barres, format, planche, première étiquette libre
+// (pour finir une planche entamée) et dossier de sortie
+pub struct LabelsScreen {
+    form: Form,
+}
+
+impl LabelsScreen {
+    pub fn new() -> Self {
+        let types = TypeEtiquette::TOUS.iter().map(|e| e.libelle().to_string()).collect();
+        let formats = FormatEtiquette::TOUS.iter().map(|f| f.libelle().to_string()).collect();
+        let papiers = PAPIERS.iter().map(|p| p.libelle()).collect();
+        let plus_grande = PAPIERS.iter().map(|p| p.par_planche()).max().unwrap_or(1);
+        LabelsScreen {
+            form: Form::new(vec![
+                Field::choice("type", t("labels.field.kind"), types),
+                Field::choice("format", t("labels.field.format"), formats),
+                Field::choice("papier", t("labels.field.paper"), papiers),
+                Field::integer("depart", t("labels.field.start"), 1, i64::from(plus_grande)).with_value("1"),
+                Field::text("dossier", t("labels.field.folder")).with_value(DOSSIER_ETIQUETTES),
+            ]),
+        }
+    }
+
+    fn submit(&mut self, values: FormValues, ctx: &mut Context) -> Navigation {
+        let type_etiquette = TypeEtiquette::TOUS[values.choice("type")];
+        let format = FormatEtiquette::TOUS[values.choice("format")];
+        let papier = &PAPIERS[values.choice("papier")];
+        let depart = values.integer("depart") as u32;
+        if depart > papier.par_planche() {
+            return Navigation::error(tf(
+                "labels.start_beyond_sheet",
+                &[("nom", &papier.nom), ("n", &papier.par_planche())],
+            ));
+        }
+
+        let dossier = Path::new(values.text("dossier"));
+        let aujourdhui = Local::now().date_naive();
+        let b = ctx.biblio.lock().unwrap();
+        match etiquettes::ecrire(type_etiquette, format, papier, depart, &b, aujourdhui, dossier) {
+            Ok(impression) => {
+                let mut texte = tf(
+                    "labels.written",
+                    &[
+                        ("etiquettes", &tn("labels.count", impression.etiquettes)),
+                        ("planches", &tn("labels.sheets", impression.planches)),
+                        ("dossier", &dossier.display()),
+                    ],
+                );
+                if impression.sans_cote > 0 {
+                    texte.push('\n');
+                    texte.push_str(&tn("labels.skipped", impression.sans_cote));
+                }
+                Navigation::replace(MessageScreen::new(t("common.success"), texte, false))
+            }
+            Err(e) => Navigation::error(tf("common.error_detail", &[("erreur", &e)])),
+        }
+    }
+}
+
+impl Screen for LabelsScreen {
+    fn title(&self) -> String {
+        format!("🏷️  {}", t("labels.title"))
+    }
+
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        vec![
+            (&[Action::NextField], t("help.next_field")),
+            (&[Action::Submit], t("help.generate")),
+            (&[Action::Back], t("help.cancel")),
+            (&[Action::Help], t("help.help")),
+        ]
+    }
+
+    fn key_context(&self) -> KeyContext {
+        KeyContext::Form
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, action: Option<Action>, ctx: &mut Context) -> Navigation {
+        match action {
+            Some(Action::Back) => Navigation::Pop,
+            _ => match self.form.handle_key_event(key, action) {
+                Some(values) => self.submit(values, ctx),
+                None => Navigation::None,
+            },
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, _ctx: &mut Context) -> Navigation {
+        self.form.handle_mouse_event(mouse);
+        Navigation::None
+    }
+
+    fn handle_paste(&mut self, text: &str, _ctx: &mut Context) -> Navigation {
+        self.form.paste(text);
+        Navigation::None
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        self.form.render_framed(&self.title(), &hints, area, buf, ctx.theme);
+    }
+}
@@ -1,82 +1,232 @@
-use crate::app::state::ListState;
-use crossterm::event::{KeyCode, KeyEvent};
+use super::{
+    AddAuthorScreen, AddBookScreen, AddOperatorScreen, BorrowBookScreen, ConflitScreen, InventoryScreen, LabelsScreen,
+    ListAuthorsScreen, ListBooksScreen, LocationPickerScreen, MessageScreen, PassphraseScreen, ReportScreen,
+    ReturnBookScreen, ScanScreen,
+};
+use crate::app::{FICHIER_BIBLIOTHEQUE, FICHIER_OPERATEURS};
+use crate::app::event::{AppEvent, DomainEvent, EventKind};
+use crate::app::keymap::Action;
+use crate::app::screen::{Context, Navigation, Screen};
+use crate::app::state::{ListState, StatusLine};
+use crate::i18n::{t, tf};
+use crate::models::Permission;
+use crate::services::{Bibliotheque, ErreurChargement, ErreurSauvegarde};
+use crate::ui::theme::symbols;
+use crate::ui::widgets::HelpBar;
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph, Widget},
 };
+use std::path::Path;
+
+// Chaque entrée du menu porte directement l'action à exécuter
+pub type MenuAction = fn(&mut Context) -> Navigation;
 
-const MENU_ITEMS: &[(&str, &str)] = &[
-    ("add_author", "✍️  Ajouter un auteur"),
-    ("add_book", "📚 Ajouter un livre"),
-    ("list_books", "📖 Lister les livres"),
-    ("borrow_book", "✋ Emprunter un livre"),
-    ("return_book", "📥 Retourner un livre"),
-    ("list_authors", "👥 Lister les auteurs"),
-    ("save", "💾 Sauvegarder"),
-    ("load", "📂 Charger"),
-    ("quit", "X - Quitter"),
+// (icône, clé du libellé, action), repris par la palette de commandes
+pub const MENU_ITEMS: &[(&str, &str, MenuAction)] = &[
+    ("✍️  ", "menu.add_author", |_| Navigation::push(AddAuthorScreen::new())),
+    ("📚 ", "menu.add_book", |ctx| Navigation::push(AddBookScreen::new(&ctx.biblio.lock().unwrap()))),
+    ("📖 ", "menu.list_books", |_| Navigation::push(ListBooksScreen::new())),
+    ("✋ ", "menu.borrow", |_| Navigation::push(BorrowBookScreen::new())),
+    ("📥 ", "menu.return", |_| Navigation::push(ReturnBookScreen::new())),
+    ("📷 ", "menu.scan", |_| Navigation::push(ScanScreen::new())),
+    ("📋 ", "menu.inventory", |ctx| match InventoryScreen::ouvrir(&mut ctx.biblio.lock().unwrap()) {
+        Ok(inventaire) => Navigation::push(inventaire),
+        Err(e) => Navigation::error(tf("common.error_detail", &[("erreur", &e)])),
+    }),
+    ("📏 ", "menu.shelf_read", |ctx| Navigation::push(LocationPickerScreen::lecture_rayon(&ctx.biblio.lock().unwrap()))),
+    ("👥 ", "menu.list_authors", |_| Navigation::push(ListAuthorsScreen::new())),
+    ("📄 ", "menu.reports", |_| Navigation::push(ReportScreen::new())),
+    ("🏷️  ", "menu.labels", |_| Navigation::push(LabelsScreen::new())),
+    ("🔑 ", "menu.operators", operators),
+    ("🔒 ", "menu.encryption", encryption),
+    ("💾 ", "menu.save", save),
+    ("📂 ", "menu.load", load),
+    ("X - ", "menu.quit", |_| Navigation::Quit),
 ];
 
-pub enum MenuAction {
-    AddAuthor,
-    AddBook,
-    ListBooks,
-    BorrowBook,
-    ReturnBook,
-    ListAuthors,
-    Save,
-    Load,
-    Quit,
-    None,
+// Durée d'affichage d'un statut, en ticks
+const STATUS_TICKS: u16 = 20;
+
+#[derive(Default)]
+pub struct MainMenu {
+    list: ListState,
+    status: Option<StatusLine>,
+    // Zone des entrées lors du dernier rendu (pour la souris)
+    menu_area: Rect,
+}
+
+impl MainMenu {
+    pub fn new() -> Self {
+        MainMenu::default()
+    }
+
+    fn set_status(&mut self, text: String, is_error: bool) {
+        self.status = Some(StatusLine {
+            text,
+            is_error,
+            ticks_left: STATUS_TICKS,
+        });
+    }
 }
 
-pub struct MainMenu<'a> {
-    state: &'a mut ListState,
+fn save(ctx: &mut Context) -> Navigation {
+    let mut b = ctx.biblio.lock().unwrap();
+    match b.sauvegarder(FICHIER_BIBLIOTHEQUE) {
+        Ok(_) => Navigation::message(
+            t("common.success"),
+            tf("menu.saved", &[("fichier", &FICHIER_BIBLIOTHEQUE)]),
+            false,
+        ),
+        Err(ErreurSauvegarde::Conflit { revision }) => {
+            Navigation::push(ConflitScreen::new(FICHIER_BIBLIOTHEQUE, revision))
+        }
+        Err(e) => Navigation::error(tf("menu.save_failed", &[("erreur", &e)])),
+    }
 }
 
-impl<'a> MainMenu<'a> {
-    pub fn new(state: &'a mut ListState) -> Self {
-        MainMenu { state }
+fn load(ctx: &mut Context) -> Navigation {
+    let mut b = ctx.biblio.lock().unwrap();
+    match b.ouvrir(FICHIER_BIBLIOTHEQUE) {
+        Ok(()) => Navigation::message(
+            t("common.success"),
+            tf("menu.loaded", &[("fichier", &FICHIER_BIBLIOTHEQUE)]),
+            false,
+        ),
+        Err(ErreurChargement::PhraseRequise) => Navigation::push(PassphraseScreen::deverrouiller(FICHIER_BIBLIOTHEQUE)),
+        Err(e) => Navigation::error(tf("menu.load_failed", &[("erreur", &e)])),
     }
+}
 
-    pub fn handle_key_event(&mut self, key: KeyEvent) -> MenuAction {
-        match key.code {
-            KeyCode::Down | KeyCode::Char('j') => {
-                self.state.selected = (self.state.selected + 1) % MENU_ITEMS.len();
-                MenuAction::None
+// Chargement du fichier au démarrage (ou à la connexion), quel que soit le rôle: sans lui
+// un bénévole, qui ne peut pas charger de fichier, verrait une bibliothèque vide
+// Renvoie l'écran à afficher ensuite: la phrase secrète si le fichier est chiffré,
+// l'erreur s'il est illisible; rien si le fichier n'existe pas encore
+pub fn ouvrir_au_demarrage(b: &mut Bibliotheque, fichier: &str) -> Option<Box<dyn Screen>> {
+    if !Path::new(fichier).exists() {
+        return None;
+    }
+    match b.ouvrir(fichier) {
+        Ok(()) => None,
+        Err(ErreurChargement::PhraseRequise) => Some(Box::new(PassphraseScreen::deverrouiller(fichier))),
+        Err(e) => Some(Box::new(MessageScreen::new(
+            t("common.error"),
+            tf("menu.load_failed", &[("erreur", &e)]),
+            true,
+        ))),
+    }
+}
+
+// Seul un administrateur crée des comptes (ou n'importe qui tant qu'il n'y en a aucun)
+fn operators(ctx: &mut Context) -> Navigation {
+    if let Err(e) = ctx.biblio.lock().unwrap().autoriser(Permission::Administrer) {
+        return Navigation::error(tf("common.error_detail", &[("erreur", &e)]));
+    }
+    Navigation::push(AddOperatorScreen::new(FICHIER_OPERATEURS))
+}
+
+fn encryption(ctx: &mut Context) -> Navigation {
+    if let Err(e) = ctx.biblio.lock().unwrap().autoriser(Permission::Fichiers) {
+        return Navigation::error(tf("common.error_detail", &[("erreur", &e)]));
+    }
+    Navigation::push(PassphraseScreen::changer(FICHIER_BIBLIOTHEQUE))
+}
+
+impl Screen for MainMenu {
+    fn title(&self) -> String {
+        t("menu.title").to_string()
+    }
+
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        vec![
+            (&[Action::Up, Action::Down], t("help.navigate")),
+            (&[Action::Select], t("help.select")),
+            (&[Action::NextTheme], t("help.theme")),
+            (&[Action::Palette], t("help.palette")),
+            (&[Action::Help], t("help.help")),
+            (&[Action::Back], t("help.quit")),
+        ]
+    }
+
+    fn handle_key(&mut self, _key: KeyEvent, action: Option<Action>, ctx: &mut Context) -> Navigation {
+        match action {
+            Some(Action::Down) => {
+                self.list.selected = (self.list.selected + 1) % MENU_ITEMS.len();
+                Navigation::None
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                if self.state.selected == 0 {
-                    self.state.selected = MENU_ITEMS.len() - 1;
+            Some(Action::Up) => {
+                if self.list.selected == 0 {
+                    self.list.selected = MENU_ITEMS.len() - 1;
                 } else {
-                    self.state.selected -= 1;
+                    self.list.selected -= 1;
                 }
-                MenuAction::None
+                Navigation::None
             }
-            KeyCode::Enter => {
-                match MENU_ITEMS[self.state.selected].0 {
-                    "add_author" => MenuAction::AddAuthor,
-                    "add_book" => MenuAction::AddBook,
-                    "list_books" => MenuAction::ListBooks,
-                    "borrow_book" => MenuAction::BorrowBook,
-                    "return_book" => MenuAction::ReturnBook,
-                    "list_authors" => MenuAction::ListAuthors,
-                    "save" => MenuAction::Save,
-                    "load" => MenuAction::Load,
-                    "quit" => MenuAction::Quit,
-                    _ => MenuAction::None,
+            Some(Action::Select) => (MENU_ITEMS[self.list.selected].2)(ctx),
+            // Retour depuis la racine = quitter
+            Some(Action::Back) => Navigation::Quit,
+            _ => Navigation::None,
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, ctx: &mut Context) -> Navigation {
+        match mouse.kind {
+            // Un clic sélectionne l'entrée, un clic sur l'entrée déjà sélectionnée l'active
+            MouseEventKind::Down(MouseButton::Left) => {
+                match self.list.item_at(self.menu_area, mouse.column, mouse.row) {
+                    Some(index) if index == self.list.selected => (MENU_ITEMS[index].2)(ctx),
+                    Some(index) if index < MENU_ITEMS.len() => {
+                        self.list.selected = index;
+                        Navigation::None
+                    }
+                    _ => Navigation::None,
                 }
             }
-            _ => MenuAction::None,
+            MouseEventKind::ScrollDown => {
+                self.list.move_by(1, MENU_ITEMS.len());
+                Navigation::None
+            }
+            MouseEventKind::ScrollUp => {
+                self.list.move_by(-1, MENU_ITEMS.len());
+                Navigation::None
+            }
+            _ => Navigation::None,
         }
     }
-}
 
-impl<'a> Widget for MainMenu<'a> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
+    fn subscriptions(&self) -> &'static [EventKind] {
+        &[EventKind::Tick, EventKind::Domain]
+    }
+
+    fn handle_event(&mut self, event: &AppEvent, _ctx: &mut Context) {
+        match event {
+            AppEvent::Tick => {
+                if let Some(status) = &mut self.status {
+                    status.ticks_left = status.ticks_left.saturating_sub(1);
+                    if status.ticks_left == 0 {
+                        self.status = None;
+                    }
+                }
+            }
+            AppEvent::Domain(DomainEvent::Autosaved { fichier }) => {
+                self.set_status(tf("autosave.done", &[("fichier", fichier)]), false);
+            }
+            AppEvent::Domain(DomainEvent::AutosaveFailed { erreur }) => {
+                self.set_status(tf("autosave.failed", &[("erreur", erreur)]), true);
+            }
+            AppEvent::Domain(DomainEvent::ModifieAilleurs { fichier, .. }) => {
+                self.set_status(tf("autosave.changed_elsewhere", &[("fichier", fichier)]), false);
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let theme = ctx.theme;
+
         // Layout principal: titre + menu + aide
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -89,19 +239,14 @@ impl<'a> Widget for MainMenu<'a> {
 
         // Titre
         let title = Paragraph::new(Line::from(vec![
-            Span::styled("🏛️  ", Style::default().fg(Color::Cyan)),
-            Span::styled(
-                "Bibliothèque",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            ),
+            Span::styled("🏛️  ", theme.border_focused()),
+            Span::styled(t("menu.app_name"), theme.title()),
         ]))
         .alignment(Alignment::Center)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
+                .border_style(theme.border_focused()),
         );
         title.render(chunks[0], buf);
 
@@ -109,48 +254,53 @@ impl<'a> Widget for MainMenu<'a> {
         let items: Vec<ListItem> = MENU_ITEMS
             .iter()
             .enumerate()
-            .map(|(i, item)| {
-                let content = if i == self.state.selected {
+            .map(|(i, (icon, label, _))| {
+                let label = format!("{}{}", icon, t(label));
+                let content = if i == self.list.selected {
                     Line::from(vec![
-                        Span::styled("> ", Style::default().fg(Color::Cyan)),
-                        Span::styled(
-                            item.1,
-                            Style::default()
-                                .fg(Color::Cyan)
-                                .add_modifier(Modifier::BOLD),
-                        ),
+                        Span::styled(format!("{} ", symbols::SELECTOR), theme.border_focused()),
+                        Span::styled(label, theme.title()),
                     ])
                 } else {
                     Line::from(vec![
                         Span::raw("  "),
-                        Span::styled(item.1, Style::default().fg(Color::White)),
+                        Span::styled(label, theme.text()),
                     ])
                 };
                 ListItem::new(content)
             })
             .collect();
 
-        let list = List::new(items).block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Menu Principal")
-                .border_style(Style::default().fg(Color::Blue)),
-        );
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .title(self.title())
+            .border_style(theme.border());
 
-        list.render(chunks[1], buf);
+        // Opérateur connecté, en haut à droite
+        if let Some(session) = ctx.biblio.lock().unwrap().session() {
+            let operateur = tf("menu.session", &[("nom", &session.nom), ("role", &t(session.role.cle()))]);
+            block = block.title(Line::from(format!(" {} ", operateur)).right_aligned());
+        }
 
-        // Aide
-        let help = Paragraph::new(Line::from(vec![
-            Span::styled("↑/↓", Style::default().fg(Color::Yellow)),
-            Span::raw(":Navigate "),
-            Span::styled("Enter", Style::default().fg(Color::Yellow)),
-            Span::raw(":Select "),
-            Span::styled("Esc", Style::default().fg(Color::Yellow)),
-            Span::raw(":Quit"),
-        ]))
-        .alignment(Alignment::Center)
-        .style(Style::default().fg(Color::DarkGray));
+        if let Some(status) = &self.status {
+            block = block.title_bottom(
+                Line::from(Span::styled(format!(" {} ", status.text), theme.status(status.is_error)))
+                    .right_aligned(),
+            );
+        }
 
-        help.render(chunks[2], buf);
+        // Sur un petit terminal, le menu défile pour garder l'entrée sélectionnée visible
+        self.menu_area = block.inner(chunks[1]);
+        self.list.fit(MENU_ITEMS.len(), self.menu_area.height as usize);
+        let visible_items: Vec<ListItem> = items
+            .into_iter()
+            .skip(self.list.scroll_offset)
+            .take(self.menu_area.height as usize)
+            .collect();
+        List::new(visible_items).block(block).render(chunks[1], buf);
+
+        // Aide
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        HelpBar::new(&hints, theme).render(chunks[2], buf);
     }
 }
@@ -1,106 +1,147 @@
-use crate::app::state::MessageState;
+use crate::app::keymap::{Action, KeyContext};
+use crate::app::screen::{Context, Navigation, Screen};
+use crate::i18n::t;
+use crate::ui::layout::centered_rect;
+use crate::ui::theme::Theme;
+use crate::ui::widgets::HelpBar;
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     buffer::Buffer,
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
-    text::{Line, Span},
+    layout::{Alignment, Constraint, Direction, Layout, Position, Rect},
+    style::{Modifier, Style},
     widgets::{Block, Borders, Paragraph, Widget, Wrap},
 };
 
-pub struct MessageScreen<'a> {
-    state: &'a MessageState,
+// Dialog d'information ou d'erreur
+pub struct MessageScreen {
+    title: String,
+    message: String,
+    is_error: bool,
+    // Position du bouton lors du dernier rendu (pour la souris)
+    ok_button: Rect,
 }
 
-impl<'a> MessageScreen<'a> {
-    pub fn new(state: &'a MessageState) -> Self {
-        MessageScreen { state }
+const OK_BUTTON: &str = "[ OK ]";
+
+impl MessageScreen {
+    pub fn new(title: &str, message: impl Into<String>, is_error: bool) -> Self {
+        MessageScreen {
+            title: title.to_string(),
+            message: message.into(),
+            is_error,
+            ok_button: Rect::default(),
+        }
     }
 }
 
-impl<'a> Widget for MessageScreen<'a> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        // Créer un dialog centré
-        let dialog_width = 50.min(area.width.saturating_sub(4));
-        let dialog_height = 8.min(area.height.saturating_sub(4));
+impl Screen for MessageScreen {
+    fn title(&self) -> String {
+        self.title.clone()
+    }
 
-        let dialog_area = centered_rect(dialog_width, dialog_height, area);
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        vec![(&[Action::Select, Action::Back], t("common.close"))]
+    }
 
-        // Remplir l'arrière-plan avec un effet semi-transparent (grisé)
-        for y in area.top()..area.bottom() {
-            for x in area.left()..area.right() {
-                if x < buf.area.width && y < buf.area.height {
-                    buf.cell_mut((x, y)).map(|cell| cell.set_bg(Color::Black));
-                }
-            }
+    fn key_context(&self) -> KeyContext {
+        KeyContext::Dialog
+    }
+
+    fn handle_key(&mut self, _key: KeyEvent, action: Option<Action>, _ctx: &mut Context) -> Navigation {
+        match action {
+            Some(Action::Select | Action::Back) => Navigation::Pop,
+            _ => Navigation::None,
         }
+    }
 
-        // Déterminer le style selon le type de message
-        let (icon, title_color, border_color) = if self.state.is_error {
-            ("⚠️  ", Color::Red, Color::Red)
+    fn handle_mouse(&mut self, mouse: MouseEvent, _ctx: &mut Context) -> Navigation {
+        let clicked = mouse.kind == MouseEventKind::Down(MouseButton::Left)
+            && self.ok_button.contains(Position::new(mouse.column, mouse.row));
+        if clicked {
+            Navigation::Pop
         } else {
-            ("✅ ", Color::Green, Color::Green)
-        };
+            Navigation::None
+        }
+    }
 
-        let title = format!("{}{}", icon, self.state.title);
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let theme = ctx.theme;
 
-        // Créer le block du dialog
-        let block = Block::default()
-            .borders(Borders::ALL)
-            .title(title.as_str())
-            .title_style(
-                Style::default()
-                    .fg(title_color)
-                    .add_modifier(Modifier::BOLD),
-            )
-            .border_style(Style::default().fg(border_color));
+        // Déterminer le style selon le type de message
+        let icon = if self.is_error { "⚠️  " } else { "✅ " };
+        let status_style = theme.status(self.is_error);
 
-        let inner = block.inner(dialog_area);
-        block.render(dialog_area, buf);
+        let title = format!("{}{}", icon, self.title);
+        let inner = render_dialog_frame(area, buf, theme, (50, 8), &title, status_style);
 
-        // Layout interne: message + aide
+        // Layout interne: message + bouton + aide
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Min(3), Constraint::Length(2)])
+            .constraints([
+                Constraint::Min(3),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
             .split(inner);
 
         // Message
-        let message = Paragraph::new(self.state.message.as_str())
+        let message = Paragraph::new(self.message.as_str())
             .wrap(Wrap { trim: true })
             .alignment(Alignment::Center)
-            .style(Style::default().fg(Color::White));
+            .style(theme.text());
 
         message.render(chunks[0], buf);
 
+        // Bouton cliquable
+        let button_width = OK_BUTTON.len() as u16;
+        self.ok_button = Rect {
+            x: chunks[1].x + chunks[1].width.saturating_sub(button_width) / 2,
+            y: chunks[1].y,
+            width: button_width.min(chunks[1].width),
+            height: chunks[1].height,
+        };
+        Paragraph::new(OK_BUTTON)
+            .style(status_style.add_modifier(Modifier::BOLD))
+            .render(self.ok_button, buf);
+
         // Aide
-        let help = Paragraph::new(Line::from(vec![
-            Span::styled("Enter", Style::default().fg(Color::Yellow)),
-            Span::raw(" ou "),
-            Span::styled("Esc", Style::default().fg(Color::Yellow)),
-            Span::raw(": Fermer"),
-        ]))
-        .alignment(Alignment::Center)
-        .style(Style::default().fg(Color::DarkGray));
-
-        help.render(chunks[1], buf);
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        HelpBar::new(&hints, theme).render(chunks[2], buf);
     }
 }
 
-fn centered_rect(width: u16, height: u16, r: Rect) -> Rect {
-    let popup_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length((r.height.saturating_sub(height)) / 2),
-            Constraint::Length(height),
-            Constraint::Length((r.height.saturating_sub(height)) / 2),
-        ])
-        .split(r);
-
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Length((r.width.saturating_sub(width)) / 2),
-            Constraint::Length(width),
-            Constraint::Length((r.width.saturating_sub(width)) / 2),
-        ])
-        .split(popup_layout[1])[1]
+// Fond grisé et cadre d'un dialog centré (message, confirmation, bilan)
+// Renvoie la zone intérieure du cadre
+pub fn render_dialog_frame(
+    area: Rect,
+    buf: &mut Buffer,
+    theme: &Theme,
+    (width, height): (u16, u16),
+    title: &str,
+    style: Style,
+) -> Rect {
+    let dialog_area = centered_rect(
+        width.min(area.width.saturating_sub(4)),
+        height.min(area.height.saturating_sub(4)),
+        area,
+    );
+
+    // Remplir l'arrière-plan avec un effet semi-transparent (grisé)
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            if let Some(cell) = buf.cell_mut((x, y)) {
+                cell.set_bg(theme.overlay);
+            }
+        }
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title_style(style.add_modifier(Modifier::BOLD))
+        .border_style(style);
+
+    let inner = block.inner(dialog_area);
+    block.render(dialog_area, buf);
+    inner
 }
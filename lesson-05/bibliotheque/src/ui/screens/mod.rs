@@ -1,9 +1,33 @@
 pub mod author;
 pub mod book;
+pub mod confirm;
+pub mod conflict;
+pub mod inventory;
+pub mod key_help;
+pub mod labels;
 pub mod main_menu;
 pub mod message;
+pub mod operators;
+pub mod palette;
+pub mod passphrase;
+pub mod report;
+pub mod scan;
+pub mod shelf;
+pub mod summary;
 
 pub use author::*;
 pub use book::*;
+pub use confirm::*;
+pub use conflict::*;
+pub use inventory::*;
+pub use key_help::*;
+pub use labels::*;
 pub use main_menu::*;
 pub use message::*;
+pub use operators::*;
+pub use palette::*;
+pub use passphrase::*;
+pub use report::*;
+pub use scan::*;
+pub use shelf::*;
+pub use summary::*;
@@ -0,0 +1,205 @@
+use super::{MessageScreen, ouvrir_au_demarrage};
+use crate::app::keymap::{Action, KeyContext};
+use crate::app::screen::{Context, Navigation, Screen};
+use crate::i18n::{t, tf};
+use crate::models::{Role, Session};
+use crate::services::ErreurOperateur;
+use crate::services::operateurs::Operateurs;
+use crate::ui::widgets::{Field, Form, FormValues};
+use crossterm::event::{KeyEvent, MouseEvent};
+use ratatui::{buffer::Buffer, layout::Rect};
+
+// Connexion au démarrage, quand des comptes existent; on n'en sort qu'en se connectant ou en quittant
+// Le fichier de la bibliothèque est chargé une fois l'opérateur connecté
+pub struct LoginScreen {
+    form: Form,
+    operateurs: Operateurs,
+    fichier: String,
+}
+
+impl LoginScreen {
+    pub fn new(operateurs: Operateurs, fichier: &str) -> Self {
+        LoginScreen {
+            form: Form::new(vec![
+                Field::text("nom", t("login.field.name")),
+                Field::password("mot_de_passe", t("login.field.password")),
+            ]),
+            operateurs,
+            fichier: fichier.to_string(),
+        }
+    }
+
+    fn submit(&mut self, values: FormValues, ctx: &mut Context) -> Navigation {
+        match self.operateurs.authentifier(values.text("nom"), values.text("mot_de_passe")) {
+            Some(session) => {
+                let mut b = ctx.biblio.lock().unwrap();
+                b.ouvrir_session(session);
+                match ouvrir_au_demarrage(&mut b, &self.fichier) {
+                    Some(ecran) => Navigation::Replace(ecran),
+                    None => Navigation::Pop,
+                }
+            }
+            None => {
+                if let Some(champ) = self.form.field_mut("mot_de_passe") {
+                    champ.set_value("");
+                }
+                self.form.set_error("mot_de_passe", t("login.failed").to_string());
+                Navigation::None
+            }
+        }
+    }
+}
+
+impl Screen for LoginScreen {
+    fn title(&self) -> String {
+        format!("🔑 {}", t("login.title"))
+    }
+
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        vec![
+            (&[Action::NextField], t("help.next_field")),
+            (&[Action::Submit], t("help.login")),
+            (&[Action::Back], t("help.quit")),
+        ]
+    }
+
+    fn key_context(&self) -> KeyContext {
+        KeyContext::Form
+    }
+
+    // La palette ouvrirait le menu sans connexion
+    fn captures(&self, action: Action) -> bool {
+        action == Action::Palette
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, action: Option<Action>, ctx: &mut Context) -> Navigation {
+        match action {
+            Some(Action::Back) => Navigation::Quit,
+            Some(Action::Palette) => Navigation::None,
+            _ => match self.form.handle_key_event(key, action) {
+                Some(values) => self.submit(values, ctx),
+                None => Navigation::None,
+            },
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, _ctx: &mut Context) -> Navigation {
+        self.form.handle_mouse_event(mouse);
+        Navigation::None
+    }
+
+    fn handle_paste(&mut self, text: &str, _ctx: &mut Context) -> Navigation {
+        self.form.paste(text);
+        Navigation::None
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        self.form.render_framed(&self.title(), &hints, area, buf, ctx.theme);
+    }
+}
+
+// Création d'un compte d'opérateur; sans compte existant, le premier administrateur
+// est créé puis connecté
+pub struct AddOperatorScreen {
+    form: Form,
+    fichier: String,
+}
+
+impl AddOperatorScreen {
+    pub fn new(fichier: &str) -> Self {
+        let roles = Role::TOUS.iter().map(|r| t(r.cle()).to_string()).collect();
+        AddOperatorScreen {
+            form: Form::new(vec![
+                Field::text("nom", t("operators.field.name")),
+                Field::choice("role", t("operators.field.role"), roles),
+                Field::password("mot_de_passe", t("operators.field.password")),
+                Field::password("confirmation", t("operators.field.confirm")),
+            ]),
+            fichier: fichier.to_string(),
+        }
+    }
+
+    fn submit(&mut self, values: FormValues, ctx: &mut Context) -> Navigation {
+        let nom = values.text("nom");
+        let role = Role::TOUS[values.choice("role")];
+        let mot_de_passe = values.text("mot_de_passe");
+        if values.text("confirmation") != mot_de_passe {
+            self.form.set_error("confirmation", t("operators.mismatch").to_string());
+            return Navigation::None;
+        }
+
+        let mut operateurs = match Operateurs::charger(&self.fichier) {
+            Ok(operateurs) => operateurs,
+            Err(e) => return Navigation::error(tf("common.error_detail", &[("erreur", &e)])),
+        };
+        let premier = operateurs.est_vide();
+        let mut b = ctx.biblio.lock().unwrap();
+        match operateurs.ajouter(b.session(), nom, role, mot_de_passe) {
+            Ok(()) => {}
+            Err(e @ ErreurOperateur::NomPris(_)) => {
+                self.form.set_error("nom", e.to_string());
+                return Navigation::None;
+            }
+            Err(e) => return Navigation::error(tf("common.error_detail", &[("erreur", &e)])),
+        }
+        if let Err(e) = operateurs.sauvegarder(&self.fichier) {
+            return Navigation::error(tf("common.error_detail", &[("erreur", &e)]));
+        }
+
+        let message = if premier {
+            b.ouvrir_session(Session {
+                nom: nom.to_string(),
+                role,
+            });
+            tf("operators.first", &[("nom", &nom)])
+        } else {
+            tf("operators.added", &[("nom", &nom), ("role", &t(role.cle()))])
+        };
+        Navigation::replace(MessageScreen::new(t("common.success"), message, false))
+    }
+}
+
+impl Screen for AddOperatorScreen {
+    fn title(&self) -> String {
+        format!("🔑 {}", t("operators.title"))
+    }
+
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        vec![
+            (&[Action::NextField], t("help.next_field")),
+            (&[Action::Submit], t("help.add")),
+            (&[Action::Back], t("help.cancel")),
+            (&[Action::Help], t("help.help")),
+        ]
+    }
+
+    fn key_context(&self) -> KeyContext {
+        KeyContext::Form
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, action: Option<Action>, ctx: &mut Context) -> Navigation {
+        match action {
+            Some(Action::Back) => Navigation::Pop,
+            _ => match self.form.handle_key_event(key, action) {
+                Some(values) => self.submit(values, ctx),
+                None => Navigation::None,
+            },
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, _ctx: &mut Context) -> Navigation {
+        self.form.handle_mouse_event(mouse);
+        Navigation::None
+    }
+
+    fn handle_paste(&mut self, text: &str, _ctx: &mut Context) -> Navigation {
+        self.form.paste(text);
+        Navigation::None
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        self.form.render_framed(&self.title(), &hints, area, buf, ctx.theme);
+    }
+}
@@ -0,0 +1,296 @@
+use super::{ListAuthorsScreen, ListBooksScreen, MENU_ITEMS, MenuAction, apres_emprunt, apres_retour, operation_livre};
+use crate::app::keymap::{Action, KeyContext};
+use crate::app::screen::{Context, Navigation, Screen};
+use crate::app::state::ListState;
+use crate::i18n::{t, tf};
+use crate::services::Bibliotheque;
+use crate::ui::fuzzy;
+use crate::ui::layout::centered_rect;
+use crate::ui::widgets::HelpBar;
+use crate::ui::widgets::text_input::TextInput;
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Widget},
+};
+
+// Taille maximale de la palette, centrée dans l'écran
+const PALETTE_WIDTH: u16 = 70;
+const PALETTE_HEIGHT: u16 = 20;
+
+// Ce que fait une commande une fois choisie
+enum Cible {
+    // Entrée du menu principal
+    Menu(MenuAction),
+    // Action globale (thème, aide)
+    Global(Action),
+    Emprunter(u32),
+    Rendre(u32),
+    // Liste ouverte sur le livre ou l'auteur
+    Livre(u32),
+    Auteur(u32),
+}
+
+struct Commande {
+    // "Commande", "Livre" ou "Auteur", affiché devant le libellé
+    categorie: &'static str,
+    libelle: String,
+    cible: Cible,
+}
+
+// Palette de commandes: recherche approximative parmi les actions du menu
+// et le catalogue ("emp petit" -> Emprunter « Le Petit Prince »)
+pub struct CommandPalette {
+    query: TextInput,
+    commandes: Vec<Commande>,
+    // Index dans `commandes` des commandes retenues, de la meilleure à la moins bonne
+    resultats: Vec<usize>,
+    state: ListState,
+    // Zone des résultats lors du dernier rendu (souris et nombre de lignes visibles)
+    list_area: Rect,
+}
+
+impl CommandPalette {
+    // Les commandes sont calculées à l'ouverture, sur l'état courant du catalogue
+    pub fn new(biblio: &Bibliotheque) -> Self {
+        let commande = t("palette.kind.command");
+        let mut commandes: Vec<Commande> = MENU_ITEMS
+            .iter()
+            .map(|(_, label, action)| Commande {
+                categorie: commande,
+                libelle: t(label).to_string(),
+                cible: Cible::Menu(*action),
+            })
+            .collect();
+        commandes.push(Commande {
+            categorie: commande,
+            libelle: Action::NextTheme.description().to_string(),
+            cible: Cible::Global(Action::NextTheme),
+        });
+        commandes.push(Commande {
+            categorie: commande,
+            libelle: Action::Help.description().to_string(),
+            cible: Cible::Global(Action::Help),
+        });
+
+        for livre in biblio.get_livres() {
+            let (cle, cible) = if livre.emprunte {
+                ("palette.return", Cible::Rendre(livre.id))
+            } else {
+                ("palette.borrow", Cible::Emprunter(livre.id))
+            };
+            commandes.push(Commande {
+                categorie: t("palette.kind.book"),
+                libelle: tf(cle, &[("titre", &livre.titre)]),
+                cible,
+            });
+            commandes.push(Commande {
+                categorie: t("palette.kind.book"),
+                libelle: tf("palette.show_book", &[("titre", &livre.titre)]),
+                cible: Cible::Livre(livre.id),
+            });
+        }
+
+        for auteur in biblio.get_auteurs() {
+            commandes.push(Commande {
+                categorie: t("palette.kind.author"),
+                libelle: tf("palette.show_author", &[("prenom", &auteur.prenom), ("nom", &auteur.nom)]),
+                cible: Cible::Auteur(auteur.id),
+            });
+        }
+
+        let mut query = TextInput::new(t("palette.search").to_string());
+        query.focused = true;
+        let mut palette = CommandPalette {
+            query,
+            commandes,
+            resultats: Vec::new(),
+            state: ListState::default(),
+            list_area: Rect::default(),
+        };
+        palette.filtrer();
+        palette
+    }
+
+    // Recalcule les résultats après chaque frappe; à score égal, l'ordre d'origine est gardé
+    fn filtrer(&mut self) {
+        let recherche = self.query.value();
+        let mut scores: Vec<(usize, u32)> = self
+            .commandes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, commande)| fuzzy::score(recherche, &commande.libelle).map(|s| (i, s)))
+            .collect();
+        scores.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+        self.resultats = scores.into_iter().map(|(i, _)| i).collect();
+        self.state = ListState::default();
+    }
+
+    fn visible_lines(&self) -> usize {
+        self.list_area.height as usize
+    }
+
+    fn move_by(&mut self, delta: isize) {
+        self.state.move_by(delta, self.resultats.len());
+        self.state.scroll_into_view(self.visible_lines());
+    }
+
+    // Exécute la commande choisie; la palette se ferme d'abord
+    fn executer(&self, index: usize, ctx: &mut Context) -> Navigation {
+        let Some(commande) = self.resultats.get(index).map(|&i| &self.commandes[i]) else {
+            return Navigation::None;
+        };
+
+        let navigation = match commande.cible {
+            Cible::Menu(action) => action(ctx),
+            Cible::Global(action) => Navigation::Global(action),
+            Cible::Emprunter(id) => operation_livre(id, ctx, Bibliotheque::emprunter_livre, apres_emprunt),
+            Cible::Rendre(id) => operation_livre(id, ctx, Bibliotheque::retourner_livre, apres_retour),
+            Cible::Livre(id) => Navigation::push(ListBooksScreen::on_book(&ctx.biblio.lock().unwrap(), id)),
+            Cible::Auteur(id) => Navigation::push(ListAuthorsScreen::on_author(&ctx.biblio.lock().unwrap(), id)),
+        };
+
+        match navigation {
+            Navigation::Push(screen) | Navigation::Replace(screen) => Navigation::Replace(screen),
+            Navigation::Quit => Navigation::Quit,
+            navigation => Navigation::Sequence(vec![Navigation::Pop, navigation]),
+        }
+    }
+}
+
+impl Screen for CommandPalette {
+    fn title(&self) -> String {
+        format!("🔎 {}", t("palette.title"))
+    }
+
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        vec![
+            (&[Action::PrevField, Action::NextField], t("help.navigate")),
+            (&[Action::Submit], t("help.run")),
+            (&[Action::Back], t("common.close")),
+        ]
+    }
+
+    // La recherche se tape comme dans un formulaire
+    fn key_context(&self) -> KeyContext {
+        KeyContext::Form
+    }
+
+    fn captures(&self, action: Action) -> bool {
+        action == Action::Palette
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, action: Option<Action>, ctx: &mut Context) -> Navigation {
+        match action {
+            Some(Action::Back | Action::Palette) => Navigation::Pop,
+            Some(Action::Submit) => self.executer(self.state.selected, ctx),
+            Some(Action::NextField) => {
+                self.move_by(1);
+                Navigation::None
+            }
+            Some(Action::PrevField) => {
+                self.move_by(-1);
+                Navigation::None
+            }
+            _ => {
+                let avant = self.query.value().to_string();
+                self.query.handle_key_event(key);
+                if self.query.value() != avant {
+                    self.filtrer();
+                }
+                Navigation::None
+            }
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str, _ctx: &mut Context) -> Navigation {
+        self.query.paste(text);
+        self.filtrer();
+        Navigation::None
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, ctx: &mut Context) -> Navigation {
+        match mouse.kind {
+            // Comme dans le menu: un clic sélectionne, un second clic exécute
+            MouseEventKind::Down(MouseButton::Left) => {
+                match self.state.item_at(self.list_area, mouse.column, mouse.row) {
+                    Some(index) if index == self.state.selected => self.executer(index, ctx),
+                    Some(index) if index < self.resultats.len() => {
+                        self.state.selected = index;
+                        Navigation::None
+                    }
+                    _ => Navigation::None,
+                }
+            }
+            MouseEventKind::ScrollDown => {
+                self.move_by(1);
+                Navigation::None
+            }
+            MouseEventKind::ScrollUp => {
+                self.move_by(-1);
+                Navigation::None
+            }
+            _ => Navigation::None,
+        }
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let theme = ctx.theme;
+        let dialog_area = centered_rect(PALETTE_WIDTH.min(area.width), PALETTE_HEIGHT.min(area.height), area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(self.title())
+            .title_style(theme.title())
+            .border_style(theme.border_focused());
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        // Recherche, résultats, aide
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        self.query.render(chunks[0], buf, theme);
+
+        self.list_area = chunks[1];
+        self.state.fit(self.resultats.len(), self.visible_lines());
+
+        let categorie_width = self
+            .commandes
+            .iter()
+            .map(|c| c.categorie.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let items: Vec<ListItem> = if self.resultats.is_empty() {
+            vec![ListItem::new(Line::from(Span::styled(t("palette.empty"), theme.dim())))]
+        } else {
+            self.resultats
+                .iter()
+                .enumerate()
+                .skip(self.state.scroll_offset)
+                .take(self.visible_lines())
+                .map(|(i, &index)| {
+                    let commande = &self.commandes[index];
+                    let style = if i == self.state.selected { theme.selected() } else { theme.text() };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!(" {:<width$}  ", commande.categorie, width = categorie_width),
+                            theme.dim(),
+                        ),
+                        Span::raw(commande.libelle.as_str()),
+                    ]))
+                    .style(style)
+                })
+                .collect()
+        };
+        List::new(items).render(chunks[1], buf);
+
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        HelpBar::new(&hints, theme).render(chunks[2], buf);
+    }
+}
@@ -0,0 +1,142 @@
+use super::{ConflitScreen, MessageScreen};
+use crate::app::keymap::{Action, KeyContext};
+use crate::app::screen::{Context, Navigation, Screen};
+use crate::i18n::{t, tf};
+use crate::services::{ErreurChargement, ErreurSauvegarde};
+use crate::ui::widgets::{Field, Form, FormValues};
+use crossterm::event::{KeyEvent, MouseEvent};
+use ratatui::{buffer::Buffer, layout::Rect};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    // Chargement d'un fichier chiffré
+    Deverrouiller,
+    // Nouvelle phrase, ou aucune pour revenir au fichier en clair
+    Changer,
+}
+
+// Phrase secrète du fichier de la bibliothèque
+pub struct PassphraseScreen {
+    form: Form,
+    fichier: String,
+    mode: Mode,
+}
+
+impl PassphraseScreen {
+    pub fn deverrouiller(fichier: &str) -> Self {
+        PassphraseScreen {
+            form: Form::new(vec![Field::password("phrase", t("passphrase.field.phrase"))]),
+            fichier: fichier.to_string(),
+            mode: Mode::Deverrouiller,
+        }
+    }
+
+    // Les deux champs vides retirent le chiffrement
+    pub fn changer(fichier: &str) -> Self {
+        PassphraseScreen {
+            form: Form::new(vec![
+                Field::password("phrase", t("passphrase.field.new")).optional(),
+                Field::password("confirmation", t("passphrase.field.confirm")).optional(),
+            ]),
+            fichier: fichier.to_string(),
+            mode: Mode::Changer,
+        }
+    }
+
+    fn deverrouiller_fichier(&mut self, phrase: &str, ctx: &mut Context) -> Navigation {
+        match ctx.biblio.lock().unwrap().ouvrir_avec_phrase(&self.fichier, phrase) {
+            Ok(()) => Navigation::replace(MessageScreen::new(
+                t("common.success"),
+                tf("menu.loaded", &[("fichier", &self.fichier)]),
+                false,
+            )),
+            Err(e @ ErreurChargement::PhraseIncorrecte) => {
+                if let Some(champ) = self.form.field_mut("phrase") {
+                    champ.set_value("");
+                }
+                self.form.set_error("phrase", e.to_string());
+                Navigation::None
+            }
+            Err(e) => Navigation::error(tf("menu.load_failed", &[("erreur", &e)])),
+        }
+    }
+
+    // Enregistré tout de suite: le fichier ne reste pas avec l'ancienne phrase
+    fn changer_phrase(&mut self, phrase: &str, ctx: &mut Context) -> Navigation {
+        let cle = if phrase.is_empty() { "passphrase.removed" } else { "passphrase.changed" };
+        match ctx.biblio.lock().unwrap().changer_phrase(&self.fichier, phrase) {
+            Ok(()) => Navigation::replace(MessageScreen::new(
+                t("common.success"),
+                tf(cle, &[("fichier", &self.fichier)]),
+                false,
+            )),
+            Err(ErreurSauvegarde::Conflit { revision }) => {
+                Navigation::replace(ConflitScreen::new(&self.fichier, revision))
+            }
+            Err(e) => Navigation::error(tf("menu.save_failed", &[("erreur", &e)])),
+        }
+    }
+
+    fn submit(&mut self, values: FormValues, ctx: &mut Context) -> Navigation {
+        let phrase = values.text("phrase");
+        match self.mode {
+            Mode::Deverrouiller => self.deverrouiller_fichier(phrase, ctx),
+            Mode::Changer if values.text("confirmation") != phrase => {
+                self.form.set_error("confirmation", t("passphrase.mismatch").to_string());
+                Navigation::None
+            }
+            Mode::Changer => self.changer_phrase(phrase, ctx),
+        }
+    }
+}
+
+impl Screen for PassphraseScreen {
+    fn title(&self) -> String {
+        let cle = match self.mode {
+            Mode::Deverrouiller => "passphrase.unlock_title",
+            Mode::Changer => "passphrase.change_title",
+        };
+        format!("🔒 {}", t(cle))
+    }
+
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        let valider = match self.mode {
+            Mode::Deverrouiller => t("help.unlock"),
+            Mode::Changer => t("help.save"),
+        };
+        vec![
+            (&[Action::NextField], t("help.next_field")),
+            (&[Action::Submit], valider),
+            (&[Action::Back], t("help.cancel")),
+        ]
+    }
+
+    fn key_context(&self) -> KeyContext {
+        KeyContext::Form
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, action: Option<Action>, ctx: &mut Context) -> Navigation {
+        match action {
+            Some(Action::Back) => Navigation::Pop,
+            _ => match self.form.handle_key_event(key, action) {
+                Some(values) => self.submit(values, ctx),
+                None => Navigation::None,
+            },
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, _ctx: &mut Context) -> Navigation {
+        self.form.handle_mouse_event(mouse);
+        Navigation::None
+    }
+
+    fn handle_paste(&mut self, text: &str, _ctx: &mut Context) -> Navigation {
+        self.form.paste(text);
+        Navigation::None
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        self.form.render_framed(&self.title(), &hints, area, buf, ctx.theme);
+    }
+}
@@ -0,0 +1,106 @@
+use super::MessageScreen;
+use crate::app::keymap::{Action, KeyContext};
+use crate::app::screen::{Context, Navigation, Screen};
+use crate::i18n::{t, tf};
+use crate::services::rapports::{self, DOSSIER_RAPPORTS, FormatRapport, Mois, TypeRapport};
+use crate::ui::widgets::{Field, FieldValue, Form, FormValues};
+use chrono::{Local, NaiveDate};
+use crossterm::event::{KeyEvent, MouseEvent};
+use ratatui::{buffer::Buffer, layout::Rect};
+use std::path::Path;
+
+// Génération d'un rapport: type, format, mois (emprunts) et dossier de sortie
+pub struct ReportScreen {
+    form: Form,
+    // Date du rapport et des retards
+    aujourdhui: NaiveDate,
+}
+
+impl ReportScreen {
+    pub fn new() -> Self {
+        let aujourdhui = Local::now().date_naive();
+        let types = TypeRapport::TOUS.iter().map(|r| r.libelle().to_string()).collect();
+        let formats = FormatRapport::TOUS.iter().map(|f| f.libelle().to_string()).collect();
+        ReportScreen {
+            form: Form::new(vec![
+                Field::choice("rapport", t("report.field.kind"), types),
+                Field::choice("format", t("report.field.format"), formats),
+                Field::text("mois", t("report.field.month"))
+                    .with_value(&Mois::de(aujourdhui).to_string())
+                    .validator(|valeur| match valeur {
+                        FieldValue::Text(texte) if Mois::analyser(texte).is_none() => {
+                            Err(t("report.invalid_month").to_string())
+                        }
+                        _ => Ok(()),
+                    }),
+                Field::text("dossier", t("report.field.folder")).with_value(DOSSIER_RAPPORTS),
+            ]),
+            aujourdhui,
+        }
+    }
+
+    fn submit(&mut self, values: FormValues, ctx: &mut Context) -> Navigation {
+        let type_rapport = TypeRapport::TOUS[values.choice("rapport")];
+        let format = FormatRapport::TOUS[values.choice("format")];
+        // Validé par le champ
+        let Some(mois) = Mois::analyser(values.text("mois")) else {
+            return Navigation::None;
+        };
+
+        let fichier = rapports::nom_fichier(type_rapport, format, mois, self.aujourdhui);
+        let chemin = Path::new(values.text("dossier")).join(fichier);
+        let b = ctx.biblio.lock().unwrap();
+        match rapports::ecrire(type_rapport, format, &b, mois, self.aujourdhui, &chemin) {
+            Ok(chemin) => Navigation::replace(MessageScreen::new(
+                t("common.success"),
+                tf("report.written", &[("fichier", &chemin.display())]),
+                false,
+            )),
+            Err(e) => Navigation::error(tf("common.error_detail", &[("erreur", &e)])),
+        }
+    }
+}
+
+impl Screen for ReportScreen {
+    fn title(&self) -> String {
+        format!("📄 {}", t("report.title"))
+    }
+
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        vec![
+            (&[Action::NextField], t("help.next_field")),
+            (&[Action::Submit], t("help.generate")),
+            (&[Action::Back], t("help.cancel")),
+            (&[Action::Help], t("help.help")),
+        ]
+    }
+
+    fn key_context(&self) -> KeyContext {
+        KeyContext::Form
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, action: Option<Action>, ctx: &mut Context) -> Navigation {
+        match action {
+            Some(Action::Back) => Navigation::Pop,
+            _ => match self.form.handle_key_event(key, action) {
+                Some(values) => self.submit(values, ctx),
+                None => Navigation::None,
+            },
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, _ctx: &mut Context) -> Navigation {
+        self.form.handle_mouse_event(mouse);
+        Navigation::None
+    }
+
+    fn handle_paste(&mut self, text: &str, _ctx: &mut Context) -> Navigation {
+        self.form.paste(text);
+        Navigation::None
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        self.form.render_framed(&self.title(), &hints, area, buf, ctx.theme);
+    }
+}
@@ -0,0 +1,328 @@
+use crate::app::keymap::{Action, KeyContext};
+use crate::app::screen::{Context, Navigation, Screen};
+use crate::i18n::{t, tf, tn};
+use crate::ui::theme::{Theme, symbols};
+use crate::ui::widgets::HelpBar;
+use crate::ui::widgets::text_input::TextInput;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Widget},
+};
+use std::time::{Duration, Instant};
+
+// Un scanner tape tout le code en quelques millisecondes: au-delà de cet écart
+// entre deux touches, c'est quelqu'un qui tape au clavier
+const SCAN_MAX_GAP: Duration = Duration::from_millis(50);
+// Les codes plus courts ne sont pas pris pour des scans (ex: un ID tapé très vite)
+const SCAN_MIN_LEN: usize = 4;
+
+// Repère la rafale de touches d'un scanner, même si du texte a été tapé avant
+#[derive(Default)]
+struct Rafale {
+    derniere_touche: Option<Instant>,
+    // Caractères reçus depuis le début de la rafale en cours
+    code: String,
+}
+
+impl Rafale {
+    fn touche(&mut self, c: char, maintenant: Instant) {
+        if !self.continue_a(maintenant) {
+            self.code.clear();
+        }
+        self.code.push(c);
+        self.derniere_touche = Some(maintenant);
+    }
+
+    // Effacement, déplacement du curseur...: ce n'est plus un scanner qui tape
+    fn interrompre(&mut self) {
+        *self = Rafale::default();
+    }
+
+    // Entrée: le code scanné si la saisie se termine par une rafale assez longue
+    fn terminer(&mut self, maintenant: Instant) -> Option<String> {
+        let code = (self.continue_a(maintenant) && self.code.chars().count() >= SCAN_MIN_LEN)
+            .then(|| self.code.clone());
+        self.interrompre();
+        code
+    }
+
+    fn continue_a(&self, maintenant: Instant) -> bool {
+        self.derniere_touche
+            .is_some_and(|derniere| maintenant.duration_since(derniere) <= SCAN_MAX_GAP)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+    Scanner,
+    Clavier,
+}
+
+enum Resultat {
+    Emprunte(String),
+    // Avec l'ID du tome suivant de la série s'il est disponible
+    Retourne(String, Option<u32>),
+    Erreur(String),
+}
+
+impl Resultat {
+    // Symbole, texte affiché, et s'il s'agit d'une erreur
+    fn afficher(&self) -> (&'static str, String, bool) {
+        match self {
+            Resultat::Emprunte(titre) => (symbols::SUCCESS, tf("scan.borrowed", &[("titre", titre)]), false),
+            Resultat::Retourne(titre, None) => (symbols::SUCCESS, tf("scan.returned", &[("titre", titre)]), false),
+            Resultat::Retourne(titre, Some(id)) => (
+                symbols::SUCCESS,
+                tf("scan.returned_next", &[("titre", titre), ("id", id)]),
+                false,
+            ),
+            Resultat::Erreur(message) => (symbols::FAILURE, message.clone(), true),
+        }
+    }
+}
+
+struct EntreeJournal {
+    code: String,
+    source: Source,
+    resultat: Resultat,
+}
+
+// Mode scan: chaque code scanné emprunte le livre s'il est disponible, le retourne
+// s'il était emprunté; un bandeau et un bip confirment, le journal garde la session
+pub struct ScanScreen {
+    input: TextInput,
+    rafale: Rafale,
+    // Du plus ancien au plus récent
+    journal: Vec<EntreeJournal>,
+}
+
+impl ScanScreen {
+    pub fn new() -> Self {
+        let mut input = TextInput::new(t("scan.field").to_string());
+        input.focused = true;
+        ScanScreen {
+            input,
+            rafale: Rafale::default(),
+            journal: Vec::new(),
+        }
+    }
+
+    fn traiter(&mut self, code: String, source: Source, ctx: &mut Context) {
+        let mut b = ctx.biblio.lock().unwrap();
+        let trouves: Vec<(u32, String, bool)> = b
+            .chercher_code(&code)
+            .iter()
+            .map(|l| (l.id, l.titre.clone(), l.emprunte))
+            .collect();
+
+        let resultat = match &trouves[..] {
+            [] => Resultat::Erreur(tf("scan.unknown", &[("code", &code)])),
+            [(id, titre, true)] => match b.retourner_livre(*id) {
+                Ok(()) => Resultat::Retourne(titre.clone(), b.tome_suivant(*id).map(|l| l.id)),
+                Err(e) => Resultat::Erreur(tf("common.error_detail", &[("erreur", &e)])),
+            },
+            [(id, titre, false)] => match b.emprunter_livre(*id) {
+                Ok(()) => Resultat::Emprunte(titre.clone()),
+                Err(e) => Resultat::Erreur(tf("common.error_detail", &[("erreur", &e)])),
+            },
+            // Plusieurs exemplaires de la même édition: impossible de savoir lequel est en main
+            exemplaires => Resultat::Erreur(tf("scan.ambiguous", &[("n", &exemplaires.len()), ("code", &code)])),
+        };
+
+        self.journal.push(EntreeJournal { code, source, resultat });
+    }
+
+    fn compter(&self, filtre: fn(&Resultat) -> bool) -> usize {
+        self.journal.iter().filter(|e| filtre(&e.resultat)).count()
+    }
+
+    fn render_bandeau(&self, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        let (texte, style) = match self.journal.last().map(|e| &e.resultat) {
+            None => (t("scan.waiting").to_string(), theme.dim()),
+            Some(resultat) => {
+                let (symbole, texte, erreur) = resultat.afficher();
+                let style = theme.status(erreur).add_modifier(Modifier::BOLD | Modifier::REVERSED);
+                (format!(" {} {} ", symbole, texte), style)
+            }
+        };
+
+        let block = Block::default().borders(Borders::ALL).border_style(theme.border());
+        Paragraph::new(Line::from(Span::styled(texte, style)))
+            .alignment(Alignment::Center)
+            .block(block)
+            .render(area, buf);
+    }
+
+    fn render_journal(&self, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        let bilan = [
+            tn("scan.log.borrowed", self.compter(|r| matches!(r, Resultat::Emprunte(_)))),
+            tn("scan.log.returned", self.compter(|r| matches!(r, Resultat::Retourne(..)))),
+            tn("scan.log.errors", self.compter(|r| matches!(r, Resultat::Erreur(_)))),
+        ];
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!("{} ({})", t("scan.log.title"), bilan.join(", ")))
+            .border_style(theme.border());
+
+        // Le plus récent en haut
+        let items: Vec<ListItem> = if self.journal.is_empty() {
+            vec![ListItem::new(Line::from(Span::styled(t("scan.log.empty"), theme.dim())))]
+        } else {
+            self.journal
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(i, entree)| {
+                    let (symbole, message, erreur) = entree.resultat.afficher();
+                    let source = match entree.source {
+                        Source::Scanner => t("scan.source.scanner"),
+                        Source::Clavier => t("scan.source.keyboard"),
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("{:>3} ", i + 1), theme.dim()),
+                        Span::styled(format!("{} ", symbole), theme.status(erreur)),
+                        Span::raw(format!("{:<17} ", entree.code)),
+                        Span::styled(format!("{:<9}", source), theme.dim()),
+                        Span::raw(message),
+                    ]))
+                })
+                .collect()
+        };
+
+        List::new(items).block(block).style(theme.text()).render(area, buf);
+    }
+}
+
+impl Screen for ScanScreen {
+    fn title(&self) -> String {
+        format!("📷 {}", t("scan.title"))
+    }
+
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        vec![
+            (&[Action::Submit], t("help.lookup")),
+            (&[Action::Back], t("common.close")),
+            (&[Action::Help], t("help.help")),
+        ]
+    }
+
+    // Les chiffres du scanner arrivent comme une saisie au clavier
+    fn key_context(&self) -> KeyContext {
+        KeyContext::Form
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, action: Option<Action>, ctx: &mut Context) -> Navigation {
+        let maintenant = Instant::now();
+        match action {
+            Some(Action::Back) => Navigation::Pop,
+            Some(Action::Submit) => {
+                let saisie = self.input.value().trim().to_string();
+                let (code, source) = match self.rafale.terminer(maintenant) {
+                    Some(code) => (code, Source::Scanner),
+                    None => (saisie, Source::Clavier),
+                };
+                self.input.clear();
+                if code.is_empty() {
+                    return Navigation::None;
+                }
+                self.traiter(code, source, ctx);
+                Navigation::Bell
+            }
+            _ => {
+                match key.code {
+                    KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        self.rafale.touche(c, maintenant)
+                    }
+                    _ => self.rafale.interrompre(),
+                }
+                self.input.handle_key_event(key);
+                Navigation::None
+            }
+        }
+    }
+
+    // Un code collé est traité comme une saisie au clavier
+    fn handle_paste(&mut self, text: &str, _ctx: &mut Context) -> Navigation {
+        self.rafale.interrompre();
+        self.input.paste(text);
+        Navigation::None
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let theme = ctx.theme;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(self.title())
+            .title_style(theme.title())
+            .border_style(theme.border_focused());
+        let inner = block.inner(chunks[0]);
+        block.render(chunks[0], buf);
+
+        // Saisie, bandeau du dernier scan, journal de la session
+        let zones = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Length(3), Constraint::Min(3)])
+            .split(inner);
+
+        self.input.render(zones[0], buf, theme);
+        self.render_bandeau(zones[1], buf, theme);
+        self.render_journal(zones[2], buf, theme);
+
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        HelpBar::new(&hints, theme).render(chunks[1], buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VITE: Duration = Duration::from_millis(5);
+    const LENT: Duration = Duration::from_millis(400);
+
+    fn taper(rafale: &mut Rafale, texte: &str, depart: Instant, ecart: Duration) -> Instant {
+        let mut instant = depart;
+        for c in texte.chars() {
+            instant += ecart;
+            rafale.touche(c, instant);
+        }
+        instant
+    }
+
+    #[test]
+    fn fast_burst_is_a_scan() {
+        let mut rafale = Rafale::default();
+        let fin = taper(&mut rafale, "9782070612758", Instant::now(), VITE);
+        assert_eq!(rafale.terminer(fin + VITE), Some("9782070612758".to_string()));
+    }
+
+    #[test]
+    fn slow_typing_is_not_a_scan() {
+        let mut rafale = Rafale::default();
+        let fin = taper(&mut rafale, "1234", Instant::now(), LENT);
+        assert_eq!(rafale.terminer(fin + VITE), None);
+
+        // Rafale trop courte, ou Entrée tapée bien après
+        let fin = taper(&mut rafale, "12", Instant::now(), VITE);
+        assert_eq!(rafale.terminer(fin + VITE), None);
+        let fin = taper(&mut rafale, "12345", Instant::now(), VITE);
+        assert_eq!(rafale.terminer(fin + LENT), None);
+    }
+
+    #[test]
+    fn scan_after_typed_text_keeps_only_the_burst() {
+        let mut rafale = Rafale::default();
+        let fin = taper(&mut rafale, "ab", Instant::now(), LENT);
+        let fin = taper(&mut rafale, "97820706", fin + LENT, VITE);
+        assert_eq!(rafale.terminer(fin + VITE), Some("97820706".to_string()));
+    }
+}
@@ -0,0 +1,377 @@
+use super::{BookDetailScreen, ListBooksScreen};
+use crate::app::keymap::Action;
+use crate::app::screen::{Context, Navigation, Screen};
+use crate::app::state::ListState;
+use crate::i18n::{t, tn};
+use crate::models::{Livre, Rayon};
+use crate::services::Bibliotheque;
+use crate::ui::theme::{Theme, symbols};
+use crate::ui::widgets::HelpBar;
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Widget},
+};
+
+// Partie de la bibliothèque à laquelle la liste des livres se limite
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FiltreEmplacement {
+    Salle(String),
+    Rayon(u32),
+}
+
+impl FiltreEmplacement {
+    pub fn contient(&self, livre: &Livre, biblio: &Bibliotheque) -> bool {
+        let Some(emplacement) = livre.emplacement else {
+            return false;
+        };
+        match self {
+            FiltreEmplacement::Rayon(id) => emplacement.rayon_id == *id,
+            FiltreEmplacement::Salle(salle) => biblio
+                .trouver_rayon(emplacement.rayon_id)
+                .is_some_and(|rayon| &rayon.salle == salle),
+        }
+    }
+
+    pub fn libelle(&self, biblio: &Bibliotheque) -> String {
+        match self {
+            FiltreEmplacement::Salle(salle) => salle.clone(),
+            FiltreEmplacement::Rayon(id) => biblio.trouver_rayon(*id).map_or(format!("#{}", id), libelle_rayon),
+        }
+    }
+}
+
+// "Salle / Rayon", la forme saisie dans le formulaire du livre
+pub fn libelle_rayon(rayon: &Rayon) -> String {
+    format!("{} / {}", rayon.salle, rayon.nom)
+}
+
+// Ce que devient l'emplacement choisi
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Destination {
+    // Filtre de la liste des livres: salles et rayons, ou tous les livres
+    Liste,
+    // Lecture d'un rayon: seuls les rayons se choisissent
+    LectureRayon,
+}
+
+struct Entree {
+    // None: tous les livres
+    filtre: Option<FiltreEmplacement>,
+    libelle: String,
+    livres: usize,
+}
+
+// Choix d'une salle ou d'un rayon, avec le nombre de livres de chacun
+pub struct LocationPickerScreen {
+    destination: Destination,
+    entrees: Vec<Entree>,
+    state: ListState,
+    // Zone des lignes lors du dernier rendu (souris)
+    list_area: Rect,
+}
+
+impl LocationPickerScreen {
+    // Ouvert depuis la liste des livres, qu'il remplace par la liste filtrée
+    pub fn filtre_liste(biblio: &Bibliotheque) -> Self {
+        Self::new(biblio, Destination::Liste)
+    }
+
+    // Ouvert depuis le menu, il laisse la place à la lecture du rayon choisi
+    pub fn lecture_rayon(biblio: &Bibliotheque) -> Self {
+        Self::new(biblio, Destination::LectureRayon)
+    }
+
+    fn new(biblio: &Bibliotheque, destination: Destination) -> Self {
+        let compter = |filtre: &FiltreEmplacement| {
+            biblio.get_livres().iter().filter(|l| filtre.contient(l, biblio)).count()
+        };
+
+        let mut entrees = Vec::new();
+        if destination == Destination::Liste {
+            entrees.push(Entree {
+                filtre: None,
+                libelle: t("shelf.picker.all").to_string(),
+                livres: biblio.get_livres().len(),
+            });
+        }
+        // Chaque salle suivie de ses rayons
+        for salle in biblio.salles() {
+            if destination == Destination::Liste {
+                let filtre = FiltreEmplacement::Salle(salle.to_string());
+                entrees.push(Entree {
+                    livres: compter(&filtre),
+                    filtre: Some(filtre),
+                    libelle: salle.to_string(),
+                });
+            }
+            for rayon in biblio.get_rayons().iter().filter(|r| r.salle == salle) {
+                let filtre = FiltreEmplacement::Rayon(rayon.id);
+                let libelle = match destination {
+                    Destination::Liste => format!("  {} {}", symbols::TREE_BRANCH, rayon.nom),
+                    Destination::LectureRayon => libelle_rayon(rayon),
+                };
+                entrees.push(Entree {
+                    livres: compter(&filtre),
+                    filtre: Some(filtre),
+                    libelle,
+                });
+            }
+        }
+
+        LocationPickerScreen {
+            destination,
+            entrees,
+            state: ListState::default(),
+            list_area: Rect::default(),
+        }
+    }
+
+    fn choisir(&self, index: usize) -> Navigation {
+        let Some(entree) = self.entrees.get(index) else {
+            return Navigation::None;
+        };
+        match (self.destination, &entree.filtre) {
+            // Ferme le choix et remplace la liste affichée dessous
+            (Destination::Liste, filtre) => Navigation::Sequence(vec![
+                Navigation::Pop,
+                Navigation::replace(ListBooksScreen::filtree(filtre.clone())),
+            ]),
+            (Destination::LectureRayon, Some(FiltreEmplacement::Rayon(id))) => {
+                Navigation::replace(ShelfReadScreen::new(*id))
+            }
+            (Destination::LectureRayon, _) => Navigation::None,
+        }
+    }
+}
+
+impl Screen for LocationPickerScreen {
+    fn title(&self) -> String {
+        match self.destination {
+            Destination::Liste => format!("🗺️  {}", t("shelf.picker.title")),
+            Destination::LectureRayon => format!("📏 {}", t("shelf.read.choose")),
+        }
+    }
+
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        vec![
+            (&[Action::Up, Action::Down], t("help.navigate")),
+            (&[Action::Select], t("help.select")),
+            (&[Action::Back], t("help.back")),
+            (&[Action::Help], t("help.help")),
+        ]
+    }
+
+    fn handle_key(&mut self, _key: KeyEvent, action: Option<Action>, _ctx: &mut Context) -> Navigation {
+        let count = self.entrees.len();
+        let page = (self.list_area.height as isize).max(1);
+        match action {
+            Some(Action::Back) => return Navigation::Pop,
+            Some(Action::Select) => return self.choisir(self.state.selected),
+            Some(Action::Down) if count > 0 => self.state.selected = (self.state.selected + 1) % count,
+            Some(Action::Up) if count > 0 => self.state.selected = (self.state.selected + count - 1) % count,
+            Some(Action::PageDown) => self.state.move_by(page, count),
+            Some(Action::PageUp) => self.state.move_by(-page, count),
+            _ => {}
+        }
+        self.state.scroll_into_view(self.list_area.height as usize);
+        Navigation::None
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, _ctx: &mut Context) -> Navigation {
+        let count = self.entrees.len();
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                match self.state.item_at(self.list_area, mouse.column, mouse.row) {
+                    // Un clic sur la ligne déjà sélectionnée la choisit
+                    Some(index) if index == self.state.selected => return self.choisir(index),
+                    Some(index) if index < count => self.state.selected = index,
+                    _ => {}
+                }
+            }
+            MouseEventKind::ScrollDown => self.state.move_by(1, count),
+            MouseEventKind::ScrollUp => self.state.move_by(-1, count),
+            _ => {}
+        }
+        self.state.scroll_into_view(self.list_area.height as usize);
+        Navigation::None
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let theme = ctx.theme;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(self.title())
+            .border_style(theme.border());
+        self.list_area = block.inner(chunks[0]);
+        let visibles = self.list_area.height as usize;
+        self.state.fit(self.entrees.len(), visibles);
+
+        let items: Vec<ListItem> = if self.entrees.is_empty() {
+            vec![ListItem::new(Line::from(Span::styled(t("shelf.picker.empty"), theme.dim())))]
+        } else {
+            self.entrees
+                .iter()
+                .enumerate()
+                .skip(self.state.scroll_offset)
+                .take(visibles)
+                .map(|(i, entree)| {
+                    let style = if i == self.state.selected { theme.selected() } else { theme.text() };
+                    ListItem::new(Line::from(vec![
+                        Span::raw(format!("{}  ", entree.libelle)),
+                        Span::styled(tn("shelf.picker.books", entree.livres), theme.dim()),
+                    ]))
+                    .style(style)
+                })
+                .collect()
+        };
+        List::new(items).block(block).render(chunks[0], buf);
+
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        HelpBar::new(&hints, theme).render(chunks[1], buf);
+    }
+}
+
+// Lecture de rayon: les livres dans l'ordre où ils sont posés, pour repérer
+// ceux dont la cote n'est pas à sa place
+pub struct ShelfReadScreen {
+    rayon_id: u32,
+    state: ListState,
+    list_area: Rect,
+}
+
+impl ShelfReadScreen {
+    pub fn new(rayon_id: u32) -> Self {
+        ShelfReadScreen {
+            rayon_id,
+            state: ListState::default(),
+            list_area: Rect::default(),
+        }
+    }
+
+    fn ligne(livre: &Livre, mal_range: bool, theme: &Theme) -> Line<'static> {
+        let position = livre.emplacement.map_or(0, |e| e.position);
+        let cote = match &livre.cote {
+            Some(cote) => Span::raw(format!("{:<14}", cote)),
+            None => Span::styled(format!("{:<14}", t("shelf.read.no_call_number")), theme.dim()),
+        };
+        let mut spans = vec![
+            Span::styled(format!("{:>4}  ", position), theme.dim()),
+            cote,
+            Span::raw(format!("{} ", livre.titre)),
+            Span::styled(format!("#{}", livre.id), theme.dim()),
+        ];
+        if mal_range {
+            spans.push(Span::styled(
+                format!("  {} {}", symbols::FAILURE, t("shelf.read.misfiled")),
+                theme.status(true),
+            ));
+        }
+        Line::from(spans)
+    }
+}
+
+impl Screen for ShelfReadScreen {
+    fn title(&self) -> String {
+        format!("📏 {}", t("shelf.read.title"))
+    }
+
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        vec![
+            (&[Action::Up, Action::Down], t("help.navigate")),
+            (&[Action::Select], t("help.details")),
+            (&[Action::Back], t("help.back")),
+            (&[Action::Help], t("help.help")),
+        ]
+    }
+
+    fn handle_key(&mut self, _key: KeyEvent, action: Option<Action>, ctx: &mut Context) -> Navigation {
+        let ids: Vec<u32> = ctx.biblio.lock().unwrap().livres_du_rayon(self.rayon_id).iter().map(|l| l.id).collect();
+        let count = ids.len();
+        let page = (self.list_area.height as isize).max(1);
+        match action {
+            Some(Action::Back) => return Navigation::Pop,
+            Some(Action::Select) => {
+                return match ids.get(self.state.selected) {
+                    Some(&id) => Navigation::push(BookDetailScreen::new(id)),
+                    None => Navigation::None,
+                };
+            }
+            Some(Action::Down) if count > 0 => self.state.selected = (self.state.selected + 1) % count,
+            Some(Action::Up) if count > 0 => self.state.selected = (self.state.selected + count - 1) % count,
+            Some(Action::PageDown) => self.state.move_by(page, count),
+            Some(Action::PageUp) => self.state.move_by(-page, count),
+            _ => {}
+        }
+        self.state.scroll_into_view(self.list_area.height as usize);
+        Navigation::None
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, ctx: &mut Context) -> Navigation {
+        let count = ctx.biblio.lock().unwrap().livres_du_rayon(self.rayon_id).len();
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let clicked = self.state.item_at(self.list_area, mouse.column, mouse.row);
+                if let Some(index) = clicked.filter(|&index| index < count) {
+                    self.state.selected = index;
+                }
+            }
+            MouseEventKind::ScrollDown => self.state.move_by(1, count),
+            MouseEventKind::ScrollUp => self.state.move_by(-1, count),
+            _ => {}
+        }
+        self.state.scroll_into_view(self.list_area.height as usize);
+        Navigation::None
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let theme = ctx.theme;
+        let b = ctx.biblio.lock().unwrap();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(area);
+
+        let livres = b.livres_du_rayon(self.rayon_id);
+        let mal_ranges = b.mal_ranges(self.rayon_id);
+        let mut titre = self.title();
+        if let Some(rayon) = b.trouver_rayon(self.rayon_id) {
+            titre = format!("{} · {}", titre, libelle_rayon(rayon));
+        }
+        titre = format!("{} ({})", titre, tn("shelf.read.misfiled_count", mal_ranges.len()));
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(titre)
+            .border_style(theme.border_focused());
+        self.list_area = block.inner(chunks[0]);
+        let visibles = self.list_area.height as usize;
+        self.state.fit(livres.len(), visibles);
+
+        let items: Vec<ListItem> = if livres.is_empty() {
+            vec![ListItem::new(Line::from(Span::styled(t("shelf.read.empty"), theme.dim())))]
+        } else {
+            livres
+                .iter()
+                .enumerate()
+                .skip(self.state.scroll_offset)
+                .take(visibles)
+                .map(|(i, livre)| {
+                    let style = if i == self.state.selected { theme.selected() } else { theme.text() };
+                    ListItem::new(Self::ligne(livre, mal_ranges.contains(&livre.id), theme)).style(style)
+                })
+                .collect()
+        };
+        List::new(items).block(block).render(chunks[0], buf);
+
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        HelpBar::new(&hints, theme).render(chunks[1], buf);
+    }
+}
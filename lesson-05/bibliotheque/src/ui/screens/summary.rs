@@ -0,0 +1,127 @@
+use super::render_dialog_frame;
+use crate::app::keymap::{Action, KeyContext};
+use crate::app::screen::{Context, Navigation, Screen};
+use crate::i18n::{t, tn};
+use crate::ui::theme::symbols;
+use crate::ui::widgets::HelpBar;
+use crossterm::event::{KeyEvent, MouseEvent, MouseEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+// Largeur du dialog; la hauteur suit le nombre de lignes
+const SUMMARY_WIDTH: u16 = 60;
+
+// Bilan d'une opération sur plusieurs livres: une ligne réussie ou en échec par livre
+pub struct SummaryScreen {
+    title: String,
+    lignes: Vec<(bool, String)>,
+    // Défilement quand le bilan dépasse la hauteur de l'écran
+    scroll: u16,
+}
+
+impl SummaryScreen {
+    pub fn new(title: &str, lignes: Vec<(bool, String)>) -> Self {
+        SummaryScreen {
+            title: title.to_string(),
+            lignes,
+            scroll: 0,
+        }
+    }
+
+    fn echecs(&self) -> usize {
+        self.lignes.iter().filter(|(ok, _)| !ok).count()
+    }
+}
+
+impl Screen for SummaryScreen {
+    fn title(&self) -> String {
+        self.title.clone()
+    }
+
+    fn help(&self) -> Vec<(&'static [Action], &'static str)> {
+        vec![
+            (&[Action::Up, Action::Down], t("help.scroll")),
+            (&[Action::Select, Action::Back], t("common.close")),
+        ]
+    }
+
+    // Liste plutôt que dialog: les flèches font défiler le bilan
+    fn key_context(&self) -> KeyContext {
+        KeyContext::List
+    }
+
+    fn handle_key(&mut self, _key: KeyEvent, action: Option<Action>, _ctx: &mut Context) -> Navigation {
+        match action {
+            Some(Action::Select | Action::Back) => Navigation::Pop,
+            Some(Action::Down) => {
+                self.scroll = self.scroll.saturating_add(1);
+                Navigation::None
+            }
+            Some(Action::Up) => {
+                self.scroll = self.scroll.saturating_sub(1);
+                Navigation::None
+            }
+            _ => Navigation::None,
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, _ctx: &mut Context) -> Navigation {
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.scroll = self.scroll.saturating_add(1),
+            MouseEventKind::ScrollUp => self.scroll = self.scroll.saturating_sub(1),
+            _ => {}
+        }
+        Navigation::None
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, ctx: &Context) {
+        let theme = ctx.theme;
+        let echecs = self.echecs();
+
+        // Cadre, total, ligne vide, lignes du bilan, ligne vide, aide
+        let height = self.lignes.len() as u16 + 6;
+        let icon = if echecs > 0 { "⚠️  " } else { "✅ " };
+        let title = format!("{}{}", icon, self.title);
+        let inner = render_dialog_frame(area, buf, theme, (SUMMARY_WIDTH, height), &title, theme.status(echecs > 0));
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let total = format!("{}, {}", tn("book.batch.ok", self.lignes.len() - echecs), tn("book.batch.failed", echecs));
+        Paragraph::new(total)
+            .alignment(Alignment::Center)
+            .style(theme.text())
+            .render(chunks[0], buf);
+
+        // Pas de défilement au-delà de la dernière ligne
+        let max_scroll = (self.lignes.len() as u16).saturating_sub(chunks[2].height);
+        self.scroll = self.scroll.min(max_scroll);
+
+        let lignes: Vec<Line> = self
+            .lignes
+            .iter()
+            .map(|(ok, texte)| {
+                let symbole = if *ok { symbols::SUCCESS } else { symbols::FAILURE };
+                Line::from(vec![
+                    Span::styled(format!(" {} ", symbole), theme.status(!ok)),
+                    Span::styled(texte.as_str(), theme.text()),
+                ])
+            })
+            .collect();
+        Paragraph::new(lignes).scroll((self.scroll, 0)).render(chunks[2], buf);
+
+        let hints = ctx.keymap.hints(self.key_context(), &self.help());
+        HelpBar::new(&hints, theme).render(chunks[3], buf);
+    }
+}
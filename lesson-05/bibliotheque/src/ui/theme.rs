@@ -1,32 +1,138 @@
-use ratatui::style::Color;
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
 
-// Couleurs du thème de l'application
-pub struct AppTheme;
+// Couleurs de l'application, choisies à l'exécution (préréglages ou fichier TOML)
+// Un champ absent d'un thème personnalisé reprend la valeur du thème sombre
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    // Le nom vient de la clé du thème dans le fichier de configuration
+    #[serde(skip)]
+    pub name: String,
 
-impl AppTheme {
-    // Couleurs principales
-    pub const PRIMARY: Color = Color::Cyan;
-    pub const SECONDARY: Color = Color::Blue;
-    pub const ACCENT: Color = Color::LightCyan;
+    // Couleurs principales: titres et sélection, bordures, touches et libellés
+    pub primary: Color,
+    pub secondary: Color,
+    pub accent: Color,
 
     // Couleurs de statut
-    pub const SUCCESS: Color = Color::Green;
-    pub const ERROR: Color = Color::Red;
-    pub const WARNING: Color = Color::Yellow;
+    pub success: Color,
+    pub error: Color,
 
     // Couleurs neutres
-    pub const BG: Color = Color::Reset;
-    pub const FG: Color = Color::White;
-    pub const DIM: Color = Color::DarkGray;
+    pub fg: Color,
+    pub dim: Color,
+    pub selection_bg: Color,
+    // Fond grisé derrière les dialogues
+    pub overlay: Color,
 
     // Couleurs sémantiques pour les livres
-    pub const AVAILABLE: Color = Color::Green;
-    pub const BORROWED: Color = Color::Red;
+    pub available: Color,
+    pub borrowed: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::dark()
+    }
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Theme {
+            name: "sombre".to_string(),
+            primary: Color::Cyan,
+            secondary: Color::Blue,
+            accent: Color::Yellow,
+            success: Color::Green,
+            error: Color::Red,
+            fg: Color::White,
+            dim: Color::DarkGray,
+            selection_bg: Color::DarkGray,
+            overlay: Color::Black,
+            available: Color::Green,
+            borrowed: Color::Red,
+        }
+    }
+
+    pub fn light() -> Self {
+        Theme {
+            name: "clair".to_string(),
+            primary: Color::Blue,
+            secondary: Color::Black,
+            accent: Color::Magenta,
+            success: Color::Green,
+            error: Color::Red,
+            fg: Color::Black,
+            dim: Color::DarkGray,
+            selection_bg: Color::Gray,
+            overlay: Color::White,
+            available: Color::Green,
+            borrowed: Color::Red,
+        }
+    }
+
+    // Contraste maximal: uniquement du blanc et des couleurs vives sur fond noir
+    pub fn high_contrast() -> Self {
+        Theme {
+            name: "contraste".to_string(),
+            primary: Color::White,
+            secondary: Color::White,
+            accent: Color::LightYellow,
+            success: Color::LightGreen,
+            error: Color::LightRed,
+            fg: Color::White,
+            dim: Color::Gray,
+            selection_bg: Color::Blue,
+            overlay: Color::Black,
+            available: Color::LightGreen,
+            borrowed: Color::LightRed,
+        }
+    }
+
+    // Thèmes intégrés, dans l'ordre du cycle
+    pub fn presets() -> Vec<Theme> {
+        vec![Theme::dark(), Theme::light(), Theme::high_contrast()]
+    }
+
+    // Bordure des listes et panneaux
+    pub fn border(&self) -> Style {
+        Style::default().fg(self.secondary)
+    }
+
+    // Bordure des formulaires et du champ actif
+    pub fn border_focused(&self) -> Style {
+        Style::default().fg(self.primary)
+    }
+
+    pub fn title(&self) -> Style {
+        Style::default().fg(self.primary).add_modifier(Modifier::BOLD)
+    }
+
+    pub fn selected(&self) -> Style {
+        self.title().bg(self.selection_bg)
+    }
+
+    pub fn text(&self) -> Style {
+        Style::default().fg(self.fg)
+    }
+
+    pub fn dim(&self) -> Style {
+        Style::default().fg(self.dim)
+    }
+
+    // Touches dans les barres d'aide, libellés des détails
+    pub fn key(&self) -> Style {
+        Style::default().fg(self.accent)
+    }
+
+    pub fn status(&self, is_error: bool) -> Style {
+        Style::default().fg(if is_error { self.error } else { self.success })
+    }
 
-    // Couleurs pour les bordures et titres
-    pub const BORDER_ACTIVE: Color = Color::Cyan;
-    pub const BORDER_INACTIVE: Color = Color::Blue;
-    pub const TITLE: Color = Color::Cyan;
+    pub fn livre(&self, emprunte: bool) -> Style {
+        Style::default().fg(if emprunte { self.borrowed } else { self.available })
+    }
 }
 
 // Constantes pour les symboles
@@ -35,4 +141,8 @@ pub mod symbols {
     pub const BULLET_FILLED: &str = "●";
     pub const TREE_BRANCH: &str = "└─";
     pub const SELECTOR: &str = ">";
+    pub const CHECKBOX_EMPTY: &str = "[ ]";
+    pub const CHECKBOX_CHECKED: &str = "[x]";
+    pub const SUCCESS: &str = "✓";
+    pub const FAILURE: &str = "✗";
 }
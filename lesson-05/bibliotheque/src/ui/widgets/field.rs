@@ -0,0 +1,742 @@
+use super::text_input::{CURSOR, TextInput};
+use crate::app::keymap::Action;
+use crate::i18n::{t, tf};
+use crate::ui::fuzzy;
+use crate::ui::theme::Theme;
+use chrono::{Datelike, Local, NaiveDate};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Position, Rect},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+use std::collections::HashMap;
+
+// Lignes de texte visibles dans un champ multiligne
+const MULTILINE_ROWS: u16 = 3;
+
+// Type d'un champ: décide de la saisie, de la validation et de la valeur renvoyée
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldKind {
+    Text,
+    Integer { min: i64, max: i64 },
+    // Entier entre 1 et l'année en cours
+    Year,
+    // ISBN-10 ou ISBN-13, tirets et espaces ignorés, clé de contrôle vérifiée
+    Isbn,
+    Email,
+    // Liste fermée, parcourue avec ←/→
+    Choice(Vec<String>),
+    // AAAA-MM-JJ ou JJ/MM/AAAA
+    Date,
+    // Entrée ajoute une ligne au lieu d'envoyer le formulaire
+    Multiline,
+    // Recherche parmi des éléments existants, avec liste de suggestions
+    Lookup(Vec<Suggestion>),
+    // Texte masqué à l'affichage, pris tel quel (espaces compris)
+    Password,
+}
+
+// Élément proposé par un champ Lookup
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub id: u32,
+    pub label: String,
+}
+
+// Valeur d'un champ Lookup
+#[derive(Debug, Clone, PartialEq)]
+pub enum Lookup {
+    Existing(u32),
+    // Aucun élément ne correspondait: le texte saisi est à créer
+    New(String),
+}
+
+// Suggestions affichées au maximum sous un champ Lookup
+const LOOKUP_MAX_ENTRIES: usize = 5;
+
+// Valeur typée d'un champ valide
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    // Texte, multiligne, e-mail; ISBN normalisé (chiffres seuls)
+    Text(String),
+    // Entier et année
+    Integer(i64),
+    // Index de l'option choisie
+    Choice(usize),
+    Date(NaiveDate),
+    Lookup(Lookup),
+    // Champ facultatif laissé vide
+    Empty,
+}
+
+// Règle supplémentaire propre à un champ, appliquée après la conversion
+pub type Validator = Box<dyn Fn(&FieldValue) -> Result<(), String>>;
+
+pub struct Field {
+    // Nom de la valeur dans FormValues
+    pub name: &'static str,
+    pub input: TextInput,
+    pub kind: FieldKind,
+    required: bool,
+    validators: Vec<Validator>,
+    // Option affichée pour un champ Choice
+    choice: usize,
+    // Erreur affichée sous le champ, après une tentative d'envoi
+    pub error: Option<String>,
+    // Lookup: état de la liste de suggestions
+    lookup: LookupState,
+}
+
+#[derive(Default)]
+struct LookupState {
+    // Suggestion retenue (Entrée, Tab ou clic)
+    selected: Option<u32>,
+    // Index des suggestions qui correspondent au texte saisi, de la meilleure à la moins bonne
+    matches: Vec<usize>,
+    // Ligne surlignée dans la liste
+    highlight: usize,
+    open: bool,
+    // Libellé de l'entrée "créer" proposée quand rien ne correspond (clé avec {nom})
+    create_key: Option<&'static str>,
+    // L'utilisateur a choisi de créer un nouvel élément
+    create: bool,
+    // Zone de la liste lors du dernier rendu (pour la souris)
+    area: Rect,
+}
+
+impl Field {
+    pub fn new(name: &'static str, label: &str, kind: FieldKind) -> Self {
+        Field {
+            name,
+            input: TextInput::new(label.to_string()),
+            kind,
+            required: true,
+            validators: Vec::new(),
+            choice: 0,
+            error: None,
+            lookup: LookupState::default(),
+        }
+    }
+
+    pub fn text(name: &'static str, label: &str) -> Self {
+        Field::new(name, label, FieldKind::Text)
+    }
+
+    pub fn integer(name: &'static str, label: &str, min: i64, max: i64) -> Self {
+        Field::new(name, label, FieldKind::Integer { min, max })
+    }
+
+    pub fn year(name: &'static str, label: &str) -> Self {
+        Field::new(name, label, FieldKind::Year)
+    }
+
+    pub fn isbn(name: &'static str, label: &str) -> Self {
+        Field::new(name, label, FieldKind::Isbn)
+    }
+
+    pub fn email(name: &'static str, label: &str) -> Self {
+        Field::new(name, label, FieldKind::Email)
+    }
+
+    pub fn choice(name: &'static str, label: &str, options: Vec<String>) -> Self {
+        Field::new(name, label, FieldKind::Choice(options))
+    }
+
+    pub fn date(name: &'static str, label: &str) -> Self {
+        Field::new(name, label, FieldKind::Date)
+    }
+
+    pub fn multiline(name: &'static str, label: &str) -> Self {
+        Field::new(name, label, FieldKind::Multiline)
+    }
+
+    pub fn lookup(name: &'static str, label: &str, suggestions: Vec<Suggestion>) -> Self {
+        Field::new(name, label, FieldKind::Lookup(suggestions))
+    }
+
+    pub fn password(name: &'static str, label: &str) -> Self {
+        Field::new(name, label, FieldKind::Password)
+    }
+
+    // Lookup: propose de créer l'élément quand rien ne correspond
+    // `cle` est une clé de traduction avec {nom}, ex: "author.create"
+    pub fn allow_create(mut self, cle: &'static str) -> Self {
+        self.lookup.create_key = Some(cle);
+        self
+    }
+
+    // Un champ facultatif vide donne FieldValue::Empty au lieu d'une erreur
+    pub fn optional(mut self) -> Self {
+        self.required = false;
+        self
+    }
+
+    pub fn validator(mut self, validator: impl Fn(&FieldValue) -> Result<(), String> + 'static) -> Self {
+        self.validators.push(Box::new(validator));
+        self
+    }
+
+    // Valeur de départ (pour un Choice: le libellé de l'option)
+    pub fn with_value(mut self, value: &str) -> Self {
+        match &self.kind {
+            FieldKind::Choice(options) => {
+                self.choice = options.iter().position(|o| o == value).unwrap_or(0);
+            }
+            _ => self.input = TextInput::with_value(self.input.label.clone(), value.to_string()),
+        }
+        self
+    }
+
+    // Remplace le texte saisi (ex: valeur préremplie); un Lookup relance sa recherche
+    pub fn set_value(&mut self, value: &str) {
+        self.input.set_value(value.to_string());
+        if matches!(self.kind, FieldKind::Lookup(_)) {
+            self.search();
+        }
+        if self.error.is_some() {
+            self.error = self.parse().err();
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.input.label
+    }
+
+    // Texte saisi (pour un Choice: l'option affichée)
+    pub fn raw_value(&self) -> &str {
+        match &self.kind {
+            FieldKind::Choice(options) => options.get(self.choice).map_or("", String::as_str),
+            _ => self.input.value(),
+        }
+    }
+
+    // Conversion dans le type du champ, puis validateurs propres au champ
+    pub fn parse(&self) -> Result<FieldValue, String> {
+        let brut = self.input.value().trim();
+        let valeur = match &self.kind {
+            FieldKind::Choice(_) => FieldValue::Choice(self.choice),
+            _ if brut.is_empty() => {
+                if self.required {
+                    return Err(t("form.required").to_string());
+                }
+                FieldValue::Empty
+            }
+            FieldKind::Text | FieldKind::Multiline => FieldValue::Text(brut.to_string()),
+            FieldKind::Integer { min, max } => FieldValue::Integer(parse_integer(brut, *min, *max)?),
+            FieldKind::Year => FieldValue::Integer(parse_integer(brut, 1, Local::now().year() as i64)?),
+            FieldKind::Isbn => FieldValue::Text(parse_isbn(brut)?),
+            FieldKind::Email => FieldValue::Text(parse_email(brut)?),
+            FieldKind::Date => FieldValue::Date(parse_date(brut)?),
+            FieldKind::Lookup(suggestions) => FieldValue::Lookup(self.parse_lookup(brut, suggestions)?),
+            FieldKind::Password => FieldValue::Text(self.input.value().to_string()),
+        };
+
+        for validator in &self.validators {
+            validator(&valeur)?;
+        }
+        Ok(valeur)
+    }
+
+    // Suggestion choisie, sinon saisie exacte d'un libellé ou d'un ID, sinon création demandée
+    fn parse_lookup(&self, brut: &str, suggestions: &[Suggestion]) -> Result<Lookup, String> {
+        if let Some(id) = self.lookup.selected {
+            return Ok(Lookup::Existing(id));
+        }
+        if self.lookup.create {
+            return Ok(Lookup::New(brut.to_string()));
+        }
+        suggestions
+            .iter()
+            .find(|s| s.label.to_lowercase() == brut.to_lowercase() || brut.parse() == Ok(s.id))
+            .map(|s| Lookup::Existing(s.id))
+            .ok_or_else(|| t("form.lookup").to_string())
+    }
+
+    // Entrée dans un champ multiligne: nouvelle ligne plutôt qu'envoi
+    pub fn takes_enter(&self) -> bool {
+        self.kind == FieldKind::Multiline
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent, action: Option<Action>) {
+        match &self.kind {
+            FieldKind::Choice(options) => {
+                let count = options.len().max(1);
+                match key.code {
+                    KeyCode::Left => self.choice = (self.choice + count - 1) % count,
+                    KeyCode::Right | KeyCode::Char(' ') => self.choice = (self.choice + 1) % count,
+                    _ => return,
+                }
+            }
+            FieldKind::Multiline if action == Some(Action::Submit) => {
+                self.input.insert_str("\n");
+            }
+            FieldKind::Lookup(_) => {
+                let avant = self.input.value().to_string();
+                self.input.handle_key_event(key);
+                if self.input.value() != avant {
+                    self.search();
+                }
+            }
+            _ => self.input.handle_key_event(key),
+        }
+
+        // Une erreur déjà affichée suit la saisie: elle disparaît dès que la valeur est correcte
+        if self.error.is_some() {
+            self.error = self.parse().err();
+        }
+    }
+
+    // Texte collé: les retours à la ligne ne sont gardés que dans un champ multiligne
+    pub fn paste(&mut self, text: &str) {
+        match &self.kind {
+            FieldKind::Choice(_) => return,
+            FieldKind::Multiline => self.input.insert_str(&text.replace("\r\n", "\n")),
+            _ => self.input.paste(text),
+        }
+        if matches!(self.kind, FieldKind::Lookup(_)) {
+            self.search();
+        }
+        if self.error.is_some() {
+            self.error = self.parse().err();
+        }
+    }
+
+    // Lookup: nouvelle recherche après chaque frappe, le choix précédent est oublié
+    fn search(&mut self) {
+        let FieldKind::Lookup(suggestions) = &self.kind else {
+            return;
+        };
+        let recherche = self.input.value().trim();
+
+        let mut scores: Vec<(usize, u32)> = suggestions
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| fuzzy::score(recherche, &s.label).map(|score| (i, score)))
+            .collect();
+        scores.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+
+        // Un ID tapé directement propose l'élément correspondant en premier
+        let par_id = recherche
+            .parse::<u32>()
+            .ok()
+            .and_then(|id| suggestions.iter().position(|s| s.id == id));
+        self.lookup.matches = par_id
+            .into_iter()
+            .chain(scores.into_iter().map(|(i, _)| i).filter(|&i| Some(i) != par_id))
+            .take(LOOKUP_MAX_ENTRIES)
+            .collect();
+        self.lookup.highlight = 0;
+        self.lookup.selected = None;
+        self.lookup.create = false;
+        self.lookup.open = !recherche.is_empty();
+    }
+
+    // Lignes de la liste: les suggestions, ou l'entrée "créer" si rien ne correspond
+    fn popup_entries(&self) -> Vec<(Option<u32>, String)> {
+        let FieldKind::Lookup(suggestions) = &self.kind else {
+            return Vec::new();
+        };
+        if self.lookup.matches.is_empty() {
+            let nom = self.input.value().trim();
+            return self
+                .lookup
+                .create_key
+                .map(|cle| (None, tf(cle, &[("nom", &nom)])))
+                .into_iter()
+                .collect();
+        }
+        self.lookup
+            .matches
+            .iter()
+            .map(|&i| (Some(suggestions[i].id), suggestions[i].label.clone()))
+            .collect()
+    }
+
+    pub fn popup_open(&self) -> bool {
+        self.lookup.open && !self.popup_entries().is_empty()
+    }
+
+    // Ferme la liste de suggestions; false si elle n'était pas ouverte
+    pub fn close_popup(&mut self) -> bool {
+        let etait_ouverte = self.popup_open();
+        self.lookup.open = false;
+        etait_ouverte
+    }
+
+    // Touches de la liste ouverte: flèches pour se déplacer, Entrée pour choisir,
+    // Tab choisit la suggestion surlignée puis passe au champ suivant (renvoie false
+    // pour laisser le formulaire avancer); la création ne se fait que par Entrée
+    pub fn handle_popup_key(&mut self, key: KeyEvent, action: Option<Action>) -> bool {
+        let entries = self.popup_entries();
+        let count = entries.len();
+        match action {
+            Some(Action::NextField) if key.code == KeyCode::Tab => {
+                if let Some((Some(_), _)) = entries.get(self.lookup.highlight) {
+                    self.pick(self.lookup.highlight);
+                }
+                self.lookup.open = false;
+                false
+            }
+            Some(Action::NextField) => {
+                self.lookup.highlight = (self.lookup.highlight + 1) % count;
+                true
+            }
+            Some(Action::PrevField) if key.code != KeyCode::BackTab => {
+                self.lookup.highlight = (self.lookup.highlight + count - 1) % count;
+                true
+            }
+            Some(Action::Submit) => {
+                self.pick(self.lookup.highlight);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn pick(&mut self, index: usize) {
+        match self.popup_entries().get(index) {
+            Some((Some(id), label)) => {
+                self.input = TextInput::with_value(self.input.label.clone(), label.clone());
+                self.lookup.selected = Some(*id);
+            }
+            Some((None, _)) => self.lookup.create = true,
+            None => return,
+        }
+        self.lookup.open = false;
+        if self.error.is_some() {
+            self.error = self.parse().err();
+        }
+    }
+
+    // Un clic sur une ligne de la liste la choisit
+    pub fn handle_popup_click(&mut self, column: u16, row: u16) -> bool {
+        let area = self.lookup.area;
+        if !self.popup_open() || !area.contains(Position::new(column, row)) || row == area.y {
+            return false;
+        }
+        self.pick((row - area.y - 1) as usize);
+        true
+    }
+
+    // Liste affichée sous le champ, par-dessus le reste du formulaire
+    pub fn render_popup(&mut self, field_area: Rect, buf: &mut Buffer, theme: &Theme) {
+        self.lookup.area = Rect::default();
+        if !self.popup_open() {
+            return;
+        }
+
+        let entries = self.popup_entries();
+        let bounds = *buf.area();
+        let y = field_area.y + 2;
+        let area = Rect {
+            x: field_area.x + 1,
+            y,
+            width: field_area.width.saturating_sub(2),
+            height: (entries.len() as u16 + 2).min(bounds.bottom().saturating_sub(y)),
+        };
+        self.lookup.area = area;
+
+        let lines: Vec<Line> = entries
+            .iter()
+            .enumerate()
+            .map(|(i, (id, label))| {
+                let style = if i == self.lookup.highlight { theme.selected() } else { theme.text() };
+                let mut spans = vec![Span::styled(format!(" {}", label), style)];
+                if let Some(id) = id {
+                    spans.push(Span::styled(format!("  #{}", id), theme.dim()));
+                }
+                Line::from(spans)
+            })
+            .collect();
+
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).border_style(theme.border_focused()))
+            .render(area, buf);
+    }
+
+    // Cadre du champ, plus une ligne pour l'erreur éventuelle
+    pub fn height(&self) -> u16 {
+        let lignes = if self.takes_enter() { MULTILINE_ROWS } else { 1 };
+        lignes + 2 + u16::from(self.error.is_some())
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer, theme: &Theme, focused: bool) {
+        self.input.focused = focused;
+        let input_area = Rect {
+            height: area.height.saturating_sub(u16::from(self.error.is_some())),
+            ..area
+        };
+
+        match &self.kind {
+            FieldKind::Choice(options) => {
+                let option = options.get(self.choice).map_or("", String::as_str);
+                let style = if focused { theme.title() } else { theme.text() };
+                let ligne = Line::from(vec![
+                    Span::styled("◀ ", theme.key()),
+                    Span::styled(option, style),
+                    Span::styled(" ▶", theme.key()),
+                ]);
+                self.input.render_content(input_area, buf, theme, Text::from(ligne), 0);
+            }
+            FieldKind::Password => {
+                let mut masque = "•".repeat(self.input.value().chars().count());
+                if focused {
+                    masque.push_str(CURSOR);
+                }
+                self.input.render_content(input_area, buf, theme, Text::from(masque), 0);
+            }
+            _ => self.input.render(input_area, buf, theme),
+        }
+
+        if let Some(error) = &self.error {
+            let error_area = Rect {
+                y: input_area.bottom(),
+                height: 1,
+                ..area
+            };
+            Paragraph::new(format!(" ⚠ {}", error))
+                .style(theme.status(true))
+                .render(error_area, buf);
+        }
+    }
+}
+
+fn parse_integer(brut: &str, min: i64, max: i64) -> Result<i64, String> {
+    let n: i64 = brut.parse().map_err(|_| t("form.integer").to_string())?;
+    if n < min || n > max {
+        return Err(tf("form.range", &[("min", &min), ("max", &max)]));
+    }
+    Ok(n)
+}
+
+// Renvoie l'ISBN sans séparateurs ("978-2-07-040850-4" -> "9782070408504")
+fn parse_isbn(brut: &str) -> Result<String, String> {
+    let isbn: String = brut.chars().filter(|c| *c != '-' && *c != ' ').collect::<String>().to_uppercase();
+    let chiffres: Vec<u32> = isbn
+        .chars()
+        .enumerate()
+        .map(|(i, c)| match c {
+            // X vaut 10, seulement en dernière position d'un ISBN-10
+            'X' if i == 9 && isbn.len() == 10 => Some(10),
+            c => c.to_digit(10),
+        })
+        .collect::<Option<_>>()
+        .ok_or_else(|| t("form.isbn").to_string())?;
+
+    let valide = match chiffres.len() {
+        10 => chiffres.iter().enumerate().map(|(i, c)| (10 - i as u32) * c).sum::<u32>() % 11 == 0,
+        13 => {
+            chiffres
+                .iter()
+                .enumerate()
+                .map(|(i, c)| if i % 2 == 0 { *c } else { 3 * c })
+                .sum::<u32>()
+                % 10
+                == 0
+        }
+        _ => return Err(t("form.isbn").to_string()),
+    };
+
+    if valide { Ok(isbn) } else { Err(t("form.isbn_checksum").to_string()) }
+}
+
+fn parse_email(brut: &str) -> Result<String, String> {
+    let invalide = || t("form.email").to_string();
+    let (local, domaine) = brut.split_once('@').ok_or_else(invalide)?;
+    let correct = !local.is_empty()
+        && domaine.contains('.')
+        && !domaine.starts_with('.')
+        && !domaine.ends_with('.')
+        && !domaine.contains('@')
+        && !brut.contains(char::is_whitespace);
+    if correct { Ok(brut.to_string()) } else { Err(invalide()) }
+}
+
+fn parse_date(brut: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(brut, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(brut, "%d/%m/%Y"))
+        .map_err(|_| t("form.date").to_string())
+}
+
+// Valeurs d'un formulaire valide, par nom de champ
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FormValues(HashMap<&'static str, FieldValue>);
+
+impl FormValues {
+    pub fn insert(&mut self, name: &'static str, value: FieldValue) {
+        self.0.insert(name, value);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&FieldValue> {
+        self.0.get(name)
+    }
+
+    // Accès typés: le formulaire garantit qu'un champ obligatoire est rempli et du
+    // bon type, une valeur manquante ici est une erreur de l'écran
+    pub fn text(&self, name: &str) -> &str {
+        match self.get(name) {
+            Some(FieldValue::Text(texte)) => texte,
+            Some(FieldValue::Empty) => "",
+            autre => panic!("champ texte '{}' attendu, trouvé {:?}", name, autre),
+        }
+    }
+
+    pub fn integer(&self, name: &str) -> i64 {
+        match self.get(name) {
+            Some(FieldValue::Integer(n)) => *n,
+            autre => panic!("champ entier '{}' attendu, trouvé {:?}", name, autre),
+        }
+    }
+
+    pub fn choice(&self, name: &str) -> usize {
+        match self.get(name) {
+            Some(FieldValue::Choice(index)) => *index,
+            autre => panic!("champ à choix '{}' attendu, trouvé {:?}", name, autre),
+        }
+    }
+
+    pub fn lookup(&self, name: &str) -> &Lookup {
+        match self.get(name) {
+            Some(FieldValue::Lookup(lookup)) => lookup,
+            autre => panic!("champ de recherche '{}' attendu, trouvé {:?}", name, autre),
+        }
+    }
+
+    // Champs facultatifs: None s'ils sont restés vides
+    pub fn optional_integer(&self, name: &str) -> Option<i64> {
+        match self.get(name) {
+            Some(FieldValue::Integer(n)) => Some(*n),
+            Some(FieldValue::Empty) => None,
+            autre => panic!("champ entier '{}' attendu, trouvé {:?}", name, autre),
+        }
+    }
+
+    pub fn optional_lookup(&self, name: &str) -> Option<&Lookup> {
+        match self.get(name) {
+            Some(FieldValue::Lookup(lookup)) => Some(lookup),
+            Some(FieldValue::Empty) => None,
+            autre => panic!("champ de recherche '{}' attendu, trouvé {:?}", name, autre),
+        }
+    }
+
+    // None si le champ (facultatif) est resté vide
+    pub fn date(&self, name: &str) -> Option<NaiveDate> {
+        match self.get(name) {
+            Some(FieldValue::Date(date)) => Some(*date),
+            Some(FieldValue::Empty) => None,
+            autre => panic!("champ date '{}' attendu, trouvé {:?}", name, autre),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::LangueDeTest;
+    use crossterm::event::KeyModifiers;
+
+    #[test]
+    fn required_and_optional_fields() {
+        let _langue = LangueDeTest::francais();
+        assert_eq!(Field::text("nom", "Nom").parse(), Err("Champ obligatoire".to_string()));
+        assert_eq!(Field::text("nom", "Nom").optional().parse(), Ok(FieldValue::Empty));
+        assert_eq!(Field::text("nom", "Nom").with_value("  Hugo ").parse(), Ok(FieldValue::Text("Hugo".into())));
+    }
+
+    #[test]
+    fn integer_range() {
+        let _langue = LangueDeTest::francais();
+        let champ = |texte| Field::integer("id", "ID", 1, 99).with_value(texte).parse();
+        assert_eq!(champ("42"), Ok(FieldValue::Integer(42)));
+        assert_eq!(champ("abc"), Err("Nombre entier attendu".to_string()));
+        assert_eq!(champ("0"), Err("Doit être entre 1 et 99".to_string()));
+        assert!(Field::year("annee", "Année").with_value("1943").parse().is_ok());
+        assert!(Field::year("annee", "Année").with_value("99999").parse().is_err());
+    }
+
+    #[test]
+    fn isbn_check_digit() {
+        let _langue = LangueDeTest::francais();
+        let champ = |texte| Field::isbn("isbn", "ISBN").with_value(texte).parse();
+        assert_eq!(champ("978-2-07-040850-4"), Ok(FieldValue::Text("9782070408504".into())));
+        assert_eq!(champ("0-306-40615-2"), Ok(FieldValue::Text("0306406152".into())));
+        assert_eq!(champ("080442957x"), Ok(FieldValue::Text("080442957X".into())));
+        assert_eq!(champ("978-2-07-040850-5"), Err("Clé de contrôle ISBN incorrecte".to_string()));
+        assert_eq!(champ("12345"), Err("ISBN invalide (10 ou 13 chiffres)".to_string()));
+    }
+
+    #[test]
+    fn email_and_date() {
+        assert!(Field::email("email", "E-mail").with_value("lecteur@exemple.fr").parse().is_ok());
+        for invalide in ["lecteur", "@exemple.fr", "a@b", "a@.fr", "a b@c.fr"] {
+            assert!(Field::email("email", "E-mail").with_value(invalide).parse().is_err(), "{}", invalide);
+        }
+
+        let date = NaiveDate::from_ymd_opt(1943, 4, 6).unwrap();
+        assert_eq!(Field::date("d", "Date").with_value("1943-04-06").parse(), Ok(FieldValue::Date(date)));
+        assert_eq!(Field::date("d", "Date").with_value("06/04/1943").parse(), Ok(FieldValue::Date(date)));
+        assert!(Field::date("d", "Date").with_value("1943-02-30").parse().is_err());
+    }
+
+    #[test]
+    fn password_is_kept_verbatim_and_masked() {
+        let _langue = LangueDeTest::francais();
+        let mut champ = Field::password("mdp", "Mot de passe").with_value(" été ");
+        assert_eq!(champ.parse(), Ok(FieldValue::Text(" été ".into())));
+        assert_eq!(Field::password("mdp", "Mot de passe").with_value("  ").parse(), Err("Champ obligatoire".to_string()));
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 20, 3));
+        champ.render(buf.area, &mut buf, &Theme::default(), false);
+        let ligne: String = (1..6).map(|x| buf[(x, 1)].symbol().to_string()).collect();
+        assert_eq!(ligne, "•••••");
+    }
+
+    #[test]
+    fn choice_cycles_with_arrows() {
+        let options = vec!["Roman".to_string(), "Essai".to_string(), "Poésie".to_string()];
+        let mut champ = Field::choice("genre", "Genre", options).with_value("Poésie");
+        assert_eq!(champ.parse(), Ok(FieldValue::Choice(2)));
+
+        champ.handle_key_event(KeyEvent::new(KeyCode::Right, KeyModifiers::NONE), None);
+        assert_eq!(champ.raw_value(), "Roman");
+        champ.handle_key_event(KeyEvent::new(KeyCode::Left, KeyModifiers::NONE), None);
+        assert_eq!(champ.parse(), Ok(FieldValue::Choice(2)));
+        // La saisie de texte n'a pas d'effet
+        champ.handle_key_event(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE), None);
+        assert_eq!(champ.raw_value(), "Poésie");
+    }
+
+    #[test]
+    fn multiline_takes_enter() {
+        let mut champ = Field::multiline("resume", "Résumé").with_value("Ligne 1");
+        champ.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE), Some(Action::Submit));
+        champ.handle_key_event(KeyEvent::new(KeyCode::Char('2'), KeyModifiers::NONE), None);
+        assert_eq!(champ.raw_value(), "Ligne 1\n2");
+        assert_eq!(champ.height(), 5);
+    }
+
+    #[test]
+    fn custom_validators_run_after_conversion() {
+        let pair = Field::integer("n", "N", 0, 100).validator(|valeur| match valeur {
+            FieldValue::Integer(n) if n % 2 == 0 => Ok(()),
+            _ => Err("Nombre pair attendu".to_string()),
+        });
+        assert_eq!(pair.with_value("3").parse(), Err("Nombre pair attendu".to_string()));
+    }
+
+    #[test]
+    fn error_is_refreshed_while_typing() {
+        let _langue = LangueDeTest::francais();
+        let mut champ = Field::integer("id", "ID", 1, 99).with_value("x");
+        champ.error = champ.parse().err();
+        assert_eq!(champ.height(), 4);
+
+        champ.handle_key_event(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE), None);
+        assert_eq!(champ.error.as_deref(), Some("Champ obligatoire"));
+        champ.handle_key_event(KeyEvent::new(KeyCode::Char('7'), KeyModifiers::NONE), None);
+        assert_eq!(champ.error, None);
+    }
+}
@@ -1,156 +1,223 @@
-use super::text_input::TextInput;
-use crossterm::event::{KeyCode, KeyEvent};
+use super::HelpBar;
+use super::field::{Field, FormValues};
+use crate::app::keymap::Action;
+use crate::ui::layout::form_layout;
+use crate::ui::theme::Theme;
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     buffer::Buffer,
-    layout::{Constraint, Direction, Layout, Rect},
-    widgets::Widget,
+    layout::{Position, Rect},
+    widgets::{Block, Borders, Widget},
 };
 
 pub struct Form {
-    pub fields: Vec<TextInput>,
+    pub fields: Vec<Field>,
     pub focused_field: usize,
+    // Zone de chaque champ lors du dernier rendu (pour la souris)
+    field_areas: Vec<Rect>,
+    // Premier champ affiché quand le formulaire est plus haut que l'écran
+    scroll: usize,
 }
 
 impl Form {
-    pub fn new(labels: Vec<String>) -> Self {
-        let fields = labels
-            .into_iter()
-            .map(|label| TextInput::new(label))
-            .collect();
-
+    pub fn new(fields: Vec<Field>) -> Self {
         Form {
             fields,
             focused_field: 0,
+            field_areas: Vec::new(),
+            scroll: 0,
         }
     }
 
-    pub fn with_values(labels: Vec<String>, values: Vec<String>) -> Self {
-        let fields = labels
-            .into_iter()
-            .zip(values.into_iter())
-            .map(|(label, value)| TextInput::with_value(label, value))
-            .collect();
-
-        Form {
-            fields,
-            focused_field: 0,
+    // Navigation entre les champs, sinon saisie dans le champ actif
+    // Submit renvoie les valeurs si tous les champs sont valides; sinon les erreurs
+    // s'affichent sous les champs et le premier champ en erreur prend le focus
+    // Back est traité par l'écran avant d'arriver ici
+    pub fn handle_key_event(&mut self, key: KeyEvent, action: Option<Action>) -> Option<FormValues> {
+        // Une liste de suggestions ouverte passe avant la navigation et l'envoi
+        if let Some(field) = self.fields.get_mut(self.focused_field)
+            && field.popup_open()
+            && field.handle_popup_key(key, action)
+        {
+            return None;
         }
-    }
 
-    pub fn handle_key_event(&mut self, key: KeyEvent) -> bool {
-        match key.code {
-            KeyCode::Tab => {
-                self.focus_next();
-                true
-            }
-            KeyCode::BackTab => {
-                self.focus_prev();
-                true
-            }
-            KeyCode::Down => {
-                self.focus_next();
-                true
-            }
-            KeyCode::Up => {
-                self.focus_prev();
-                true
-            }
+        let takes_enter = self.fields.get(self.focused_field).is_some_and(Field::takes_enter);
+        match action {
+            Some(Action::NextField) => self.focus_next(),
+            Some(Action::PrevField) => self.focus_prev(),
+            Some(Action::Submit) if !takes_enter => return self.submit(),
             _ => {
                 if let Some(field) = self.fields.get_mut(self.focused_field) {
-                    field.handle_key_event(key);
+                    field.handle_key_event(key, action);
                 }
-                false
             }
         }
+        None
     }
 
-    pub fn focus_next(&mut self) {
-        if !self.fields.is_empty() {
-            self.focused_field = (self.focused_field + 1) % self.fields.len();
+    // Texte collé dans le champ actif
+    pub fn paste(&mut self, text: &str) {
+        if let Some(field) = self.fields.get_mut(self.focused_field) {
+            field.paste(text);
         }
     }
 
-    pub fn focus_prev(&mut self) {
-        if !self.fields.is_empty() {
-            if self.focused_field == 0 {
-                self.focused_field = self.fields.len() - 1;
-            } else {
-                self.focused_field -= 1;
+    pub fn submit(&mut self) -> Option<FormValues> {
+        let mut values = FormValues::default();
+        for field in &mut self.fields {
+            match field.parse() {
+                Ok(value) => {
+                    field.error = None;
+                    values.insert(field.name, value);
+                }
+                Err(error) => field.error = Some(error),
             }
         }
+
+        match self.fields.iter().position(|field| field.error.is_some()) {
+            Some(index) => {
+                self.focused_field = index;
+                None
+            }
+            None => Some(values),
+        }
+    }
+
+    // Erreur détectée par l'écran après l'envoi (ex: règle qui dépend de la bibliothèque)
+    pub fn set_error(&mut self, name: &str, error: String) {
+        if let Some(index) = self.fields.iter().position(|field| field.name == name) {
+            self.fields[index].error = Some(error);
+            self.focused_field = index;
+        }
+    }
+
+    // Champ par son nom, sans toucher au focus (ex: réponse arrivée en arrière-plan)
+    pub fn field_mut(&mut self, name: &str) -> Option<&mut Field> {
+        self.fields.iter_mut().find(|field| field.name == name)
     }
 
-    pub fn values(&self) -> Vec<String> {
-        self.fields.iter().map(|f| f.value().to_string()).collect()
+    // Esc ferme d'abord une liste de suggestions ouverte; false s'il n'y en avait pas
+    pub fn close_popup(&mut self) -> bool {
+        self.fields
+            .get_mut(self.focused_field)
+            .is_some_and(Field::close_popup)
     }
 
-    pub fn get_value(&self, index: usize) -> Option<&str> {
-        self.fields.get(index).map(|f| f.value())
+    // Hauteur nécessaire pour afficher tous les champs (et leurs erreurs)
+    pub fn height(&self) -> u16 {
+        self.fields.iter().map(Field::height).sum()
     }
 
-    pub fn set_values(&mut self, values: Vec<String>) {
-        for (i, value) in values.into_iter().enumerate() {
-            if let Some(field) = self.fields.get_mut(i) {
-                field.set_value(value);
+    // Un clic dans un champ lui donne le focus
+    pub fn handle_mouse_event(&mut self, mouse: MouseEvent) -> bool {
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+            return false;
+        }
+
+        if let Some(field) = self.fields.get_mut(self.focused_field)
+            && field.handle_popup_click(mouse.column, mouse.row)
+        {
+            return true;
+        }
+
+        let position = Position::new(mouse.column, mouse.row);
+        match self.field_areas.iter().position(|area| area.contains(position)) {
+            Some(index) => {
+                self.focused_field = index;
+                true
             }
+            None => false,
         }
     }
 
-    pub fn clear(&mut self) {
-        for field in &mut self.fields {
-            field.clear();
+    pub fn focus_next(&mut self) {
+        if !self.fields.is_empty() {
+            self.focused_field = (self.focused_field + 1) % self.fields.len();
         }
-        self.focused_field = 0;
     }
 
-    pub fn validate(&self) -> Result<(), Vec<String>> {
-        // Validation basique: vérifier que les champs ne sont pas vides
-        let errors: Vec<String> = self
-            .fields
-            .iter()
-            .enumerate()
-            .filter_map(|(i, field)| {
-                if field.value().trim().is_empty() {
-                    Some(format!("Le champ '{}' ne peut pas être vide", field.label))
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        if errors.is_empty() {
-            Ok(())
-        } else {
-            Err(errors)
+    pub fn focus_prev(&mut self) {
+        if !self.fields.is_empty() {
+            if self.focused_field == 0 {
+                self.focused_field = self.fields.len() - 1;
+            } else {
+                self.focused_field -= 1;
+            }
         }
     }
 }
 
-impl Widget for &mut Form {
-    fn render(self, area: Rect, buf: &mut Buffer) {
+impl Form {
+    // Écran de formulaire complet: cadre titré ajusté aux champs + aide en bas
+    pub fn render_framed(
+        &mut self,
+        title: &str,
+        help: &[(String, &str)],
+        area: Rect,
+        buf: &mut Buffer,
+        theme: &Theme,
+    ) {
+        let (form_area, help_area) = form_layout(area, self.height());
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(theme.border_focused());
+
+        let inner = block.inner(form_area);
+        block.render(form_area, buf);
+
+        self.render(inner, buf, theme);
+
+        HelpBar::new(help, theme).render(help_area, buf);
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer, theme: &Theme) {
         let num_fields = self.fields.len();
         if num_fields == 0 {
             return;
         }
 
-        // Créer un layout vertical avec un espace pour chaque champ
-        let constraints: Vec<Constraint> = self
-            .fields
-            .iter()
-            .map(|_| Constraint::Length(3))
-            .collect();
+        // Trop de champs pour la hauteur disponible: on fait défiler
+        // pour que le champ actif soit entièrement visible
+        let heights: Vec<u16> = self.fields.iter().map(Field::height).collect();
+        self.scroll = self.scroll.min(self.focused_field);
+        while self.scroll < self.focused_field
+            && heights[self.scroll..=self.focused_field].iter().sum::<u16>() > area.height
+        {
+            self.scroll += 1;
+        }
+        if heights.iter().sum::<u16>() <= area.height {
+            self.scroll = 0;
+        }
 
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(constraints)
-            .split(area);
+        // Les champs empilés à partir du premier affiché; ceux qui sont au-dessus
+        // ou qui ne tiennent pas entièrement en bas n'ont pas de zone
+        self.field_areas = vec![Rect::default(); num_fields];
+        let mut y = area.y;
+        for (idx, &height) in heights.iter().enumerate().skip(self.scroll) {
+            if y + height > area.bottom() {
+                break;
+            }
+            self.field_areas[idx] = Rect::new(area.x, y, area.width, height);
+            y += height;
+        }
 
-        // Rendre chaque champ
+        // Rendre chaque champ visible
         for (idx, field) in self.fields.iter_mut().enumerate() {
-            field.focused = idx == self.focused_field;
-            if let Some(&chunk) = chunks.get(idx) {
-                field.render(chunk, buf);
+            let chunk = self.field_areas[idx];
+            if !chunk.is_empty() {
+                field.render(chunk, buf, theme, idx == self.focused_field);
             }
         }
+
+        // Suggestions du champ actif, dessinées en dernier pour recouvrir les champs suivants
+        let focused_area = self.field_areas.get(self.focused_field).copied().unwrap_or_default();
+        if let Some(field) = self.fields.get_mut(self.focused_field)
+            && !focused_area.is_empty()
+        {
+            field.render_popup(focused_area, buf, theme);
+        }
     }
 }
@@ -0,0 +1,36 @@
+use crate::ui::theme::Theme;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+// Barre d'aide "Touche:Action Touche:Action" en bas des écrans
+// Les touches viennent du keymap (voir Keymap::hints)
+pub struct HelpBar<'a> {
+    hints: &'a [(String, &'a str)],
+    theme: &'a Theme,
+}
+
+impl<'a> HelpBar<'a> {
+    pub fn new(hints: &'a [(String, &'a str)], theme: &'a Theme) -> Self {
+        HelpBar { hints, theme }
+    }
+}
+
+impl Widget for HelpBar<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut spans = Vec::new();
+        for (i, (key, action)) in self.hints.iter().enumerate() {
+            let separator = if i + 1 < self.hints.len() { " " } else { "" };
+            spans.push(Span::styled(key.as_str(), self.theme.key()));
+            spans.push(Span::raw(format!(":{}{}", action, separator)));
+        }
+
+        Paragraph::new(Line::from(spans))
+            .alignment(Alignment::Center)
+            .style(self.theme.dim())
+            .render(area, buf);
+    }
+}
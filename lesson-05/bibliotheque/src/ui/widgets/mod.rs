@@ -1,4 +1,10 @@
+// Les écrans n'utilisent pas encore tous les types de champs (ISBN, e-mail, date...)
+#[allow(dead_code)]
+pub mod field;
 pub mod form;
+pub mod help_bar;
 pub mod text_input;
 
+pub use field::{Field, FieldValue, FormValues, Lookup, Suggestion};
 pub use form::Form;
+pub use help_bar::HelpBar;
@@ -1,82 +1,307 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::ui::theme::Theme;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Color, Style},
-    text::Line,
+    style::Style,
+    text::{Line, Span, Text},
     widgets::{Block, Borders, Paragraph, Widget},
 };
-use tui_input::Input;
+use std::cell::Cell;
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
+pub const CURSOR: &str = "█";
+
+// Champ de saisie qui raisonne en graphèmes: "é" écrit e + accent combinant,
+// un emoji ou un drapeau se déplacent et s'effacent d'un seul coup
 pub struct TextInput {
-    pub input: Input,
+    value: String,
+    // Positions en octets dans `value`, toujours à une frontière de graphème
+    cursor: usize,
+    // Autre extrémité de la sélection (Shift + flèches)
+    anchor: Option<usize>,
+    // Première colonne affichée quand la ligne dépasse la largeur du champ
+    // (mise à jour au render pour garder le curseur visible)
+    offset: Cell<usize>,
     pub label: String,
     pub focused: bool,
 }
 
 impl TextInput {
     pub fn new(label: String) -> Self {
-        TextInput {
-            input: Input::default(),
-            label,
-            focused: false,
-        }
+        TextInput::with_value(label, String::new())
     }
 
     pub fn with_value(label: String, value: String) -> Self {
         TextInput {
-            input: Input::default().with_value(value),
+            cursor: value.len(),
+            value,
+            anchor: None,
+            offset: Cell::new(0),
             label,
             focused: false,
         }
     }
 
+    // Ctrl (ou Alt sur macOS) + flèches / effacement: mot par mot
+    // Shift + flèches: sélection, remplacée ou effacée par la frappe suivante
     pub fn handle_key_event(&mut self, key: KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let mot = key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+
+        // Les touches d'effacement suppriment d'abord la sélection
+        let efface = matches!(key.code, KeyCode::Backspace | KeyCode::Delete)
+            || (ctrl && key.code == KeyCode::Char('w'));
+        if efface && self.delete_selection() {
+            return;
+        }
+
         match key.code {
-            KeyCode::Char(c) => {
-                self.input.handle(tui_input::InputRequest::InsertChar(c));
+            KeyCode::Char('a') if ctrl => {
+                self.anchor = Some(0);
+                self.cursor = self.value.len();
+            }
+            KeyCode::Char('w') if ctrl => self.delete(self.mot_precedent()..self.cursor),
+            // AltGr arrive comme Ctrl + Alt sous Windows
+            KeyCode::Char(c) if !ctrl || key.modifiers.contains(KeyModifiers::ALT) => {
+                self.insert_str(c.encode_utf8(&mut [0; 4]));
             }
             KeyCode::Backspace => {
-                self.input.handle(tui_input::InputRequest::DeletePrevChar);
+                let debut = if mot { self.mot_precedent() } else { self.grapheme_precedent() };
+                self.delete(debut..self.cursor);
             }
             KeyCode::Delete => {
-                self.input.handle(tui_input::InputRequest::DeleteNextChar);
+                let fin = if mot { self.mot_suivant() } else { self.grapheme_suivant() };
+                self.delete(self.cursor..fin);
             }
             KeyCode::Left => {
-                self.input.handle(tui_input::InputRequest::GoToPrevChar);
+                let cible = match self.selection() {
+                    // Sans Shift, une sélection se replie sur son début
+                    Some(selection) if !shift => selection.start,
+                    _ if mot => self.mot_precedent(),
+                    _ => self.grapheme_precedent(),
+                };
+                self.move_to(cible, shift);
             }
             KeyCode::Right => {
-                self.input.handle(tui_input::InputRequest::GoToNextChar);
-            }
-            KeyCode::Home => {
-                self.input.handle(tui_input::InputRequest::GoToStart);
-            }
-            KeyCode::End => {
-                self.input.handle(tui_input::InputRequest::GoToEnd);
+                let cible = match self.selection() {
+                    Some(selection) if !shift => selection.end,
+                    _ if mot => self.mot_suivant(),
+                    _ => self.grapheme_suivant(),
+                };
+                self.move_to(cible, shift);
             }
+            KeyCode::Home => self.move_to(self.debut_ligne(), shift),
+            KeyCode::End => self.move_to(self.fin_ligne(), shift),
             _ => {}
         }
     }
 
     pub fn value(&self) -> &str {
-        self.input.value()
+        &self.value
     }
 
     pub fn set_value(&mut self, value: String) {
-        self.input = Input::default().with_value(value);
+        *self = TextInput {
+            focused: self.focused,
+            ..TextInput::with_value(std::mem::take(&mut self.label), value)
+        };
     }
 
     pub fn clear(&mut self) {
-        self.input = Input::default();
+        self.set_value(String::new());
+    }
+
+    // Insère au curseur, à la place de la sélection s'il y en a une
+    pub fn insert_str(&mut self, text: &str) {
+        self.delete_selection();
+        self.value.insert_str(self.cursor, text);
+        self.cursor += text.len();
+        self.realigner();
+    }
+
+    // Collage dans un champ d'une seule ligne: les retours à la ligne deviennent des espaces
+    pub fn paste(&mut self, text: &str) {
+        let ligne = text.trim_end_matches(['\r', '\n']).lines().collect::<Vec<_>>().join(" ");
+        self.insert_str(&ligne);
     }
+
+    fn selection(&self) -> Option<Range<usize>> {
+        self.anchor
+            .filter(|&anchor| anchor != self.cursor)
+            .map(|anchor| anchor.min(self.cursor)..anchor.max(self.cursor))
+    }
+
+    fn delete_selection(&mut self) -> bool {
+        match self.selection() {
+            Some(selection) => {
+                self.delete(selection);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn delete(&mut self, range: Range<usize>) {
+        self.cursor = range.start;
+        self.anchor = None;
+        self.value.replace_range(range, "");
+    }
+
+    fn move_to(&mut self, position: usize, shift: bool) {
+        if shift {
+            self.anchor.get_or_insert(self.cursor);
+        } else {
+            self.anchor = None;
+        }
+        self.cursor = position;
+    }
+
+    // Un texte modifié par collage peut contenir des graphèmes qui chevauchent le curseur
+    // (ex: accent combinant collé juste après une lettre): on se recale sur une frontière
+    fn realigner(&mut self) {
+        let mut debut = 0;
+        for grapheme in self.value.graphemes(true) {
+            let fin = debut + grapheme.len();
+            if self.cursor > debut && self.cursor < fin {
+                self.cursor = fin;
+                break;
+            }
+            debut = fin;
+        }
+    }
+
+    fn grapheme_precedent(&self) -> usize {
+        self.value[..self.cursor]
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(i, _)| i)
+    }
+
+    fn grapheme_suivant(&self) -> usize {
+        self.value[self.cursor..]
+            .graphemes(true)
+            .next()
+            .map_or(self.cursor, |grapheme| self.cursor + grapheme.len())
+    }
+
+    // Début du mot sous le curseur ou du mot précédent (espaces et ponctuation sautés)
+    fn mot_precedent(&self) -> usize {
+        self.value[..self.cursor]
+            .split_word_bound_indices()
+            .rev()
+            .find(|(_, segment)| est_mot(segment))
+            .map_or(0, |(i, _)| i)
+    }
+
+    // Fin du mot sous le curseur ou du mot suivant
+    fn mot_suivant(&self) -> usize {
+        self.value[self.cursor..]
+            .split_word_bound_indices()
+            .find(|(_, segment)| est_mot(segment))
+            .map_or(self.value.len(), |(i, segment)| self.cursor + i + segment.len())
+    }
+
+    // Home / End restent sur la ligne du curseur dans un champ multiligne
+    fn debut_ligne(&self) -> usize {
+        self.value[..self.cursor].rfind('\n').map_or(0, |i| i + 1)
+    }
+
+    fn fin_ligne(&self) -> usize {
+        self.value[self.cursor..]
+            .find('\n')
+            .map_or(self.value.len(), |i| self.cursor + i)
+    }
+}
+
+fn est_mot(segment: &str) -> bool {
+    segment.chars().any(char::is_alphanumeric)
 }
 
-impl Widget for &TextInput {
-    fn render(self, area: Rect, buf: &mut Buffer) {
+impl TextInput {
+    pub fn render(&self, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        let width = area.width.saturating_sub(2) as usize;
+        let selection = self.selection().filter(|_| self.focused);
+
+        // Chaque ligne découpée en graphèmes stylés, curseur compris
+        let mut lignes: Vec<Vec<(&str, Style)>> = Vec::new();
+        let mut cursor_line = 0;
+        let mut cursor_col = 0;
+        let mut debut = 0;
+        for ligne in self.value.split('\n') {
+            let mut cellules = Vec::new();
+            let mut col = 0;
+            for (i, grapheme) in ligne.grapheme_indices(true) {
+                let position = debut + i;
+                if self.focused && position == self.cursor {
+                    (cursor_line, cursor_col) = (lignes.len(), col);
+                    cellules.push((CURSOR, Style::default()));
+                    col += 1;
+                }
+                let style = match &selection {
+                    Some(selection) if selection.contains(&position) => theme.selected(),
+                    _ => Style::default(),
+                };
+                cellules.push((grapheme, style));
+                col += grapheme.width();
+            }
+            if self.focused && debut + ligne.len() == self.cursor {
+                (cursor_line, cursor_col) = (lignes.len(), col);
+                cellules.push((CURSOR, Style::default()));
+            }
+            lignes.push(cellules);
+            debut += ligne.len() + 1;
+        }
+
+        // Défilement horizontal: le curseur reste visible, sans vide inutile à droite
+        let offset = if self.focused {
+            let largeur_ligne: usize = lignes[cursor_line].iter().map(|(g, _)| g.width()).sum();
+            let mut offset = self.offset.get();
+            if cursor_col < offset {
+                offset = cursor_col;
+            } else if cursor_col + 1 > offset + width {
+                offset = cursor_col + 1 - width;
+            }
+            offset = offset.min(largeur_ligne.saturating_sub(width));
+            self.offset.set(offset);
+            offset
+        } else {
+            0
+        };
+
+        let text: Text = lignes
+            .into_iter()
+            .map(|cellules| {
+                let mut col = 0;
+                let spans: Vec<Span> = cellules
+                    .into_iter()
+                    .filter(|(grapheme, _)| {
+                        let visible = col >= offset;
+                        col += grapheme.width();
+                        visible
+                    })
+                    .map(|(grapheme, style)| Span::styled(grapheme, style))
+                    .collect();
+                Line::from(spans)
+            })
+            .collect();
+
+        // Texte sur plusieurs lignes: on fait défiler pour garder le curseur visible
+        let visible = area.height.saturating_sub(2);
+        let scroll = (cursor_line as u16).saturating_sub(visible.saturating_sub(1));
+
+        self.render_content(area, buf, theme, text, scroll);
+    }
+
+    // Cadre du champ autour d'un contenu quelconque (ex: option d'une liste de choix)
+    pub fn render_content(&self, area: Rect, buf: &mut Buffer, theme: &Theme, content: Text, scroll: u16) {
         let border_style = if self.focused {
-            Style::default().fg(Color::Cyan)
+            theme.border_focused()
         } else {
-            Style::default().fg(Color::DarkGray)
+            theme.dim()
         };
 
         let block = Block::default()
@@ -87,24 +312,161 @@ impl Widget for &TextInput {
         let inner = block.inner(area);
         block.render(area, buf);
 
-        // Afficher le texte avec curseur si focus
-        let text = if self.focused {
-            let cursor_pos = self.input.cursor();
-            let value = self.input.value();
+        let paragraph = Paragraph::new(content)
+            .style(theme.text())
+            .scroll((scroll, 0));
 
-            // Insertion du curseur
-            if cursor_pos < value.len() {
-                format!("{}█{}", &value[..cursor_pos], &value[cursor_pos..])
-            } else {
-                format!("{}█", value)
-            }
-        } else {
-            self.input.value().to_string()
-        };
+        paragraph.render(inner, buf);
+    }
+}
 
-        let paragraph = Paragraph::new(Line::from(text))
-            .style(Style::default().fg(Color::White));
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::theme::Theme;
 
-        paragraph.render(inner, buf);
+    fn saisie(valeur: &str) -> TextInput {
+        let mut input = TextInput::with_value("Titre".to_string(), valeur.to_string());
+        input.focused = true;
+        input
+    }
+
+    fn touche(input: &mut TextInput, code: KeyCode, modifiers: KeyModifiers) {
+        input.handle_key_event(KeyEvent::new(code, modifiers));
+    }
+
+    // Position du curseur en graphèmes depuis le début du texte
+    fn curseur(input: &TextInput) -> usize {
+        input.value[..input.cursor].graphemes(true).count()
+    }
+
+    fn selection(input: &TextInput) -> Option<&str> {
+        input.selection().map(|selection| &input.value[selection])
+    }
+
+    fn taper(input: &mut TextInput, texte: &str) {
+        for c in texte.chars() {
+            touche(input, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+    }
+
+    // Contenu de la zone de saisie (sans le cadre), ligne par ligne
+    fn afficher(input: &TextInput, width: u16) -> String {
+        let area = Rect::new(0, 0, width, 3);
+        let mut buf = Buffer::empty(area);
+        input.render(area, &mut buf, &Theme::default());
+        (1..width - 1)
+            .map(|x| buf[(x, 1)].symbol().to_string())
+            .collect::<String>()
+            .trim_end()
+            .to_string()
+    }
+
+    #[test]
+    fn accented_text_moves_and_deletes_by_grapheme() {
+        let mut input = saisie("Les Misérables");
+        for _ in 0..8 {
+            touche(&mut input, KeyCode::Left, KeyModifiers::NONE);
+        }
+        assert_eq!(curseur(&input), 6);
+        assert_eq!(afficher(&input, 30), "Les Mi█sérables");
+
+        touche(&mut input, KeyCode::Right, KeyModifiers::NONE);
+        touche(&mut input, KeyCode::Delete, KeyModifiers::NONE);
+        assert_eq!(input.value(), "Les Misrables");
+        taper(&mut input, "é");
+        touche(&mut input, KeyCode::Backspace, KeyModifiers::NONE);
+        touche(&mut input, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(input.value(), "Les Mirables");
+    }
+
+    #[test]
+    fn combining_accents_and_emoji_are_single_graphemes() {
+        // "e" + accent combinant, puis un drapeau (deux caractères régionaux)
+        let mut input = saisie("Ame\u{301}lie 🇫🇷");
+        assert_eq!(curseur(&input), 8);
+        touche(&mut input, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(input.value(), "Ame\u{301}lie ");
+
+        touche(&mut input, KeyCode::Home, KeyModifiers::NONE);
+        touche(&mut input, KeyCode::Right, KeyModifiers::NONE);
+        touche(&mut input, KeyCode::Right, KeyModifiers::NONE);
+        touche(&mut input, KeyCode::Delete, KeyModifiers::NONE);
+        assert_eq!(input.value(), "Amlie ");
+    }
+
+    #[test]
+    fn ctrl_arrows_jump_by_word() {
+        let mut input = saisie("L'Étranger, d'Albert Camus");
+        touche(&mut input, KeyCode::Left, KeyModifiers::CONTROL);
+        assert_eq!(curseur(&input), 21);
+        touche(&mut input, KeyCode::Left, KeyModifiers::CONTROL);
+        assert_eq!(curseur(&input), 12);
+        touche(&mut input, KeyCode::Left, KeyModifiers::CONTROL);
+        assert_eq!(curseur(&input), 0);
+
+        touche(&mut input, KeyCode::Right, KeyModifiers::CONTROL);
+        assert_eq!(curseur(&input), 10);
+        touche(&mut input, KeyCode::Delete, KeyModifiers::CONTROL);
+        assert_eq!(input.value(), "L'Étranger Camus");
+        touche(&mut input, KeyCode::Backspace, KeyModifiers::CONTROL);
+        assert_eq!(input.value(), " Camus");
+    }
+
+    #[test]
+    fn shift_selection_is_replaced_by_typing() {
+        let mut input = saisie("Notre-Dame de Paris");
+        touche(&mut input, KeyCode::Left, KeyModifiers::CONTROL | KeyModifiers::SHIFT);
+        assert_eq!(selection(&input), Some("Paris"));
+        taper(&mut input, "Pâques");
+        assert_eq!(input.value(), "Notre-Dame de Pâques");
+        assert_eq!(selection(&input), None);
+
+        touche(&mut input, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        touche(&mut input, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(input.value(), "");
+    }
+
+    #[test]
+    fn left_collapses_selection_to_its_start() {
+        let mut input = saisie("Zoé");
+        touche(&mut input, KeyCode::Left, KeyModifiers::SHIFT);
+        touche(&mut input, KeyCode::Left, KeyModifiers::SHIFT);
+        assert_eq!(selection(&input), Some("oé"));
+        touche(&mut input, KeyCode::Left, KeyModifiers::NONE);
+        assert_eq!((curseur(&input), selection(&input)), (1, None));
+    }
+
+    #[test]
+    fn paste_inserts_at_cursor_on_one_line() {
+        let mut input = saisie("Le  des choses");
+        for _ in 0..11 {
+            touche(&mut input, KeyCode::Left, KeyModifiers::NONE);
+        }
+        input.paste("Parti pris\r\n");
+        assert_eq!(input.value(), "Le Parti pris des choses");
+        input.paste("a\nb");
+        assert_eq!(input.value(), "Le Parti prisa b des choses");
+    }
+
+    #[test]
+    fn long_text_scrolls_to_keep_cursor_visible() {
+        let mut input = saisie("Les Misérables, tome premier: Fantine");
+        assert_eq!(afficher(&input, 12), ": Fantine█");
+
+        touche(&mut input, KeyCode::Home, KeyModifiers::NONE);
+        assert_eq!(afficher(&input, 12), "█Les Misér");
+
+        // Sans focus, le début du texte est affiché
+        input.focused = false;
+        touche(&mut input, KeyCode::End, KeyModifiers::NONE);
+        assert_eq!(afficher(&input, 12), "Les Miséra");
+    }
+
+    #[test]
+    fn wide_characters_count_double_when_scrolling() {
+        let input = saisie("吾輩は猫である");
+        // 7 idéogrammes de largeur 2 + le curseur dans 9 colonnes
+        assert_eq!(afficher(&input, 11).replace(' ', ""), "猫である█");
     }
 }